@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use clap::{
     Args,
     Parser,
@@ -23,6 +25,15 @@ pub enum Commands {
     Generate(Generate),
     #[command(short_flag = 'C')]
     Convert(Convert),
+    /// Trotterizes time evolution of a Pauli Hamiltonian
+    #[command(short_flag = 'T')]
+    Trotter(Trotter),
+    /// Computes norms and resource metrics of a Pauli Hamiltonian
+    #[command(short_flag = 'S')]
+    Stats(Stats),
+    /// Computes lowest eigenvalues of a Hamiltonian with Lanczos
+    #[command(short_flag = 'D')]
+    Diagonalize(Diagonalize),
 }
 
 #[derive(Debug, Args)]
@@ -42,6 +53,9 @@ pub struct Generate {
     /// STDOUT, if not specified
     #[arg(long, short)]
     pub output_file:       Option<String>,
+    /// Notation of Pauli strings in the output
+    #[arg(long, default_value = "dense")]
+    pub pauli_notation:    PauliNotation,
 }
 
 #[derive(Debug, Args)]
@@ -65,6 +79,108 @@ pub struct Convert {
     pub pretty_print:    bool,
     #[arg(short, long)]
     pub mapping:         Mapping,
+    /// Ordering of spin-orbitals in the input
+    #[arg(long, default_value = "interleaved")]
+    pub input_ordering:  OrbitalOrdering,
+    /// Ordering of spin-orbitals on the qubit register
+    #[arg(long, default_value = "interleaved")]
+    pub ordering:        OrbitalOrdering,
+    /// Number of spatial orbitals for the block ordering.
+    /// Required if either ordering is block
+    #[arg(long)]
+    pub num_orbitals:    Option<u32>,
+    /// Notation of Pauli strings in the output
+    #[arg(long, default_value = "dense")]
+    pub pauli_notation:  PauliNotation,
+    /// Read and map the input term by term, without holding it in memory.
+    /// Requires input format: binary, jsonl or json
+    #[arg(long, default_value = "false")]
+    pub stream:          bool,
+    /// Write unsorted partial results of at most this many terms each,
+    /// instead of accumulating the output.  Must be positive.  Requires
+    /// output format: binary or jsonl
+    #[arg(long, requires = "stream")]
+    pub partial_size:    Option<NonZeroUsize>,
+}
+
+#[derive(Debug, Args)]
+pub struct Trotter {
+    /// STDIN, if not specified
+    #[arg(long, short)]
+    pub input_file:    Option<String>,
+    #[arg(long, default_value = "json")]
+    pub input_format:  Format,
+    /// STDOUT, if not specified
+    #[arg(long, short)]
+    pub output_file:   Option<String>,
+    #[arg(long, default_value = "qasm")]
+    pub output_format: Format,
+    #[arg(long, default_value = "3")]
+    pub qasm_version:  QasmVersion,
+    /// Evolution time
+    #[arg(short, long, default_value = "1.0")]
+    pub time:          f64,
+    /// Number of Trotter steps
+    #[arg(short = 'n', long, default_value = "1")]
+    pub steps:         u32,
+    /// Order of the product formula: 1 or an even number
+    #[arg(long, default_value = "1")]
+    pub order:         u32,
+    #[arg(long, default_value = "lexicographic")]
+    pub term_ordering: TermOrdering,
+}
+
+#[derive(Debug, Args)]
+pub struct Stats {
+    /// STDIN, if not specified
+    #[arg(long, short)]
+    pub input_file:    Option<String>,
+    #[arg(long, default_value = "json")]
+    pub input_format:  Format,
+    /// STDOUT, if not specified
+    #[arg(long, short)]
+    pub output_file:   Option<String>,
+    #[arg(long, default_value = "json")]
+    pub output_format: Format,
+    /// Pretty print the output if possible
+    #[arg(short, long, default_value = "false")]
+    pub pretty_print:  bool,
+}
+
+#[derive(Debug, Args)]
+pub struct Diagonalize {
+    /// STDIN, if not specified
+    #[arg(long, short)]
+    pub input_file:      Option<String>,
+    #[arg(long, default_value = "qubits")]
+    pub input_encoding:  Encoding,
+    #[arg(long, default_value = "json")]
+    pub input_format:    Format,
+    /// STDOUT, if not specified
+    #[arg(long, short)]
+    pub output_file:     Option<String>,
+    #[arg(long, default_value = "json")]
+    pub output_format:   Format,
+    /// Pretty print the output if possible
+    #[arg(short, long, default_value = "false")]
+    pub pretty_print:    bool,
+    /// Number of lowest eigenvalues
+    #[arg(short = 'k', long, default_value = "1")]
+    pub num_eigenvalues: usize,
+    /// Number of qubits (spin-orbitals).
+    /// Inferred from the input, if not specified
+    #[arg(long)]
+    pub num_qubits:      Option<u16>,
+    /// Restrict fermionic Hamiltonian to this number of electrons
+    #[arg(long)]
+    pub num_electrons:   Option<u16>,
+    #[arg(long, default_value = "500")]
+    pub max_iterations:  usize,
+    /// Maximal number of Krylov vectors kept in memory
+    #[arg(long, default_value = "20")]
+    pub basis_size:      usize,
+    #[arg(long, default_value = "1e-10")]
+    pub tolerance:       f64,
 }
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
@@ -102,11 +218,37 @@ impl std::fmt::Display for Mapping {
     }
 }
 
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OrbitalOrdering {
+    Interleaved,
+    Block,
+}
+
+impl std::fmt::Display for OrbitalOrdering {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Format {
     Json,
     Yaml,
     Toml,
+    Qasm,
+    Openfermion,
+    Qiskit,
+    Binary,
+    Jsonl,
+    Csv,
+    Tsv,
+    Npy,
 }
 
 impl std::fmt::Display for Format {
@@ -120,3 +262,60 @@ impl std::fmt::Display for Format {
             .fmt(f)
     }
 }
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum QasmVersion {
+    #[value(name = "2")]
+    V2,
+    #[value(name = "3")]
+    V3,
+}
+
+impl std::fmt::Display for QasmVersion {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TermOrdering {
+    Lexicographic,
+    Magnitude,
+    Commuting,
+}
+
+impl std::fmt::Display for TermOrdering {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PauliNotation {
+    Dense,
+    Sparse,
+}
+
+impl std::fmt::Display for PauliNotation {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
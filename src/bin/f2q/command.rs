@@ -1,31 +1,56 @@
 use std::{
     fs::File,
     io::{
+        BufRead,
+        BufReader,
         BufWriter,
         Write,
     },
 };
 
 use f2q::{
-    code::Code,
-    terms::SumRepr,
+    code::qubits::{
+        PauliNotation,
+        WithNotation,
+    },
+    io::{
+        binary,
+        csv,
+        jsonl,
+        npy,
+        openfermion,
+        qiskit,
+    },
+    metrics::Metrics,
+    terms::{
+        FermiSum,
+        PauliSum,
+    },
+};
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
 };
-use num::Float;
-use serde::Serialize;
 
 use crate::{
     cli::{
+        self,
         Convert,
+        Diagonalize,
         Encoding,
         Format,
         Generate,
         Mapping,
+        Stats,
+        Trotter,
     },
     errors::Error,
 };
 
 mod convert;
+mod diagonalize;
 mod generate;
+mod trotter;
 
 pub fn generate(args: &Generate) -> Result<(), Error> {
     match args.encoding {
@@ -44,58 +69,323 @@ pub fn convert(args: &Convert) -> Result<(), Error> {
     Ok(())
 }
 
-fn serialize_sumrepr<T, K>(
-    repr: &SumRepr<T, K>,
+pub fn trotter(args: &Trotter) -> Result<(), Error> {
+    trotter::circuit(args)
+}
+
+pub fn stats(args: &Stats) -> Result<(), Error> {
+    let repr: PauliSum =
+        deserialize_sumrepr(args.input_file.as_deref(), args.input_format)?;
+    serialize_output(
+        &Metrics::new(&repr),
+        args.output_file.as_deref(),
+        args.output_format,
+        args.pretty_print,
+    )
+}
+
+pub fn diagonalize(args: &Diagonalize) -> Result<(), Error> {
+    match args.input_encoding {
+        Encoding::Qubits => diagonalize::qubits(args),
+        Encoding::Fermions => diagonalize::fermions(args),
+    }
+}
+
+fn pauli_notation(notation: cli::PauliNotation) -> PauliNotation {
+    match notation {
+        cli::PauliNotation::Dense => PauliNotation::Dense,
+        cli::PauliNotation::Sparse => PauliNotation::Sparse,
+    }
+}
+
+// Sums of terms that can be read and written in formats other than
+// the serde formats.
+trait SumFormat: Sized {
+    type Notated<'a>: Serialize
+    where
+        Self: 'a;
+
+    fn notated(
+        &self,
+        notation: PauliNotation,
+    ) -> Self::Notated<'_>;
+
+    fn read_format<R: BufRead>(
+        reader: R,
+        format: Format,
+    ) -> Result<Self, Error>;
+
+    fn write_format<W: Write>(
+        &self,
+        writer: W,
+        format: Format,
+    ) -> Result<(), Error>;
+}
+
+fn read_to_string<R: BufRead>(mut reader: R) -> Result<String, Error> {
+    let mut buf = String::new();
+    reader.read_to_string(&mut buf)?;
+    Ok(buf)
+}
+
+impl SumFormat for FermiSum {
+    type Notated<'a> = &'a Self;
+
+    fn notated(
+        &self,
+        _: PauliNotation,
+    ) -> Self::Notated<'_> {
+        self
+    }
+
+    fn read_format<R: BufRead>(
+        reader: R,
+        format: Format,
+    ) -> Result<Self, Error> {
+        match format {
+            Format::Openfermion => Ok(openfermion::parse_fermion_operator(
+                &read_to_string(reader)?,
+            )?),
+            Format::Binary => Ok(binary::read_sum(reader)?),
+            Format::Jsonl => Ok(jsonl::read_sum(reader)?),
+            Format::Csv => Ok(csv::read_sum(reader, csv::CSV)?),
+            Format::Tsv => Ok(csv::read_sum(reader, csv::TSV)?),
+            _ => Err(Error::CmdArgs {
+                msg: format!("cannot read fermions from format: {format}"),
+            }),
+        }
+    }
+
+    fn write_format<W: Write>(
+        &self,
+        writer: W,
+        format: Format,
+    ) -> Result<(), Error> {
+        match format {
+            Format::Openfermion => {
+                openfermion::write_fermion_operator(self, writer)?;
+                Ok(())
+            }
+            Format::Binary => {
+                binary::write_sum(self, writer)?;
+                Ok(())
+            }
+            Format::Jsonl => {
+                jsonl::write_sum(self, writer)?;
+                Ok(())
+            }
+            Format::Csv => {
+                csv::write_sum(self, writer, csv::CSV)?;
+                Ok(())
+            }
+            Format::Tsv => {
+                csv::write_sum(self, writer, csv::TSV)?;
+                Ok(())
+            }
+            Format::Npy => {
+                npy::write_integrals(self, writer)?;
+                Ok(())
+            }
+            _ => Err(Error::CmdArgs {
+                msg: format!("cannot write fermions in format: {format}"),
+            }),
+        }
+    }
+}
+
+impl SumFormat for PauliSum {
+    type Notated<'a> = WithNotation<'a, f64>;
+
+    fn notated(
+        &self,
+        notation: PauliNotation,
+    ) -> Self::Notated<'_> {
+        self.with_notation(notation)
+    }
+
+    fn read_format<R: BufRead>(
+        reader: R,
+        format: Format,
+    ) -> Result<Self, Error> {
+        match format {
+            Format::Openfermion => {
+                Ok(openfermion::parse_qubit_operator(&read_to_string(reader)?)?)
+            }
+            Format::Qiskit => Ok(qiskit::read_sparse_pauli_op(reader)?),
+            Format::Binary => Ok(binary::read_sum(reader)?),
+            Format::Jsonl => Ok(jsonl::read_sum(reader)?),
+            Format::Csv => Ok(csv::read_sum(reader, csv::CSV)?),
+            Format::Tsv => Ok(csv::read_sum(reader, csv::TSV)?),
+            _ => Err(Error::CmdArgs {
+                msg: format!("cannot read qubits from format: {format}"),
+            }),
+        }
+    }
+
+    fn write_format<W: Write>(
+        &self,
+        writer: W,
+        format: Format,
+    ) -> Result<(), Error> {
+        match format {
+            Format::Openfermion => {
+                openfermion::write_qubit_operator(self, writer)?;
+                Ok(())
+            }
+            Format::Qiskit => {
+                qiskit::write_sparse_pauli_op(self, writer)?;
+                Ok(())
+            }
+            Format::Binary => {
+                binary::write_sum(self, writer)?;
+                Ok(())
+            }
+            Format::Jsonl => {
+                jsonl::write_sum(self, writer)?;
+                Ok(())
+            }
+            Format::Csv => {
+                csv::write_sum(self, writer, csv::CSV)?;
+                Ok(())
+            }
+            Format::Tsv => {
+                csv::write_sum(self, writer, csv::TSV)?;
+                Ok(())
+            }
+            Format::Npy => {
+                npy::write_pauli_table(self, writer)?;
+                Ok(())
+            }
+            _ => Err(Error::CmdArgs {
+                msg: format!("cannot write qubits in format: {format}"),
+            }),
+        }
+    }
+}
+
+fn deserialize_sumrepr<S>(
+    input_path: Option<&str>,
+    format: Format,
+) -> Result<S, Error>
+where
+    S: DeserializeOwned + SumFormat,
+{
+    if let Some(path) = input_path {
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+        deserialize_sumrepr_from_reader(reader, format)
+    } else {
+        let stdin = std::io::stdin().lock();
+        let reader = BufReader::new(stdin);
+        deserialize_sumrepr_from_reader(reader, format)
+    }
+}
+
+fn deserialize_sumrepr_from_reader<S, R>(
+    reader: R,
+    format: Format,
+) -> Result<S, Error>
+where
+    S: DeserializeOwned + SumFormat,
+    R: BufRead,
+{
+    Ok(match format {
+        Format::Json => serde_json::from_reader(reader)?,
+        Format::Toml => toml::from_str(&read_to_string(reader)?)?,
+        Format::Yaml => serde_yaml::from_reader(reader)?,
+        _ => S::read_format(reader, format)?,
+    })
+}
+
+fn serialize_sumrepr<S>(
+    repr: &S,
+    output_path: Option<&str>,
+    format: Format,
+    pretty_print: bool,
+    notation: cli::PauliNotation,
+) -> Result<(), Error>
+where
+    S: SumFormat,
+{
+    match format {
+        Format::Json | Format::Yaml | Format::Toml => serialize_output(
+            &repr.notated(pauli_notation(notation)),
+            output_path,
+            format,
+            pretty_print,
+        ),
+        _ => {
+            if let Some(path) = output_path {
+                let file = File::create(path)?;
+                repr.write_format(BufWriter::new(file), format)
+            } else {
+                let stdout = std::io::stdout().lock();
+                repr.write_format(BufWriter::new(stdout), format)
+            }
+        }
+    }
+}
+
+fn serialize_output<S>(
+    value: &S,
     output_path: Option<&str>,
     format: Format,
     pretty_print: bool,
 ) -> Result<(), Error>
 where
-    T: Float,
-    K: Code,
-    SumRepr<T, K>: Serialize,
+    S: Serialize,
 {
     if let Some(path) = output_path {
         let file = File::create(path)?;
         let writer = BufWriter::new(file);
-        serialize_sumrepr_to_writer(repr, writer, format, pretty_print)
+        serialize_output_to_writer(value, writer, format, pretty_print)
     } else {
         let stdout = std::io::stdout().lock();
         let writer = BufWriter::new(stdout);
-        serialize_sumrepr_to_writer(repr, writer, format, pretty_print)
+        serialize_output_to_writer(value, writer, format, pretty_print)
     }
 }
 
-fn serialize_sumrepr_to_writer<T, K, W>(
-    repr: &SumRepr<T, K>,
+fn serialize_output_to_writer<S, W>(
+    value: &S,
     writer: BufWriter<W>,
     format: Format,
     pretty_print: bool,
 ) -> Result<(), Error>
 where
-    T: Float,
-    K: Code,
-    SumRepr<T, K>: Serialize,
+    S: Serialize,
     W: Write,
 {
     match format {
         Format::Json => {
             if pretty_print {
-                serde_json::to_writer_pretty(writer, repr)?;
+                serde_json::to_writer_pretty(writer, value)?;
             } else {
-                serde_json::to_writer(writer, repr)?;
+                serde_json::to_writer(writer, value)?;
             }
         }
-        Format::Yaml => serde_yaml::to_writer(writer, repr)?,
+        Format::Yaml => serde_yaml::to_writer(writer, value)?,
         Format::Toml => {
             let mut writer = writer;
             let buf = if pretty_print {
-                toml::to_string_pretty(&repr)?
+                toml::to_string_pretty(value)?
             } else {
-                toml::to_string(&repr)?
+                toml::to_string(value)?
             };
             write!(writer, "{buf}")?;
         }
+        Format::Qasm
+        | Format::Openfermion
+        | Format::Qiskit
+        | Format::Binary
+        | Format::Jsonl
+        | Format::Csv
+        | Format::Tsv
+        | Format::Npy => {
+            return Err(Error::CmdArgs {
+                msg: format!("cannot write output in format: {format}"),
+            })
+        }
     };
 
     Ok(())
@@ -1,20 +1,77 @@
+use std::{
+    fs::File,
+    io::{
+        BufRead,
+        BufReader,
+        BufWriter,
+        Write,
+    },
+};
+
 use f2q::{
-    map::JordanWigner,
+    code::{
+        fermions::{
+            Fermions,
+            OrbitalOrdering,
+        },
+        qubits::Pauli,
+    },
+    io::{
+        binary,
+        jsonl,
+    },
+    map::{
+        jordan_wigner_term,
+        JordanWigner,
+    },
     terms::{
+        FermiSum,
         PauliSum,
         Terms,
     },
 };
 
-use super::serialize_sumrepr;
+use super::{
+    deserialize_sumrepr,
+    serialize_sumrepr,
+};
 use crate::{
     cli::{
+        self,
         Convert,
         Encoding,
+        Format,
     },
     errors::Error,
 };
 
+fn orbital_ordering(
+    ordering: cli::OrbitalOrdering,
+    num_orbitals: u32,
+) -> OrbitalOrdering {
+    match ordering {
+        cli::OrbitalOrdering::Interleaved => OrbitalOrdering::Interleaved,
+        cli::OrbitalOrdering::Block => OrbitalOrdering::Block {
+            num_orbitals,
+        },
+    }
+}
+
+// Number of spatial orbitals of the block ordering, or 0 if neither ordering
+// is block.  Indices in the block ordering depend on the number of orbitals,
+// so it is never guessed from the input.
+fn block_num_orbitals(args: &Convert) -> Result<u32, Error> {
+    match (args.input_ordering, args.ordering) {
+        (
+            cli::OrbitalOrdering::Interleaved,
+            cli::OrbitalOrdering::Interleaved,
+        ) => Ok(0),
+        _ => args.num_orbitals.ok_or_else(|| Error::CmdArgs {
+            msg: "block ordering requires: --num-orbitals".to_string(),
+        }),
+    }
+}
+
 pub fn jordan_wigner(args: &Convert) -> Result<(), Error> {
     if !(args.input_encoding == Encoding::Fermions
         && args.output_encoding == Encoding::Qubits)
@@ -25,63 +82,449 @@ pub fn jordan_wigner(args: &Convert) -> Result<(), Error> {
                 .to_string(),
         });
     }
+    if args.stream {
+        return jordan_wigner_stream(args);
+    }
+    let num_orbitals = block_num_orbitals(args)?;
+
+    let in_repr: FermiSum =
+        deserialize_sumrepr(args.input_file.as_deref(), args.input_format)?;
+    let min_num_orbitals = jordan_wigner::num_orbitals(&in_repr);
+    if args.num_orbitals.is_some_and(|n| n < min_num_orbitals) {
+        return Err(Error::CmdArgs {
+            msg: format!(
+                "number of orbitals must be at least: {min_num_orbitals}"
+            ),
+        });
+    }
+    let input_ordering = orbital_ordering(args.input_ordering, num_orbitals);
+    let in_repr = if input_ordering == OrbitalOrdering::Interleaved {
+        in_repr
+    } else {
+        log::debug!("Relabel orbitals: {}", args.input_ordering);
+        in_repr.relabel(|orb| input_ordering.orbital(orb.index()))?
+    };
+    let ordering = orbital_ordering(args.ordering, num_orbitals);
 
-    let in_repr = jordan_wigner::parse_input(args)?;
     let mut out_repr = PauliSum::with_capacity(in_repr.len() * 4);
-    JordanWigner::new(&in_repr).add_to(&mut out_repr)?;
+    JordanWigner::with_ordering(&in_repr, ordering).add_to(&mut out_repr)?;
     serialize_sumrepr(
         &out_repr,
         args.output_file.as_deref(),
         args.output_format,
         args.pretty_print,
+        args.pauli_notation,
     )
 }
 
-mod jordan_wigner {
+fn jordan_wigner_stream(args: &Convert) -> Result<(), Error> {
+    if !matches!(
+        args.input_format,
+        Format::Binary | Format::Jsonl | Format::Json
+    ) {
+        return Err(Error::CmdArgs {
+            msg: "streaming requires input format: binary, jsonl or json"
+                .to_string(),
+        });
+    }
+    if args.partial_size.is_some()
+        && !matches!(args.output_format, Format::Binary | Format::Jsonl)
+    {
+        return Err(Error::CmdArgs {
+            msg: "partial results require output format: binary or jsonl"
+                .to_string(),
+        });
+    }
+    let num_orbitals = block_num_orbitals(args)?;
+
+    let reader: Box<dyn BufRead> =
+        if let Some(path) = args.input_file.as_deref() {
+            Box::new(BufReader::new(File::open(path)?))
+        } else {
+            Box::new(BufReader::new(std::io::stdin().lock()))
+        };
+    let mut output = StreamOutput::new(args, num_orbitals)?;
+    match args.input_format {
+        Format::Binary => {
+            for term in binary::Reader::<_, Fermions>::new(reader)? {
+                let (coeff, code) = term?;
+                output.add_term(coeff, code)?;
+            }
+        }
+        Format::Jsonl => {
+            for term in jsonl::Reader::<_, f64, Fermions>::new(reader) {
+                let (coeff, code) = term?;
+                output.add_term(coeff, code)?;
+            }
+        }
+        _ => json_stream::for_each_term(reader, &mut |coeff, code| {
+            output.add_term(coeff, code)
+        })?,
+    }
+
+    output.finish(args)
+}
+
+// Jordan-Wigner mapping of terms added one by one.  The result is either
+// collected into a single sum, or written in partial sums of at most
+// `partial_size` Pauli strings, each as soon as it is full.
+struct StreamOutput {
+    input_ordering: OrbitalOrdering,
+    ordering:       OrbitalOrdering,
+    // Pauli strings of a single mapped term
+    mapped:         Vec<(f64, Pauli)>,
+    sum:            PauliSum,
+    partial:        Option<(usize, PartialWriter)>,
+}
+
+impl StreamOutput {
+    fn new(
+        args: &Convert,
+        num_orbitals: u32,
+    ) -> Result<Self, Error> {
+        let partial = match args.partial_size {
+            Some(size) => Some((size.get(), PartialWriter::new(args)?)),
+            None => None,
+        };
+
+        Ok(Self {
+            input_ordering: orbital_ordering(args.input_ordering, num_orbitals),
+            ordering: orbital_ordering(args.ordering, num_orbitals),
+            mapped: Vec::new(),
+            sum: PauliSum::new(),
+            partial,
+        })
+    }
+
+    fn add_term(
+        &mut self,
+        coeff: f64,
+        code: Fermions,
+    ) -> Result<(), Error> {
+        let (coeff, code) =
+            jordan_wigner::relabel_term(coeff, code, self.input_ordering)?;
+        let Some((partial_size, writer)) = &mut self.partial else {
+            return Ok(jordan_wigner_term(
+                coeff,
+                code,
+                self.ordering,
+                &mut self.sum,
+            )?);
+        };
+
+        self.mapped.clear();
+        jordan_wigner_term(coeff, code, self.ordering, &mut self.mapped)?;
+        for &(coeff, code) in &self.mapped {
+            self.sum.add_term(code, coeff);
+            if self.sum.len() == *partial_size {
+                writer.write_sum(&self.sum)?;
+                self.sum = PauliSum::with_capacity(*partial_size);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(
+        self,
+        args: &Convert,
+    ) -> Result<(), Error> {
+        let Some((_, mut writer)) = self.partial else {
+            return serialize_sumrepr(
+                &self.sum,
+                args.output_file.as_deref(),
+                args.output_format,
+                args.pretty_print,
+                args.pauli_notation,
+            );
+        };
+        writer.write_sum(&self.sum)?;
+        writer.finish()
+    }
+}
+
+// Writer of partial sums.  The same Pauli string may appear in several
+// partial sums; they are summed up when the output is read back.
+enum PartialWriter {
+    Binary(binary::Writer<Box<dyn Write>, Pauli>),
+    Jsonl(jsonl::Writer<Box<dyn Write>, Pauli>),
+}
+
+impl PartialWriter {
+    fn new(args: &Convert) -> Result<Self, Error> {
+        let writer: Box<dyn Write> =
+            if let Some(path) = args.output_file.as_deref() {
+                Box::new(BufWriter::new(File::create(path)?))
+            } else {
+                Box::new(BufWriter::new(std::io::stdout().lock()))
+            };
+
+        Ok(if args.output_format == Format::Binary {
+            Self::Binary(binary::Writer::with_unknown_num_terms(writer)?)
+        } else {
+            Self::Jsonl(jsonl::Writer::with_header(writer)?)
+        })
+    }
+
+    fn write_sum(
+        &mut self,
+        sum: &PauliSum,
+    ) -> Result<(), Error> {
+        log::debug!("Write partial result: {} terms", sum.len());
+        for (&coeff, code) in sum.iter() {
+            match self {
+                Self::Binary(writer) => writer.write_term(coeff, code)?,
+                Self::Jsonl(writer) => writer.write_term(coeff, code)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), Error> {
+        match self {
+            Self::Binary(writer) => writer.finish()?,
+            Self::Jsonl(writer) => writer.finish()?,
+        };
+
+        Ok(())
+    }
+}
+
+// Stream terms of the JSON representation of the sum of fermionic terms:
+//
+// {"type": "sumrepr", "encoding": "fermions", "terms": [...]}
+mod json_stream {
 
     use std::{
-        fs::File,
-        io::{
-            BufRead,
-            BufReader,
-        },
+        fmt,
+        io::BufRead,
     };
 
-    use f2q::terms::FermiSum;
-
-    use crate::{
-        cli::{
-            Convert,
-            Format,
+    use f2q::code::fermions::Fermions;
+    use serde::{
+        de::{
+            DeserializeSeed,
+            Error as _,
+            IgnoredAny,
+            MapAccess,
+            SeqAccess,
+            Visitor,
         },
-        errors::Error,
+        Deserialize,
+        Deserializer,
     };
 
-    pub fn parse_input(args: &Convert) -> Result<FermiSum, Error> {
-        if let Some(path) = &args.input_file {
-            let file = File::open(path)?;
-            let reader = BufReader::new(file);
-            parse_input_reader(reader, args)
-        } else {
-            let stdin = std::io::stdin().lock();
-            let reader = BufReader::new(stdin);
-            parse_input_reader(reader, args)
+    use crate::errors::Error;
+
+    // Callback receiving terms one by one.
+    type ForEachTerm<'a> =
+        &'a mut dyn FnMut(f64, Fermions) -> Result<(), Error>;
+
+    #[derive(Deserialize)]
+    struct Term {
+        code:  Fermions,
+        value: f64,
+    }
+
+    // Passes terms to the callback.  An error of the callback is stored in
+    // `failed`, since serde errors carry only a message.
+    struct Terms<'a> {
+        f:      ForEachTerm<'a>,
+        failed: &'a mut Option<Error>,
+    }
+
+    impl<'de> DeserializeSeed<'de> for Terms<'_> {
+        type Value = ();
+
+        fn deserialize<D>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(self)
         }
     }
 
-    fn parse_input_reader<R: BufRead>(
-        reader: R,
-        args: &Convert,
-    ) -> Result<FermiSum, Error> {
-        Ok(match args.input_format {
-            Format::Json => serde_json::from_reader(reader)?,
-            Format::Toml => {
-                let mut reader = reader;
-                let mut buf = String::new();
-                reader.read_to_string(&mut buf)?;
-                toml::from_str(&buf)?
+    impl<'de> Visitor<'de> for Terms<'_> {
+        type Value = ();
+
+        fn expecting(
+            &self,
+            formatter: &mut fmt::Formatter,
+        ) -> fmt::Result {
+            write!(formatter, "sequence of objects with keys: 'code', 'value'")
+        }
+
+        fn visit_seq<A>(
+            self,
+            mut seq: A,
+        ) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            while let Some(Term {
+                code,
+                value,
+            }) = seq.next_element()?
+            {
+                if let Err(e) = (self.f)(value, code) {
+                    *self.failed = Some(e);
+                    return Err(A::Error::custom("term rejected"));
+                }
             }
-            Format::Yaml => serde_yaml::from_reader(reader)?,
+
+            Ok(())
+        }
+    }
+
+    struct SumRepr<'a>(Terms<'a>);
+
+    impl<'de> DeserializeSeed<'de> for SumRepr<'_> {
+        type Value = ();
+
+        fn deserialize<D>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_map(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for SumRepr<'_> {
+        type Value = ();
+
+        fn expecting(
+            &self,
+            formatter: &mut fmt::Formatter,
+        ) -> fmt::Result {
+            write!(formatter, "object with keys: 'type', 'encoding', 'terms'")
+        }
+
+        fn visit_map<A>(
+            self,
+            mut map: A,
+        ) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut seen = (false, false, false);
+            let mut terms = Some(self.0);
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "type" => {
+                        if map.next_value::<String>()? != "sumrepr" {
+                            return Err(A::Error::custom(
+                                "type should be: 'sumrepr'",
+                            ));
+                        }
+                        seen.0 = true;
+                    }
+                    "encoding" => {
+                        if map.next_value::<String>()? != "fermions" {
+                            return Err(A::Error::custom(
+                                "encoding should be: 'fermions'",
+                            ));
+                        }
+                        seen.1 = true;
+                    }
+                    "terms" => {
+                        let terms = terms.take().ok_or_else(|| {
+                            A::Error::duplicate_field("terms")
+                        })?;
+                        map.next_value_seed(terms)?;
+                        seen.2 = true;
+                    }
+                    _ => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            match seen {
+                (false, ..) => Err(A::Error::missing_field("type")),
+                (_, false, _) => Err(A::Error::missing_field("encoding")),
+                (.., false) => Err(A::Error::missing_field("terms")),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Pass terms to `f` as they are read, without holding the sum in
+    /// memory.
+    pub fn for_each_term<R>(
+        reader: R,
+        f: ForEachTerm,
+    ) -> Result<(), Error>
+    where
+        R: BufRead,
+    {
+        let mut failed = None;
+        let mut de = serde_json::Deserializer::from_reader(reader);
+        let result = SumRepr(Terms {
+            f,
+            failed: &mut failed,
+        })
+        .deserialize(&mut de)
+        .and_then(|()| de.end());
+        if let Some(e) = failed {
+            return Err(e);
+        }
+
+        result.map_err(|e| Error::Serde {
+            msg: format!("{e}"),
         })
     }
 }
+
+mod jordan_wigner {
+
+    use f2q::{
+        code::fermions::{
+            Fermions,
+            OrbitalOrdering,
+        },
+        terms::FermiSum,
+        Error,
+    };
+
+    /// Number of spatial orbitals needed to accommodate all terms.
+    pub fn num_orbitals(repr: &FermiSum) -> u32 {
+        repr.iter()
+            .filter_map(|(_, code)| code.max_index())
+            .max()
+            .map_or(0, |index| index / 2 + 1)
+    }
+
+    /// Interpret indices of a single term as given in `ordering`.
+    pub fn relabel_term(
+        coeff: f64,
+        code: Fermions,
+        ordering: OrbitalOrdering,
+    ) -> Result<(f64, Fermions), Error> {
+        let OrbitalOrdering::Block {
+            num_orbitals,
+        } = ordering
+        else {
+            return Ok((coeff, code));
+        };
+        if code
+            .max_index()
+            .is_some_and(|index| ordering.checked_orbital(index).is_none())
+        {
+            return Err(Error::QubitIndex {
+                msg: format!(
+                    "term does not fit into {num_orbitals} spatial orbitals"
+                ),
+            });
+        }
+        code.relabel(coeff, |orb| ordering.orbital(orb.index()))
+            .ok_or(Error::QubitIndex {
+                msg: "relabeled orbitals must be distinct".to_string(),
+            })
+    }
+}
@@ -0,0 +1,114 @@
+use f2q::{
+    lanczos::{
+        Eigen,
+        Lanczos,
+    },
+    map::JordanWigner,
+    terms::{
+        FermiSum,
+        PauliSum,
+        SumRepr,
+        Terms,
+    },
+};
+use serde::Serialize;
+
+use super::{
+    deserialize_sumrepr,
+    serialize_output,
+};
+use crate::{
+    cli::Diagonalize,
+    errors::Error,
+    log_or_eprintln_if_verbose,
+};
+
+#[derive(Serialize)]
+struct Output {
+    eigenvalues: Vec<f64>,
+}
+
+fn solver(args: &Diagonalize) -> Lanczos {
+    Lanczos {
+        num_eigenvalues: args.num_eigenvalues,
+        max_iterations: args.max_iterations,
+        basis_size: args.basis_size,
+        tolerance: args.tolerance,
+        ..Default::default()
+    }
+}
+
+fn num_qubits(
+    args: &Diagonalize,
+    min_num_qubits: u16,
+) -> Result<u16, Error> {
+    let num_qubits = args.num_qubits.unwrap_or(min_num_qubits);
+    if num_qubits < min_num_qubits {
+        return Err(Error::CmdArgs {
+            msg: format!("number of qubits must be at least: {min_num_qubits}"),
+        });
+    }
+    log_or_eprintln_if_verbose(&format!("Number of qubits: {num_qubits}"));
+
+    Ok(num_qubits)
+}
+
+fn write_output(
+    args: &Diagonalize,
+    eigen: Eigen,
+) -> Result<(), Error> {
+    serialize_output(
+        &Output {
+            eigenvalues: eigen.values,
+        },
+        args.output_file.as_deref(),
+        args.output_format,
+        args.pretty_print,
+    )
+}
+
+pub fn qubits(args: &Diagonalize) -> Result<(), Error> {
+    if args.num_electrons.is_some() {
+        return Err(Error::CmdArgs {
+            msg: "number of electrons requires fermions encoding".to_string(),
+        });
+    }
+
+    let repr: PauliSum =
+        deserialize_sumrepr(args.input_file.as_deref(), args.input_format)?;
+    let min_num_qubits = repr
+        .iter()
+        .map(|(_, code)| u16::from(code.min_register_size()))
+        .max()
+        .unwrap_or(0);
+    let num_qubits = num_qubits(args, min_num_qubits)?;
+    let eigen = solver(args).solve(&repr, num_qubits)?;
+
+    write_output(args, eigen)
+}
+
+pub fn fermions(args: &Diagonalize) -> Result<(), Error> {
+    let repr: FermiSum =
+        deserialize_sumrepr(args.input_file.as_deref(), args.input_format)?;
+    // Jordan-Wigner maps spin-orbital i to qubit i.
+    let min_num_qubits = repr
+        .iter()
+        .filter_map(|(_, code)| code.max_index())
+        .max()
+        .map_or(Ok(0), |index| {
+            u16::try_from(u64::from(index) + 1).map_err(|_| Error::CmdArgs {
+                msg: format!("orbital index too large: {index}"),
+            })
+        })?;
+    let num_qubits = num_qubits(args, min_num_qubits)?;
+
+    let eigen = if let Some(num_electrons) = args.num_electrons {
+        solver(args).solve_fermions(&repr, num_qubits, num_electrons)?
+    } else {
+        let mut qubit_repr = SumRepr::with_capacity(repr.len() * 4);
+        JordanWigner::new(&repr).add_to(&mut qubit_repr)?;
+        solver(args).solve(&qubit_repr, num_qubits)?
+    };
+
+    write_output(args, eigen)
+}
@@ -83,6 +83,7 @@ fn fermions_random(args: &Generate) -> Result<(), Error> {
         args.output_file.as_deref(),
         args.format,
         args.pretty_print,
+        args.pauli_notation,
     )
 }
 
@@ -113,5 +114,6 @@ fn qubits_random(args: &Generate) -> Result<(), Error> {
         args.output_file.as_deref(),
         args.format,
         args.pretty_print,
+        args.pauli_notation,
     )
 }
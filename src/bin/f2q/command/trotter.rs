@@ -0,0 +1,92 @@
+use std::{
+    fs::File,
+    io::BufWriter,
+};
+
+use f2q::{
+    circuit,
+    terms::PauliSum,
+    trotter::{
+        self,
+        Formula,
+        Trotter,
+    },
+};
+
+use super::deserialize_sumrepr;
+use crate::{
+    cli::{
+        self,
+        Format,
+        QasmVersion,
+        TermOrdering,
+    },
+    errors::Error,
+    log_or_eprintln_if_verbose,
+};
+
+fn formula(order: u32) -> Result<Formula, Error> {
+    match order {
+        1 => Ok(Formula::First),
+        k if k > 0 && k % 2 == 0 => Ok(Formula::Suzuki(k / 2)),
+        _ => Err(Error::CmdArgs {
+            msg: format!("order must be 1 or a positive even number: {order}"),
+        }),
+    }
+}
+
+fn term_ordering(ordering: TermOrdering) -> trotter::TermOrdering {
+    match ordering {
+        TermOrdering::Lexicographic => trotter::TermOrdering::Lexicographic,
+        TermOrdering::Magnitude => trotter::TermOrdering::Magnitude,
+        TermOrdering::Commuting => trotter::TermOrdering::Commuting,
+    }
+}
+
+fn qasm_version(version: QasmVersion) -> circuit::QasmVersion {
+    match version {
+        QasmVersion::V2 => circuit::QasmVersion::V2,
+        QasmVersion::V3 => circuit::QasmVersion::V3,
+    }
+}
+
+pub fn circuit(args: &cli::Trotter) -> Result<(), Error> {
+    if args.output_format != Format::Qasm {
+        return Err(Error::CmdArgs {
+            msg: format!(
+                "Trotter circuit cannot be written in format: {}",
+                args.output_format
+            ),
+        });
+    }
+    let trotter = Trotter::with_ordering(
+        formula(args.order)?,
+        args.steps,
+        term_ordering(args.term_ordering),
+    )
+    .ok_or_else(|| Error::CmdArgs {
+        msg: "number of steps must be positive".to_string(),
+    })?;
+
+    let repr: PauliSum =
+        deserialize_sumrepr(args.input_file.as_deref(), args.input_format)?;
+    let circuit = trotter.circuit(&repr, args.time)?;
+
+    let counts = circuit.gate_counts();
+    log_or_eprintln_if_verbose(&format!(
+        "Gate counts: single-qubit: {}, two-qubit: {}, rotations: {}, depth: \
+         {}",
+        counts.single_qubit, counts.two_qubit, counts.rotations, counts.depth
+    ));
+
+    let version = qasm_version(args.qasm_version);
+    if let Some(path) = &args.output_file {
+        let file = File::create(path)?;
+        circuit.write_qasm(BufWriter::new(file), version, false)?;
+    } else {
+        let stdout = std::io::stdout().lock();
+        circuit.write_qasm(BufWriter::new(stdout), version, false)?;
+    }
+
+    Ok(())
+}
@@ -52,5 +52,8 @@ fn main_exec(cli: &Cli) -> Result<(), Error> {
     match &cli.command {
         Commands::Generate(args) => command::generate(args),
         Commands::Convert(args) => command::convert(args),
+        Commands::Trotter(args) => command::trotter(args),
+        Commands::Stats(args) => command::stats(args),
+        Commands::Diagonalize(args) => command::diagonalize(args),
     }
 }
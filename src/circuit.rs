@@ -0,0 +1,633 @@
+//! Quantum circuits.
+//!
+//! A circuit is a list of gates acting on a register of qubits, indexed in
+//! the same way as sites of a [`Pauli`] string.
+
+use std::{
+    fmt::Display,
+    ops::Neg,
+};
+
+use num::Num;
+
+use crate::{
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    math::{
+        Group,
+        Root4,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+/// Quantum gate
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gate {
+    /// Hadamard gate
+    H(u16),
+    /// Phase gate: `diag(1, i)`
+    S(u16),
+    /// Inverse of the phase gate: `diag(1, -i)`
+    Sdg(u16),
+    /// Controlled-NOT gate: `Cnot(control, target)`
+    Cnot(u16, u16),
+    /// Controlled-Z gate
+    Cz(u16, u16),
+    /// Swap gate
+    Swap(u16, u16),
+    /// Rotation about the Z axis: `Rz(q, phi) = exp(-i phi Z / 2)`
+    Rz(u16, f64),
+}
+
+impl Gate {
+    /// Qubits the gate acts on.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::circuit::Gate;
+    /// assert_eq!(Gate::H(3).qubits(), vec![3]);
+    /// assert_eq!(Gate::Cnot(1, 2).qubits(), vec![1, 2]);
+    /// ```
+    #[must_use]
+    pub fn qubits(&self) -> Vec<u16> {
+        match *self {
+            Self::H(q) | Self::S(q) | Self::Sdg(q) | Self::Rz(q, _) => vec![q],
+            Self::Cnot(a, b) | Self::Cz(a, b) | Self::Swap(a, b) => vec![a, b],
+        }
+    }
+
+    // Two-qubit gates must act on distinct qubits, and rotation angles must
+    // be finite, so that the gate can be conjugated and written as QASM.
+    fn check(&self) -> Result<(), Error> {
+        match *self {
+            Self::Cnot(a, b) | Self::Cz(a, b) | Self::Swap(a, b) if a == b => {
+                Err(Error::QubitIndex {
+                    msg: format!("two-qubit gate acts twice on qubit: {a}"),
+                })
+            }
+            Self::Rz(_, phi) if !phi.is_finite() => Err(Error::Coefficient {
+                msg: format!("rotation angle must be finite: {phi}"),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    // Images of X and Z operators at site `q` under conjugation by the gate.
+    fn images(
+        &self,
+        q: u16,
+    ) -> ((Root4, Pauli), (Root4, Pauli)) {
+        use PauliOp::{
+            X,
+            Y,
+            Z,
+        };
+
+        let single = |op: PauliOp| {
+            let mut code = Pauli::identity();
+            code.set(q, op);
+            code
+        };
+        let pair = |p: u16, op_p: PauliOp, r: u16, op_r: PauliOp| {
+            let mut code = Pauli::identity();
+            code.set(p, op_p);
+            code.set(r, op_r);
+            code
+        };
+        let one = Root4::identity();
+
+        match *self {
+            Self::Rz(..) => unreachable!("conjugation by a non-Clifford gate"),
+            Self::H(_) => ((one, single(Z)), (one, single(X))),
+            Self::S(_) => ((one, single(Y)), (one, single(Z))),
+            Self::Sdg(_) => ((-one, single(Y)), (one, single(Z))),
+            Self::Cnot(c, t) => {
+                if q == c {
+                    ((one, pair(c, X, t, X)), (one, single(Z)))
+                } else {
+                    ((one, single(X)), (one, pair(c, Z, t, Z)))
+                }
+            }
+            Self::Cz(a, b) => {
+                let other = if q == a { b } else { a };
+                ((one, pair(q, X, other, Z)), (one, single(Z)))
+            }
+            Self::Swap(a, b) => {
+                let other = if q == a { b } else { a };
+                let mut x = Pauli::identity();
+                x.set(other, X);
+                let mut z = Pauli::identity();
+                z.set(other, Z);
+                ((one, x), (one, z))
+            }
+        }
+    }
+
+    /// True if the gate is a Clifford gate, i.e. it maps Pauli strings to
+    /// Pauli strings under conjugation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::circuit::Gate;
+    /// assert!(Gate::Cz(0, 1).is_clifford());
+    /// assert!(!Gate::Rz(0, 0.5).is_clifford());
+    /// ```
+    #[must_use]
+    pub fn is_clifford(&self) -> bool {
+        !matches!(self, Self::Rz(..))
+    }
+
+    /// Inverse gate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::circuit::Gate;
+    /// assert_eq!(Gate::S(2).inverse(), Gate::Sdg(2));
+    /// assert_eq!(Gate::Cnot(0, 1).inverse(), Gate::Cnot(0, 1));
+    /// assert_eq!(Gate::Rz(0, 0.5).inverse(), Gate::Rz(0, -0.5));
+    /// ```
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        match *self {
+            Self::S(q) => Self::Sdg(q),
+            Self::Sdg(q) => Self::S(q),
+            Self::Rz(q, phi) => Self::Rz(q, -phi),
+            gate => gate,
+        }
+    }
+
+    /// Conjugate Pauli string by the gate: `G P G^\dagger`.
+    ///
+    /// Returns the image of the Pauli string together with the phase.  For
+    /// a Clifford gate, the phase is `1` or `-1`: [`Root4::R0`] or
+    /// [`Root4::R1`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gate is not a Clifford gate, or if a two-qubit gate
+    /// acts twice on the same qubit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     circuit::Gate,
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     Root4,
+    /// # };
+    /// use PauliOp::{
+    ///     X,
+    ///     Y,
+    ///     Z,
+    /// };
+    ///
+    /// // CNOT (X ⊗ Z) CNOT = (XZ) ⊗ (XZ) = -Y ⊗ Y
+    /// let (omega, code) = Gate::Cnot(0, 1).conjugate(Pauli::with_ops([X, Z]));
+    /// assert_eq!(omega, Root4::R1);
+    /// assert_eq!(code, Pauli::with_ops([Y, Y]));
+    /// ```
+    #[must_use]
+    pub fn conjugate(
+        &self,
+        code: Pauli,
+    ) -> (Root4, Pauli) {
+        let qubits = self.qubits();
+        assert!(
+            qubits.len() < 2 || qubits[0] != qubits[1],
+            "two-qubit gate must act on distinct qubits"
+        );
+        let mut rest = code;
+        for &q in &qubits {
+            rest.set(q, PauliOp::I);
+        }
+
+        let mul = |(w1, p1): (Root4, Pauli), (w2, p2): (Root4, Pauli)| {
+            let (w, p) = p1 * p2;
+            (w1 * w2 * w, p)
+        };
+
+        qubits.iter().fold((Root4::identity(), rest), |acc, &q| {
+            let (img_x, img_z) = self.images(q);
+            match code.pauli(q).expect("qubit index within 0..64") {
+                PauliOp::I => acc,
+                PauliOp::X => mul(acc, img_x),
+                PauliOp::Z => mul(acc, img_z),
+                // Y = i X Z
+                PauliOp::Y => {
+                    let (w, p) = mul(img_x, img_z);
+                    mul(acc, (w * Root4::i(), p))
+                }
+            }
+        })
+    }
+}
+
+impl Display for Gate {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Self::H(q) => write!(f, "h q[{q}]"),
+            Self::S(q) => write!(f, "s q[{q}]"),
+            Self::Sdg(q) => write!(f, "sdg q[{q}]"),
+            Self::Cnot(c, t) => write!(f, "cx q[{c}],q[{t}]"),
+            Self::Cz(a, b) => write!(f, "cz q[{a}],q[{b}]"),
+            Self::Swap(a, b) => write!(f, "swap q[{a}],q[{b}]"),
+            Self::Rz(q, phi) => write!(f, "rz({phi}) q[{q}]"),
+        }
+    }
+}
+
+/// Sequence of gates acting on a register of qubits.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::circuit::{Circuit, Gate};
+/// let mut circuit = Circuit::new(2);
+/// circuit.push(Gate::H(0)).unwrap();
+/// circuit.push(Gate::Cnot(0, 1)).unwrap();
+///
+/// assert_eq!(circuit.num_qubits(), 2);
+/// assert_eq!(circuit.gates(), &[Gate::H(0), Gate::Cnot(0, 1)]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Circuit {
+    num_qubits: u16,
+    gates:      Vec<Gate>,
+}
+
+impl Circuit {
+    /// Create empty circuit acting on `num_qubits` qubits.
+    #[must_use]
+    pub fn new(num_qubits: u16) -> Self {
+        Self {
+            num_qubits,
+            gates: Vec::new(),
+        }
+    }
+
+    /// Size of the qubit register.
+    #[must_use]
+    pub fn num_qubits(&self) -> u16 {
+        self.num_qubits
+    }
+
+    /// List of gates in the order of application.
+    #[must_use]
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    /// Number of gates in the circuit.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.gates.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.gates.is_empty()
+    }
+
+    /// Append gate to the circuit.
+    ///
+    /// The register is enlarged, if the gate acts on a qubit outside of it.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a two-qubit gate acts twice on the same qubit, or if
+    /// the rotation angle is not finite.  The circuit is left intact.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     circuit::{Circuit, Gate},
+    /// #     Error,
+    /// # };
+    /// let mut circuit = Circuit::new(2);
+    ///
+    /// assert!(matches!(
+    ///     circuit.push(Gate::Cnot(1, 1)),
+    ///     Err(Error::QubitIndex { .. })
+    /// ));
+    /// assert!(matches!(
+    ///     circuit.push(Gate::Rz(0, f64::NAN)),
+    ///     Err(Error::Coefficient { .. })
+    /// ));
+    /// assert!(circuit.is_empty());
+    /// ```
+    pub fn push(
+        &mut self,
+        gate: Gate,
+    ) -> Result<(), Error> {
+        gate.check()?;
+        if let Some(&max) = gate.qubits().iter().max() {
+            self.num_qubits = self.num_qubits.max(max + 1);
+        }
+        self.gates.push(gate);
+
+        Ok(())
+    }
+
+    /// Count gates in the circuit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::circuit::{Circuit, Gate};
+    /// let mut circuit = Circuit::new(3);
+    /// circuit.push(Gate::H(0)).unwrap();
+    /// circuit.push(Gate::Cnot(0, 1)).unwrap();
+    /// circuit.push(Gate::Rz(1, 0.5)).unwrap();
+    /// circuit.push(Gate::H(2)).unwrap();
+    ///
+    /// let counts = circuit.gate_counts();
+    /// assert_eq!(counts.single_qubit, 3);
+    /// assert_eq!(counts.two_qubit, 1);
+    /// assert_eq!(counts.rotations, 1);
+    /// assert_eq!(counts.depth, 3);
+    /// ```
+    #[must_use]
+    pub fn gate_counts(&self) -> GateCounts {
+        let mut counts = GateCounts::default();
+        let mut layers = vec![0; usize::from(self.num_qubits)];
+        for gate in &self.gates {
+            let qubits = gate.qubits();
+            match qubits.len() {
+                1 => counts.single_qubit += 1,
+                _ => counts.two_qubit += 1,
+            }
+            if let Gate::Rz(..) = gate {
+                counts.rotations += 1;
+            }
+            let layer = qubits
+                .iter()
+                .map(|&q| layers[usize::from(q)])
+                .max()
+                .unwrap_or(0)
+                + 1;
+            for &q in &qubits {
+                layers[usize::from(q)] = layer;
+            }
+            counts.depth = counts.depth.max(layer);
+        }
+
+        counts
+    }
+
+    /// True if all gates are Clifford gates.
+    #[must_use]
+    pub fn is_clifford(&self) -> bool {
+        self.gates.iter().all(Gate::is_clifford)
+    }
+
+    /// Inverse circuit: inverse gates in the reverse order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::circuit::{Circuit, Gate};
+    /// let mut circuit = Circuit::new(2);
+    /// circuit.push(Gate::S(0)).unwrap();
+    /// circuit.push(Gate::Cnot(0, 1)).unwrap();
+    ///
+    /// assert_eq!(circuit.inverse().gates(), &[Gate::Cnot(0, 1), Gate::Sdg(0)]);
+    /// ```
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        Self {
+            num_qubits: self.num_qubits,
+            gates:      self.gates.iter().rev().map(Gate::inverse).collect(),
+        }
+    }
+
+    /// Conjugate Pauli string by the circuit: `U P U^\dagger`.
+    ///
+    /// Returns the image and the phase, as [`Gate::conjugate`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the circuit contains a non-Clifford gate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     circuit::{Circuit, Gate},
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     Root4,
+    /// # };
+    /// use PauliOp::{
+    ///     X,
+    ///     Y,
+    /// };
+    ///
+    /// // S X S^dagger = Y, H Y H = -Y
+    /// let mut circuit = Circuit::new(1);
+    /// circuit.push(Gate::S(0)).unwrap();
+    /// circuit.push(Gate::H(0)).unwrap();
+    ///
+    /// let (omega, code) = circuit.conjugate(Pauli::with_ops([X]));
+    /// assert_eq!(omega, Root4::R1);
+    /// assert_eq!(code, Pauli::with_ops([Y]));
+    /// ```
+    #[must_use]
+    pub fn conjugate(
+        &self,
+        code: Pauli,
+    ) -> (Root4, Pauli) {
+        self.gates.iter().fold(
+            (Root4::identity(), code),
+            |(omega, code), gate| {
+                let (w, code) = gate.conjugate(code);
+                (omega * w, code)
+            },
+        )
+    }
+
+    /// Conjugate sum of Pauli strings by the circuit: `U H U^\dagger`.
+    ///
+    /// This transforms a Hamiltonian by a Clifford circuit without
+    /// simulating the circuit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the circuit contains a non-Clifford gate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     circuit::{Circuit, Gate},
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     terms::SumRepr,
+    /// # };
+    /// use PauliOp::{
+    ///     I,
+    ///     X,
+    ///     Y,
+    ///     Z,
+    /// };
+    ///
+    /// // Bell state preparation
+    /// let mut circuit = Circuit::new(2);
+    /// circuit.push(Gate::H(0)).unwrap();
+    /// circuit.push(Gate::Cnot(0, 1)).unwrap();
+    ///
+    /// let repr = SumRepr::from([
+    ///     (1.0, Pauli::with_ops([Z, I])),
+    ///     (2.0, Pauli::with_ops([Y, Z])),
+    /// ]);
+    /// let result = circuit.conjugate_sum(&repr);
+    ///
+    /// assert_eq!(result.coeff(Pauli::with_ops([X, X])), Some(&1.0));
+    /// assert_eq!(result.coeff(Pauli::with_ops([X, Y])), Some(&-2.0));
+    /// ```
+    #[must_use]
+    pub fn conjugate_sum<T>(
+        &self,
+        repr: &SumRepr<T, Pauli>,
+    ) -> SumRepr<T, Pauli>
+    where
+        T: Num + Neg<Output = T> + Copy,
+    {
+        let mut result = SumRepr::with_capacity(repr.len());
+        for (&coeff, &code) in repr.iter() {
+            let (omega, image) = self.conjugate(code);
+            let coeff = match omega {
+                Root4::R0 => coeff,
+                Root4::R1 => -coeff,
+                _ => unreachable!("conjugated Pauli string is Hermitian"),
+            };
+            result.add_term(image, coeff);
+        }
+
+        result
+    }
+
+    /// Write the circuit in the OpenQASM format.
+    ///
+    /// If `measure` is true, all qubits are measured at the end of the
+    /// circuit into a classical register of the same size.  OpenQASM 2 has
+    /// no `swap` in `qelib1.inc`, so swaps are written as three `cx` gates.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if writing fails.
+    pub fn write_qasm<W>(
+        &self,
+        mut writer: W,
+        version: QasmVersion,
+        measure: bool,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        let size = self.num_qubits.max(1);
+        match version {
+            QasmVersion::V2 => {
+                writeln!(writer, "OPENQASM 2.0;")?;
+                writeln!(writer, "include \"qelib1.inc\";")?;
+                writeln!(writer, "qreg q[{size}];")?;
+                if measure {
+                    writeln!(writer, "creg c[{size}];")?;
+                }
+            }
+            QasmVersion::V3 => {
+                writeln!(writer, "OPENQASM 3.0;")?;
+                writeln!(writer, "include \"stdgates.inc\";")?;
+                writeln!(writer, "qubit[{size}] q;")?;
+                if measure {
+                    writeln!(writer, "bit[{size}] c;")?;
+                }
+            }
+        }
+        for gate in &self.gates {
+            match (version, gate) {
+                // swap is not defined in qelib1.inc
+                (QasmVersion::V2, &Gate::Swap(a, b)) => {
+                    for (c, t) in [(a, b), (b, a), (a, b)] {
+                        writeln!(writer, "{};", Gate::Cnot(c, t))?;
+                    }
+                }
+                _ => writeln!(writer, "{gate};")?,
+            }
+        }
+        if measure {
+            match version {
+                QasmVersion::V2 => writeln!(writer, "measure q -> c;")?,
+                QasmVersion::V3 => writeln!(writer, "c = measure q;")?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the circuit in the OpenQASM format to a string.
+    ///
+    /// See [`Circuit::write_qasm`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::circuit::{Circuit, Gate, QasmVersion};
+    /// let mut circuit = Circuit::new(2);
+    /// circuit.push(Gate::H(0)).unwrap();
+    /// circuit.push(Gate::Cnot(0, 1)).unwrap();
+    ///
+    /// assert_eq!(
+    ///     circuit.to_qasm(QasmVersion::V2, true),
+    ///     "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[2];\ncreg c[2];\nh \
+    ///      q[0];\ncx q[0],q[1];\nmeasure q -> c;\n"
+    /// );
+    ///
+    /// assert_eq!(
+    ///     circuit.to_qasm(QasmVersion::V3, true),
+    ///     "OPENQASM 3.0;\ninclude \"stdgates.inc\";\nqubit[2] q;\nbit[2] c;\nh \
+    ///      q[0];\ncx q[0],q[1];\nc = measure q;\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_qasm(
+        &self,
+        version: QasmVersion,
+        measure: bool,
+    ) -> String {
+        let mut buf = Vec::new();
+        self.write_qasm(&mut buf, version, measure)
+            .expect("write to buffer");
+        String::from_utf8(buf).expect("QASM output is valid UTF-8")
+    }
+}
+
+/// Version of the OpenQASM format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QasmVersion {
+    /// OpenQASM 2.0
+    V2,
+    /// OpenQASM 3.0
+    #[default]
+    V3,
+}
+
+/// Gate counts of a circuit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GateCounts {
+    /// Number of single-qubit gates, including rotations
+    pub single_qubit: usize,
+    /// Number of two-qubit gates
+    pub two_qubit:    usize,
+    /// Number of rotation gates
+    pub rotations:    usize,
+    /// Circuit depth
+    pub depth:        usize,
+}
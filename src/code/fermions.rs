@@ -7,10 +7,12 @@ use std::{
     ops::Range,
 };
 
+use num::Float;
+
 use crate::Error;
 
 /// Spin one-half
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum Spin {
     #[default]
     Down,
@@ -102,7 +104,7 @@ impl_spin_int!(u8 u16 u32 u64 u128 usize);
 impl_spin_int!(i8 i16 i32 i64 i128 isize);
 
 /// Electronic orbital consisting of a principal quantum number and a spin 1/2.
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Default, PartialOrd, Ord)]
 pub struct Orbital {
     pub n: u32,
     pub s: Spin,
@@ -136,6 +138,8 @@ impl Orbital {
     /// where `n` is the principal quantum number and `s` is the spin number,
     /// i.e. `s = 0` for `Spin::Down` and `s = 1` for `Spin::Up`.
     ///
+    /// This is the same as `self.index_in(OrbitalOrdering::Interleaved)`.
+    ///
     /// # Panics
     ///
     /// Panics is the orbitals index cannot fit into `usize`.
@@ -153,15 +157,44 @@ impl Orbital {
     /// ```
     #[must_use]
     pub fn index(&self) -> u32 {
-        assert!(
-            self.n <= u32::MAX / 2 - u32::from(self.s),
-            "orbital index out of bound"
-        );
-        self.n * 2 + u32::from(self.s)
+        self.index_in(OrbitalOrdering::Interleaved)
+    }
+
+    /// Compute orbital index according to the given ordering.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the orbital cannot be enumerated by `ordering`.
+    /// See [`OrbitalOrdering::index()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::fermions::{Orbital, OrbitalOrdering, Spin};
+    ///
+    /// let ordering = OrbitalOrdering::Block {
+    ///     num_orbitals: 4
+    /// };
+    ///
+    /// let orb = Orbital::new(3, Spin::Down);
+    /// assert_eq!(orb.index_in(ordering), 3);
+    ///
+    /// let orb = Orbital::new(3, Spin::Up);
+    /// assert_eq!(orb.index_in(ordering), 7);
+    /// ```
+    #[must_use]
+    pub fn index_in(
+        &self,
+        ordering: OrbitalOrdering,
+    ) -> u32 {
+        ordering.index(self)
     }
 
     /// Return orbital corresponding to the given index.
     ///
+    /// This is the same as
+    /// `Orbital::with_index_in(index, OrbitalOrdering::Interleaved)`.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -173,7 +206,41 @@ impl Orbital {
     /// ```
     #[must_use]
     pub fn with_index(index: u32) -> Self {
-        Self::new(index / 2, Spin::from(index & 1 != 0))
+        Self::with_index_in(index, OrbitalOrdering::Interleaved)
+    }
+
+    /// Return orbital corresponding to the given index according to
+    /// `ordering`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range for `ordering`.
+    /// See [`OrbitalOrdering::orbital()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::fermions::{Orbital, OrbitalOrdering, Spin};
+    ///
+    /// let ordering = OrbitalOrdering::Block {
+    ///     num_orbitals: 4
+    /// };
+    ///
+    /// assert_eq!(
+    ///     Orbital::with_index_in(3, ordering),
+    ///     Orbital::new(3, Spin::Down)
+    /// );
+    /// assert_eq!(
+    ///     Orbital::with_index_in(5, ordering),
+    ///     Orbital::new(1, Spin::Up)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_index_in(
+        index: u32,
+        ordering: OrbitalOrdering,
+    ) -> Self {
+        ordering.orbital(index)
     }
 
     /// Generate orbitals with indeces in the given range.
@@ -204,10 +271,174 @@ impl Orbital {
     }
 }
 
+/// Enumeration of spin-orbitals.
+///
+/// Describes how an [`Orbital`] is assigned a single index, which becomes
+/// the qubit index after a fermion-to-qubit mapping.  The choice of ordering
+/// changes the weight of Pauli strings produced e.g. by the Jordan-Wigner
+/// mapping.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::code::fermions::{Orbital, OrbitalOrdering, Spin};
+///
+/// let orb = Orbital::new(1, Spin::Up);
+///
+/// assert_eq!(OrbitalOrdering::Interleaved.index(&orb), 3);
+///
+/// let ordering = OrbitalOrdering::Block {
+///     num_orbitals: 5
+/// };
+/// assert_eq!(ordering.index(&orb), 6);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum OrbitalOrdering {
+    /// Orbitals with opposite spins alternate: `2*n + s`.
+    #[default]
+    Interleaved,
+    /// All orbitals with `Spin::Down`, followed by all orbitals with
+    /// `Spin::Up`: `n + s * num_orbitals`.
+    Block { num_orbitals: u32 },
+}
+
+impl OrbitalOrdering {
+    /// Compute orbital index.
+    ///
+    /// Returns `None`, if the orbital cannot be enumerated, i.e. if the
+    /// index does not fit into `u32`, or if `orbital.n >= num_orbitals` for
+    /// the block ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::fermions::{Orbital, OrbitalOrdering, Spin};
+    ///
+    /// let ordering = OrbitalOrdering::Block {
+    ///     num_orbitals: 2
+    /// };
+    ///
+    /// assert_eq!(ordering.checked_index(&Orbital::new(1, Spin::Up)), Some(3));
+    /// assert_eq!(ordering.checked_index(&Orbital::new(2, Spin::Up)), None);
+    /// ```
+    #[must_use]
+    pub fn checked_index(
+        &self,
+        orbital: &Orbital,
+    ) -> Option<u32> {
+        let s = u32::from(orbital.s);
+        match self {
+            Self::Interleaved => {
+                (orbital.n <= u32::MAX / 2 - s).then(|| orbital.n * 2 + s)
+            }
+            Self::Block {
+                num_orbitals,
+            } => (orbital.n < *num_orbitals)
+                .then_some(())
+                .and_then(|()| num_orbitals.checked_mul(s))
+                .and_then(|offset| offset.checked_add(orbital.n)),
+        }
+    }
+
+    /// Compute orbital index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the orbital cannot be enumerated.
+    /// See [`OrbitalOrdering::checked_index()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::fermions::{Orbital, OrbitalOrdering, Spin};
+    ///
+    /// let ordering = OrbitalOrdering::Block {
+    ///     num_orbitals: 2
+    /// };
+    ///
+    /// assert_eq!(ordering.index(&Orbital::new(0, Spin::Up)), 2);
+    /// ```
+    #[must_use]
+    pub fn index(
+        &self,
+        orbital: &Orbital,
+    ) -> u32 {
+        self.checked_index(orbital)
+            .expect("orbital index out of bound")
+    }
+
+    /// Return orbital corresponding to the given index.
+    ///
+    /// Returns `None`, if `index >= 2 * num_orbitals` for the block
+    /// ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::fermions::{Orbital, OrbitalOrdering, Spin};
+    ///
+    /// let ordering = OrbitalOrdering::Block {
+    ///     num_orbitals: 2
+    /// };
+    ///
+    /// assert_eq!(ordering.checked_orbital(2), Some(Orbital::new(0, Spin::Up)));
+    /// assert_eq!(ordering.checked_orbital(4), None);
+    /// ```
+    #[must_use]
+    pub fn checked_orbital(
+        &self,
+        index: u32,
+    ) -> Option<Orbital> {
+        match self {
+            Self::Interleaved => {
+                Some(Orbital::new(index / 2, Spin::from(index & 1 != 0)))
+            }
+            Self::Block {
+                num_orbitals,
+            } => {
+                if index < *num_orbitals {
+                    Some(Orbital::new(index, Spin::Down))
+                } else {
+                    (index - num_orbitals < *num_orbitals)
+                        .then(|| Orbital::new(index - num_orbitals, Spin::Up))
+                }
+            }
+        }
+    }
+
+    /// Return orbital corresponding to the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= 2 * num_orbitals` for the block ordering.
+    /// See [`OrbitalOrdering::checked_orbital()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::fermions::{Orbital, OrbitalOrdering, Spin};
+    ///
+    /// let ordering = OrbitalOrdering::Block {
+    ///     num_orbitals: 2
+    /// };
+    ///
+    /// assert_eq!(ordering.orbital(1), Orbital::new(1, Spin::Down));
+    /// assert_eq!(ordering.orbital(2), Orbital::new(0, Spin::Up));
+    /// ```
+    #[must_use]
+    pub fn orbital(
+        &self,
+        index: u32,
+    ) -> Orbital {
+        self.checked_orbital(index)
+            .expect("orbital index out of bound")
+    }
+}
+
 /// Creation operator
 ///
 /// A newtype struct representing a creation operator.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Cr(pub Orbital);
 
 impl Cr {
@@ -230,7 +461,7 @@ impl Cr {
 /// Annihilation operator
 ///
 /// A newtype struct representing an annihilation operator.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct An(pub Orbital);
 
 impl An {
@@ -265,7 +496,10 @@ impl An {
 ///   ```
 ///
 ///   then `p < q`, `r > s` and `p <= s`.
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+///
+/// Integrals are ordered by the number of operators, the offset first, and
+/// then lexicographically by the orbital indices.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum Fermions {
     #[default]
     Offset,
@@ -380,6 +614,126 @@ impl Fermions {
     }
 }
 
+impl Fermions {
+    /// Largest orbital index of the term, or `None` for the offset.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::fermions::Fermions;
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// assert_eq!(Fermions::Offset.max_index(), None);
+    /// assert_eq!(Fermions::try_from((0, 3))?.max_index(), Some(3));
+    /// assert_eq!(Fermions::try_from((0, 4, 2, 1))?.max_index(), Some(4));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_index(&self) -> Option<u32> {
+        match self {
+            Self::Offset => None,
+            Self::One {
+                cr,
+                an,
+            } => Some(cr.index().max(an.index())),
+            Self::Two {
+                cr,
+                an,
+            } => Some(
+                cr.0.index()
+                    .max(cr.1.index())
+                    .max(an.0.index())
+                    .max(an.1.index()),
+            ),
+        }
+    }
+
+    /// Relabel orbitals and bring the term back to canonical order.
+    ///
+    /// Each orbital `p` in the term is replaced by `f(p)`.  The coefficient
+    /// `coeff` of the term changes sign, if an odd number of transpositions
+    /// of fermionic operators is needed to restore canonical order.  Since
+    /// the Hamiltonian is Hermitian, the term can be replaced by its
+    /// Hermitian conjugate, if necessary.  The coefficient is real, hence it
+    /// stays the same under conjugation.
+    ///
+    /// Returns `None`, if `f` maps two distinct orbitals of a two-electron
+    /// term to the same orbital.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::fermions::{Fermions, Orbital};
+    /// let code = Fermions::try_from((0, 1, 3, 2)).unwrap();
+    ///
+    /// // Swap orbitals 2 and 3
+    /// let swap = |orb: Orbital| match orb.index() {
+    ///     2 => Orbital::with_index(3),
+    ///     3 => Orbital::with_index(2),
+    ///     _ => orb,
+    /// };
+    ///
+    /// assert_eq!(code.relabel(1.0, swap), Some((-1.0, code)));
+    ///
+    /// // Map everything to the same orbital
+    /// assert_eq!(code.relabel(1.0, |_| Orbital::with_index(0)), None);
+    /// ```
+    pub fn relabel<T, F>(
+        &self,
+        coeff: T,
+        f: F,
+    ) -> Option<(T, Self)>
+    where
+        T: Float,
+        F: Fn(Orbital) -> Orbital,
+    {
+        match self {
+            Self::Offset => Some((coeff, Self::Offset)),
+            Self::One {
+                cr,
+                an,
+            } => {
+                let (p, q) = (f(cr.0), f(an.0));
+                if p.index() <= q.index() {
+                    Self::one_electron(Cr(p), An(q))
+                } else {
+                    // Hermitian conjugate
+                    Self::one_electron(Cr(q), An(p))
+                }
+                .map(|code| (coeff, code))
+            }
+            Self::Two {
+                cr,
+                an,
+            } => {
+                let mut sign = false;
+                let (mut p, mut q) = (f(cr.0 .0), f(cr.1 .0));
+                if p.index() > q.index() {
+                    std::mem::swap(&mut p, &mut q);
+                    sign = !sign;
+                }
+                let (mut r, mut s) = (f(an.0 .0), f(an.1 .0));
+                if r.index() < s.index() {
+                    std::mem::swap(&mut r, &mut s);
+                    sign = !sign;
+                }
+                if p.index() > s.index() {
+                    // Hermitian conjugate
+                    (p, q, r, s) = (s, r, q, p);
+                }
+
+                Self::two_electron((Cr(p), Cr(q)), (An(r), An(s))).map(|code| {
+                    if sign {
+                        (-coeff, code)
+                    } else {
+                        (coeff, code)
+                    }
+                })
+            }
+        }
+    }
+}
+
 impl From<()> for Fermions {
     fn from((): ()) -> Self {
         Fermions::Offset
@@ -2,18 +2,40 @@
 
 use std::{
     fmt::Display,
-    ops::Mul,
+    ops::{
+        Mul,
+        Neg,
+    },
+    str::FromStr,
 };
 
+use num::Num;
 pub use pauli_group::PauliGroup;
 
 use crate::{
     math::Root4,
+    terms::SumRepr,
     Error,
 };
 
 const PAULI_MASK: u64 = 0b11;
 
+// Lower bit of each pair of bits is set, if the Pauli operator is
+// non-trivial.
+const fn nontrivial_mask(pack: u64) -> u64 {
+    (pack | (pack >> 1)) & 0x5555_5555_5555_5555
+}
+
+// Move the lower bit of each pair of bits to the lower half of the word.
+const fn compact_mask(pack: u64) -> u64 {
+    let mut x = pack & 0x5555_5555_5555_5555;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x | (x >> 4)) & 0x00ff_00ff_00ff_00ff;
+    x = (x | (x >> 8)) & 0x0000_ffff_0000_ffff;
+    (x | (x >> 16)) & 0x0000_0000_ffff_ffff
+}
+
 /// Pauli operator
 ///
 /// # Examples
@@ -539,6 +561,480 @@ impl Pauli {
         })
         .expect("pauli iterator has no more than 64 elements")
     }
+
+    /// Check if two Pauli strings commute.
+    ///
+    /// Two Pauli strings commute, if the number of sites where both operators
+    /// are non-trivial and different is even.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::qubits::{PauliOp, Pauli};
+    /// use PauliOp::{
+    ///     I,
+    ///     X,
+    ///     Y,
+    ///     Z,
+    /// };
+    ///
+    /// let code = Pauli::with_ops([X, X]);
+    ///
+    /// assert!(code.commutes_with(&Pauli::with_ops([Y, Y])));
+    /// assert!(!code.commutes_with(&Pauli::with_ops([I, Z])));
+    /// ```
+    #[must_use]
+    pub fn commutes_with(
+        &self,
+        other: &Self,
+    ) -> bool {
+        let anti = |a: u64, b: u64| {
+            (nontrivial_mask(a) & nontrivial_mask(b) & nontrivial_mask(a ^ b))
+                .count_ones()
+        };
+
+        (anti(self.pack.0, other.pack.0) + anti(self.pack.1, other.pack.1)) % 2
+            == 0
+    }
+
+    /// Check if two Pauli strings commute qubit-wise.
+    ///
+    /// Two Pauli strings commute qubit-wise, if at each site the operators
+    /// are either equal or one of them is the identity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::qubits::{PauliOp, Pauli};
+    /// use PauliOp::{
+    ///     I,
+    ///     X,
+    ///     Y,
+    ///     Z,
+    /// };
+    ///
+    /// let code = Pauli::with_ops([X, I, Z]);
+    ///
+    /// assert!(code.commutes_qubitwise(&Pauli::with_ops([X, Y])));
+    /// assert!(!code.commutes_qubitwise(&Pauli::with_ops([Y, Y])));
+    /// ```
+    #[must_use]
+    pub fn commutes_qubitwise(
+        &self,
+        other: &Self,
+    ) -> bool {
+        let anti = |a: u64, b: u64| {
+            nontrivial_mask(a) & nontrivial_mask(b) & nontrivial_mask(a ^ b)
+        };
+
+        anti(self.pack.0, other.pack.0) == 0
+            && anti(self.pack.1, other.pack.1) == 0
+    }
+
+    /// Symplectic representation of the code: bit masks of sites with
+    /// Pauli X or Y, and with Pauli Z or Y.
+    ///
+    /// Bit `i` of each mask corresponds to the qubit `i`.
+    pub(crate) fn xz_masks(&self) -> (u64, u64) {
+        let x = |pack: u64| compact_mask(pack ^ (pack >> 1));
+        let z = |pack: u64| compact_mask(pack >> 1);
+
+        (
+            x(self.pack.0) | x(self.pack.1) << 32,
+            z(self.pack.0) | z(self.pack.1) << 32,
+        )
+    }
+
+    /// Relabel qubits.
+    ///
+    /// The Pauli operator at site `i` is moved to site `perm[i]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `perm` is not injective, or if it maps a site
+    /// outside of `0..64` (excl.), or if there is a non-trivial Pauli
+    /// operator at a site `i >= perm.len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::qubits::{PauliOp, Pauli};
+    /// use PauliOp::{
+    ///     I,
+    ///     X,
+    ///     Y,
+    ///     Z,
+    /// };
+    ///
+    /// let code = Pauli::with_ops([X, Y, Z]);
+    /// let perm = code.permute(&[2, 0, 1]).unwrap();
+    ///
+    /// assert_eq!(perm, Pauli::with_ops([Y, Z, X]));
+    ///
+    /// assert!(code.permute(&[0, 1]).is_err());
+    /// assert!(code.permute(&[0, 1, 64]).is_err());
+    /// assert!(code.permute(&[0, 1, 1]).is_err());
+    /// ```
+    pub fn permute(
+        &self,
+        perm: &[u16],
+    ) -> Result<Self, Error> {
+        let mut seen = [false; 64];
+        if !perm.iter().all(|&j| {
+            usize::from(j) < 64
+                && !std::mem::replace(&mut seen[usize::from(j)], true)
+        }) {
+            return Err(Error::QubitIndex {
+                msg: "permuted qubit indices must be distinct and within 0..64"
+                    .to_string(),
+            });
+        }
+
+        let mut code = Self::identity();
+        for (i, pauli) in self
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, p)| p != PauliOp::I)
+        {
+            let index = perm.get(i).copied().ok_or(Error::QubitIndex {
+                msg: format!("missing permuted qubit index of site: {i}"),
+            })?;
+            // SAFETY: We just checked if index is within bounds
+            unsafe {
+                code.set_unchecked(index, pauli);
+            }
+        }
+
+        Ok(code)
+    }
+
+    /// Embed the code into a larger register, shifting qubit indices by
+    /// `offset`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the shifted code does not fit into 64 qubits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::qubits::{PauliOp, Pauli};
+    /// use PauliOp::{
+    ///     I,
+    ///     X,
+    ///     Y,
+    /// };
+    ///
+    /// let code = Pauli::with_ops([X, Y]);
+    ///
+    /// assert_eq!(code.embed(2).unwrap(), Pauli::with_ops([I, I, X, Y]));
+    /// assert!(code.embed(63).is_err());
+    /// ```
+    pub fn embed(
+        &self,
+        offset: u16,
+    ) -> Result<Self, Error> {
+        if *self == Self::identity() {
+            return Ok(*self);
+        }
+        if u32::from(self.min_register_size()) + u32::from(offset) > 64 {
+            return Err(Error::QubitIndex {
+                msg: "embedded code must fit into 64 qubits".to_string(),
+            });
+        }
+        Ok(Self::from(self.index() << (2 * offset)))
+    }
+
+    /// Write the code in the sparse notation: non-trivial Pauli operators
+    /// followed by their qubit indices, separated by spaces.
+    ///
+    /// The identity is written as `"I"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::qubits::{PauliOp, Pauli};
+    /// let mut code = Pauli::identity();
+    /// assert_eq!(code.to_sparse(), "I");
+    ///
+    /// code.set(0, PauliOp::X);
+    /// code.set(3, PauliOp::Y);
+    /// code.set(17, PauliOp::Z);
+    /// assert_eq!(code.to_sparse(), "X0 Y3 Z17");
+    /// ```
+    #[must_use]
+    pub fn to_sparse(&self) -> String {
+        let ops: Vec<_> = self
+            .into_iter()
+            .zip(0..)
+            .filter(|(op, _)| *op != PauliOp::I)
+            .map(|(op, i): (PauliOp, u16)| format!("{op}{i}"))
+            .collect();
+        if ops.is_empty() {
+            "I".to_string()
+        } else {
+            ops.join(" ")
+        }
+    }
+
+    /// Parse the sparse notation: Pauli operators followed by their qubit
+    /// indices, e.g. `"X0 Y3 Z17"` or `"X0Y3"`.
+    ///
+    /// Whitespace between operators is optional.  Identity operators, e.g.
+    /// `"I5"`, are allowed.  An empty string, or `"I"`, is the identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the string is malformed, if a qubit index is not
+    /// within 0..64, or if an index appears more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::code::qubits::{PauliOp, Pauli};
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// let code = Pauli::from_sparse("X0 Y3 Z17")?;
+    ///
+    /// assert_eq!(code.pauli(0), Some(PauliOp::X));
+    /// assert_eq!(code.pauli(3), Some(PauliOp::Y));
+    /// assert_eq!(code.pauli(17), Some(PauliOp::Z));
+    /// assert_eq!(code.num_nontrivial(), 3);
+    ///
+    /// assert_eq!(Pauli::from_sparse("X0Y3Z17")?, code);
+    /// assert!(Pauli::from_sparse("X64").is_err());
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn from_sparse(s: &str) -> Result<Self, Error> {
+        let parse_err = |msg: &str| Error::Parse {
+            msg: format!("{msg}: {s:?}"),
+        };
+
+        let s = s.trim();
+        if s == "I" {
+            return Ok(Self::identity());
+        }
+        let mut code = Self::identity();
+        let mut seen: u64 = 0;
+        let mut chars = s.chars().peekable();
+        while let Some(ch) = chars.next() {
+            if ch.is_whitespace() {
+                continue;
+            }
+            let op = match ch {
+                'I' => PauliOp::I,
+                'X' => PauliOp::X,
+                'Y' => PauliOp::Y,
+                'Z' => PauliOp::Z,
+                _ => return Err(parse_err("unknown Pauli operator")),
+            };
+            let mut digits = String::new();
+            while let Some(&d) = chars.peek() {
+                if !d.is_ascii_digit() {
+                    break;
+                }
+                digits.push(d);
+                chars.next();
+            }
+            if digits.is_empty() {
+                return Err(parse_err("missing qubit index"));
+            }
+            let index: u16 = digits
+                .parse()
+                .ok()
+                .filter(|&i| i < 64)
+                .ok_or_else(|| Error::QubitIndex {
+                    msg: format!("qubit index must be within 0..64: {digits}"),
+                })?;
+            if seen >> index & 1 == 1 {
+                return Err(parse_err("repeated qubit index"));
+            }
+            seen |= 1 << index;
+            code.set(index, op);
+        }
+
+        Ok(code)
+    }
+}
+
+/// Text notation of Pauli strings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PauliNotation {
+    /// One character for each qubit, trailing identities truncated:
+    /// `"XIIY"`
+    #[default]
+    Dense,
+    /// Non-trivial operators with qubit indices: `"X0 Y3"`
+    Sparse,
+}
+
+/// Sum of Pauli strings serialized in the given notation.
+///
+/// See [`SumRepr::with_notation`].
+#[derive(Debug)]
+pub struct WithNotation<'a, T> {
+    repr:     &'a SumRepr<T, Pauli>,
+    notation: PauliNotation,
+}
+
+impl<'a, T> WithNotation<'a, T> {
+    pub(crate) fn repr(&self) -> &'a SumRepr<T, Pauli> {
+        self.repr
+    }
+
+    pub(crate) fn notation(&self) -> PauliNotation {
+        self.notation
+    }
+}
+
+impl<T> SumRepr<T, Pauli> {
+    /// Serialize Pauli strings in the given notation.
+    ///
+    /// The default serialization uses the dense notation.  Deserialization
+    /// accepts both.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliNotation},
+    /// #     terms::SumRepr,
+    /// # };
+    /// let mut code = Pauli::identity();
+    /// code.set(60, f2q::code::qubits::PauliOp::Z);
+    /// let repr = SumRepr::from([(0.5, code)]);
+    ///
+    /// let json =
+    ///     serde_json::to_string(&repr.with_notation(PauliNotation::Sparse))
+    ///         .unwrap();
+    /// assert_eq!(
+    ///     json,
+    ///     r#"{"type":"sumrepr","encoding":"qubits","terms":[{"code":"Z60","value":0.5}]}"#
+    /// );
+    ///
+    /// let back: SumRepr<f64, Pauli> = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(back.coeff(code), Some(&0.5));
+    /// ```
+    #[must_use]
+    pub fn with_notation(
+        &self,
+        notation: PauliNotation,
+    ) -> WithNotation<'_, T> {
+        WithNotation {
+            repr: self,
+            notation,
+        }
+    }
+}
+
+/// Parse the dense or the sparse notation.
+///
+/// The notation is sparse, if the string contains a digit.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::code::qubits::{PauliOp, Pauli};
+/// use PauliOp::{
+///     I,
+///     X,
+///     Y,
+/// };
+///
+/// # fn main() -> Result<(), f2q::Error> {
+/// let code: Pauli = "XIIY".parse()?;
+/// assert_eq!(code, Pauli::with_ops([X, I, I, Y]));
+///
+/// let code: Pauli = "X0 Y3".parse()?;
+/// assert_eq!(code, Pauli::with_ops([X, I, I, Y]));
+/// #   Ok(())
+/// # }
+/// ```
+impl FromStr for Pauli {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.chars().any(|ch| ch.is_ascii_digit()) {
+            return Self::from_sparse(s);
+        }
+        if s.is_empty() || s.chars().count() > 64 {
+            return Err(Error::Parse {
+                msg: format!(
+                    "length of dense Pauli string must be 1..=64: {s:?}"
+                ),
+            });
+        }
+        let mut code = Self::identity();
+        for (ch, i) in s.chars().zip(0..) {
+            let op = match ch {
+                'I' => PauliOp::I,
+                'X' => PauliOp::X,
+                'Y' => PauliOp::Y,
+                'Z' => PauliOp::Z,
+                _ => {
+                    return Err(Error::Parse {
+                        msg: format!(
+                            "character must be one of: I, X, Y, Z: {s:?}"
+                        ),
+                    })
+                }
+            };
+            code.set(i, op);
+        }
+
+        Ok(code)
+    }
+}
+
+/// Parse a term in the sparse notation with an optional coefficient,
+/// e.g. `"0.5 X0 Y3"`, `"-1.5 * Z17"` or `"X0Y3"`.
+///
+/// A missing coefficient is one.  A lone sign, e.g. `"-X0"`, is allowed.
+///
+/// # Errors
+///
+/// Returns error if the coefficient or the Pauli string cannot be parsed.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::code::qubits::{parse_sparse_term, Pauli, PauliOp};
+/// use PauliOp::{
+///     I,
+///     X,
+///     Y,
+/// };
+///
+/// # fn main() -> Result<(), f2q::Error> {
+/// let (coeff, code) = parse_sparse_term::<f64>("0.5 X0 Y3")?;
+/// assert_eq!(coeff, 0.5);
+/// assert_eq!(code, Pauli::with_ops([X, I, I, Y]));
+///
+/// assert_eq!(
+///     parse_sparse_term::<f64>("-X1")?,
+///     (-1.0, Pauli::with_ops([I, X]))
+/// );
+/// assert_eq!(parse_sparse_term::<f64>("2.5")?, (2.5, Pauli::identity()));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn parse_sparse_term<T>(s: &str) -> Result<(T, Pauli), Error>
+where
+    T: Num + Neg<Output = T> + FromStr,
+{
+    let s = s.trim();
+    let split = s.find(['I', 'X', 'Y', 'Z']).unwrap_or(s.len());
+    let (coeff, code) = s.split_at(split);
+    let coeff = coeff.trim().trim_end_matches('*').trim();
+    let coeff = match coeff {
+        "" | "+" => T::one(),
+        "-" => -T::one(),
+        _ => coeff.parse().map_err(|_| Error::Parse {
+            msg: format!("invalid coefficient: {coeff:?}"),
+        })?,
+    };
+
+    Ok((coeff, Pauli::from_sparse(code)?))
 }
 
 /// Iterate over Paulis in `Pauli`
@@ -0,0 +1,214 @@
+//! Partition Pauli sums into groups of commuting terms.
+//!
+//! Terms in each group can be measured simultaneously on quantum hardware.
+//! The partition is found by colouring the graph, whose vertices are Pauli
+//! strings and whose edges connect strings that cannot be measured
+//! together.
+
+use std::collections::HashSet;
+
+use num::Num;
+use rayon::prelude::*;
+
+use crate::{
+    code::qubits::Pauli,
+    terms::SumRepr,
+};
+
+/// Relation between Pauli strings that can be measured simultaneously.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compatibility {
+    /// Pauli strings commute.
+    #[default]
+    FullyCommuting,
+    /// Pauli strings commute qubit-wise.
+    QubitWise,
+}
+
+impl Compatibility {
+    /// Check if two Pauli strings are compatible.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{code::qubits::{Pauli, PauliOp}, grouping::Compatibility};
+    /// use PauliOp::{
+    ///     X,
+    ///     Y,
+    /// };
+    ///
+    /// let a = Pauli::with_ops([X, X]);
+    /// let b = Pauli::with_ops([Y, Y]);
+    ///
+    /// assert!(Compatibility::FullyCommuting.check(&a, &b));
+    /// assert!(!Compatibility::QubitWise.check(&a, &b));
+    /// ```
+    #[must_use]
+    pub fn check(
+        self,
+        a: &Pauli,
+        b: &Pauli,
+    ) -> bool {
+        match self {
+            Self::FullyCommuting => a.commutes_with(b),
+            Self::QubitWise => a.commutes_qubitwise(b),
+        }
+    }
+}
+
+/// Graph colouring heuristic.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Coloring {
+    /// Colour vertices in the order of decreasing degree.
+    LargestFirst,
+    /// Colour next the vertex with the largest number of distinct colours
+    /// among its neighbours (saturation degree).
+    #[default]
+    Dsatur,
+}
+
+/// Partition terms of the sum into groups of compatible Pauli strings.
+///
+/// Each term of `repr` ends up in exactly one group, with its coefficient.
+/// Groups are sorted by decreasing size.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     grouping::{group, Coloring, Compatibility},
+/// #     terms::SumRepr,
+/// # };
+/// use PauliOp::{
+///     I,
+///     X,
+///     Y,
+///     Z,
+/// };
+///
+/// let repr = SumRepr::from([
+///     (1.0, Pauli::with_ops([X, X])),
+///     (2.0, Pauli::with_ops([Y, Y])),
+///     (3.0, Pauli::with_ops([Z, Z])),
+///     (4.0, Pauli::with_ops([Z, I])),
+/// ]);
+///
+/// let groups = group(&repr, Compatibility::FullyCommuting, Coloring::Dsatur);
+/// assert_eq!(groups.len(), 2);
+///
+/// let groups = group(&repr, Compatibility::QubitWise, Coloring::Dsatur);
+/// assert_eq!(groups.len(), 3);
+/// ```
+#[must_use]
+pub fn group<T>(
+    repr: &SumRepr<T, Pauli>,
+    compatibility: Compatibility,
+    coloring: Coloring,
+) -> Vec<SumRepr<T, Pauli>>
+where
+    T: Num + Copy,
+{
+    let mut terms: Vec<_> =
+        repr.iter().map(|(&coeff, &code)| (coeff, code)).collect();
+    terms.sort_by_key(|(_, code)| *code);
+    let codes: Vec<_> = terms.iter().map(|(_, code)| *code).collect();
+
+    let colors = match coloring {
+        Coloring::LargestFirst => largest_first(&codes, compatibility),
+        Coloring::Dsatur => dsatur(&codes, compatibility),
+    };
+
+    let num_colors = colors.iter().max().map_or(0, |&c| c + 1);
+    let mut groups: Vec<_> = (0..num_colors).map(|_| SumRepr::new()).collect();
+    for ((coeff, code), color) in terms.into_iter().zip(colors) {
+        groups[color].add_term(code, coeff);
+    }
+    groups.sort_by_key(|g| std::cmp::Reverse(g.len()));
+
+    groups
+}
+
+fn degrees(
+    codes: &[Pauli],
+    compatibility: Compatibility,
+) -> Vec<usize> {
+    codes
+        .par_iter()
+        .map(|a| codes.iter().filter(|b| !compatibility.check(a, b)).count())
+        .collect()
+}
+
+fn largest_first(
+    codes: &[Pauli],
+    compatibility: Compatibility,
+) -> Vec<usize> {
+    let degrees = degrees(codes, compatibility);
+    let mut order: Vec<_> = (0..codes.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(degrees[i]));
+
+    let mut colors = vec![0; codes.len()];
+    let mut classes: Vec<Vec<usize>> = Vec::new();
+    for v in order {
+        let code = &codes[v];
+        let color = classes
+            .iter()
+            .position(|class| {
+                class.iter().all(|&u| compatibility.check(code, &codes[u]))
+            })
+            .unwrap_or_else(|| {
+                classes.push(Vec::new());
+                classes.len() - 1
+            });
+        classes[color].push(v);
+        colors[v] = color;
+    }
+
+    colors
+}
+
+fn dsatur(
+    codes: &[Pauli],
+    compatibility: Compatibility,
+) -> Vec<usize> {
+    let n = codes.len();
+    let degrees = degrees(codes, compatibility);
+    let mut saturation: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    let mut colors: Vec<Option<usize>> = vec![None; n];
+
+    for _ in 0..n {
+        let v = (0..n)
+            .filter(|&i| colors[i].is_none())
+            .max_by(|&a, &b| {
+                saturation[a]
+                    .len()
+                    .cmp(&saturation[b].len())
+                    .then(degrees[a].cmp(&degrees[b]))
+                    .then(b.cmp(&a))
+            })
+            .expect("there is an uncolored vertex");
+
+        // At most n - 1 neighbours, so one of n colors is free.
+        let color = (0..n)
+            .find(|c| !saturation[v].contains(c))
+            .expect("there is a free color");
+        colors[v] = Some(color);
+
+        let code = &codes[v];
+        saturation
+            .par_iter_mut()
+            .zip(&colors)
+            .zip(codes)
+            .filter(|((_, c), _)| c.is_none())
+            .for_each(|((sat, _), u)| {
+                if !compatibility.check(code, u) {
+                    sat.insert(color);
+                }
+            });
+    }
+
+    colors
+        .into_iter()
+        .map(|c| c.expect("all vertices are colored"))
+        .collect()
+}
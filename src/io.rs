@@ -0,0 +1,9 @@
+//! Import and export of Hamiltonians in formats other than serde's: formats
+//! of other software, tabular and compact formats for large sums of terms.
+
+pub mod binary;
+pub mod csv;
+pub mod jsonl;
+pub mod npy;
+pub mod openfermion;
+pub mod qiskit;
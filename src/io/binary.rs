@@ -0,0 +1,513 @@
+//! Compact binary format of sums of terms.
+//!
+//! All numbers are little-endian.  Coefficients are IEEE 754 `f64`.  A file
+//! starts with a header of 16 bytes:
+//!
+//! | offset | type     | content                         |
+//! |-------:|----------|---------------------------------|
+//! |      0 | `[u8;4]` | magic: `b"F2QB"`                |
+//! |      4 | `u16`    | version: 1                      |
+//! |      6 | `u16`    | encoding: 1 qubits, 2 fermions  |
+//! |      8 | `u64`    | number of terms                 |
+//!
+//! followed by terms.  If the number of terms is not known in advance, the
+//! header stores [`UNKNOWN_NUM_TERMS`] and terms are read until the end of
+//! input.  A term of the qubits encoding has 24 bytes:
+//!
+//! | offset | type  | content                                 |
+//! |-------:|-------|-----------------------------------------|
+//! |      0 | `f64` | coefficient                             |
+//! |      8 | `u64` | Pauli string: qubits 0..32, 2 bits each |
+//! |     16 | `u64` | Pauli string: qubits 32..64             |
+//!
+//! where the operators are: `I=0`, `X=1`, `Y=2`, `Z=3` (see
+//! [`Pauli::index`]).  A term of the fermions encoding has 9, 17 or 25
+//! bytes:
+//!
+//! | offset | type       | content                      |
+//! |-------:|------------|------------------------------|
+//! |      0 | `f64`      | coefficient                  |
+//! |      8 | `u8`       | number of indices: 0, 2 or 4 |
+//! |      9 | `[u32; n]` | spin-orbital indices         |
+//!
+//! for the offset, a one-electron term `(p, q)` and a two-electron term
+//! `(p, q, r, s)` of [`Fermions`] in the canonical order.
+//!
+//! [`write_sum`] writes terms sorted by code, so that equal sums are written
+//! as identical files.  Terms are read and written one at a time, so sums of
+//! any size can be streamed with [`Reader`] and [`Writer`], which keep the
+//! order of terms as given.
+
+use std::{
+    io::{
+        Read,
+        Write,
+    },
+    marker::PhantomData,
+};
+
+use crate::{
+    code::{
+        fermions::{
+            An,
+            Cr,
+            Fermions,
+            Orbital,
+        },
+        qubits::Pauli,
+        Code,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+/// Magic bytes at the beginning of the file.
+pub const MAGIC: [u8; 4] = *b"F2QB";
+
+/// Version of the format.
+pub const VERSION: u16 = 1;
+
+/// Number of terms in the header, if unknown when the header is written.
+pub const UNKNOWN_NUM_TERMS: u64 = u64::MAX;
+
+fn read_err(err: &std::io::Error) -> Error {
+    Error::Parse {
+        msg: format!("binary: {err}"),
+    }
+}
+
+fn read_bytes<R, const N: usize>(reader: &mut R) -> Result<[u8; N], Error>
+where
+    R: Read,
+{
+    let mut buf = [0; N];
+    reader.read_exact(&mut buf).map_err(|e| read_err(&e))?;
+    Ok(buf)
+}
+
+// Read exactly N bytes, or nothing at the end of input.
+fn read_bytes_or_eof<R, const N: usize>(
+    reader: &mut R
+) -> Result<Option<[u8; N]>, Error>
+where
+    R: Read,
+{
+    let mut buf = [0; N];
+    let mut filled = 0;
+    while filled < N {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => (),
+            Err(e) => return Err(read_err(&e)),
+        }
+    }
+    match filled {
+        0 => Ok(None),
+        _ if filled == N => Ok(Some(buf)),
+        _ => Err(Error::Parse {
+            msg: "binary: unexpected end of input".to_string(),
+        }),
+    }
+}
+
+/// Codes that can be stored in the binary format.
+pub trait BinaryCode: Code {
+    /// Encoding in the header.
+    const ENCODING: u16;
+
+    /// Write the code.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if writing fails.
+    fn write_code<W: Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()>;
+
+    /// Read the code.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if reading fails, or if the code is invalid.
+    fn read_code<R: Read>(reader: &mut R) -> Result<Self, Error>;
+}
+
+impl BinaryCode for Pauli {
+    const ENCODING: u16 = 1;
+
+    fn write_code<W: Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        writer.write_all(&self.index().to_le_bytes())
+    }
+
+    fn read_code<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        Ok(Self::from(u128::from_le_bytes(read_bytes(reader)?)))
+    }
+}
+
+impl BinaryCode for Fermions {
+    const ENCODING: u16 = 2;
+
+    fn write_code<W: Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        let indices = match self {
+            Self::Offset => vec![],
+            Self::One {
+                cr,
+                an,
+            } => vec![cr.index(), an.index()],
+            Self::Two {
+                cr,
+                an,
+            } => vec![cr.0.index(), cr.1.index(), an.0.index(), an.1.index()],
+        };
+        writer.write_all(&[u8::try_from(indices.len()).expect("at most 4")])?;
+        for index in indices {
+            writer.write_all(&index.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn read_code<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let [len] = read_bytes(reader)?;
+        let mut index = || {
+            read_bytes(reader)
+                .map(|buf| Orbital::with_index(u32::from_le_bytes(buf)))
+        };
+        let code = match len {
+            0 => Some(Self::Offset),
+            2 => Self::one_electron(Cr(index()?), An(index()?)),
+            4 => Self::two_electron(
+                (Cr(index()?), Cr(index()?)),
+                (An(index()?), An(index()?)),
+            ),
+            _ => {
+                return Err(Error::Parse {
+                    msg: format!("binary: invalid number of indices: {len}"),
+                })
+            }
+        };
+
+        code.ok_or_else(|| Error::Parse {
+            msg: "binary: indices not in canonical order".to_string(),
+        })
+    }
+}
+
+/// Stream terms in the binary format.
+///
+/// The number of terms is written in the header.  If it is not known in
+/// advance, use [`Writer::with_unknown_num_terms()`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     io::binary::{Reader, Writer},
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// let mut buf = Vec::new();
+/// let mut writer = Writer::new(&mut buf, 2).unwrap();
+/// writer.write_term(0.5, &Pauli::identity()).unwrap();
+/// writer
+///     .write_term(1.5, &Pauli::with_ops([PauliOp::X]))
+///     .unwrap();
+/// writer.finish().unwrap();
+///
+/// assert_eq!(buf.len(), 16 + 2 * 24);
+///
+/// let reader = Reader::<_, Pauli>::new(buf.as_slice())?;
+/// assert_eq!(reader.num_terms(), Some(2));
+///
+/// let terms = reader.collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(terms[1], (1.5, Pauli::with_ops([PauliOp::X])));
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Writer<W, K> {
+    writer:    W,
+    remaining: Option<u64>,
+    phantom:   PhantomData<K>,
+}
+
+impl<W, K> Writer<W, K>
+where
+    W: Write,
+    K: BinaryCode,
+{
+    /// Write the header for `num_terms` terms.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if writing fails.
+    pub fn new(
+        mut writer: W,
+        num_terms: u64,
+    ) -> std::io::Result<Self> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&K::ENCODING.to_le_bytes())?;
+        writer.write_all(&num_terms.to_le_bytes())?;
+
+        Ok(Self {
+            writer,
+            remaining: (num_terms != UNKNOWN_NUM_TERMS).then_some(num_terms),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Write the header for any number of terms.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::Pauli,
+    /// #     io::binary::{read_sum, Writer},
+    /// #     terms::SumRepr,
+    /// # };
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// let mut buf = Vec::new();
+    /// let mut writer = Writer::with_unknown_num_terms(&mut buf).unwrap();
+    /// for _ in 0..3 {
+    ///     writer.write_term(0.5, &Pauli::identity()).unwrap();
+    /// }
+    /// writer.finish().unwrap();
+    ///
+    /// // Terms with the same code are summed up
+    /// let repr: SumRepr<f64, Pauli> = read_sum(buf.as_slice())?;
+    /// assert_eq!(repr.coeff(Pauli::identity()), Some(&1.5));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn with_unknown_num_terms(writer: W) -> std::io::Result<Self> {
+        Self::new(writer, UNKNOWN_NUM_TERMS)
+    }
+
+    /// Write a term.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if writing fails, or if all terms declared in the
+    /// header have already been written.
+    pub fn write_term(
+        &mut self,
+        coeff: f64,
+        code: &K,
+    ) -> std::io::Result<()> {
+        if self.remaining == Some(0) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "more terms than declared in the header",
+            ));
+        }
+        self.writer.write_all(&coeff.to_le_bytes())?;
+        code.write_code(&mut self.writer)?;
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= 1;
+        }
+
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if flushing fails, or if fewer terms than declared in
+    /// the header have been written.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        if self.remaining.is_some_and(|remaining| remaining != 0) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "fewer terms than declared in the header",
+            ));
+        }
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+/// Iterate over terms stored in the binary format.
+///
+/// See [`Writer`] for an example.
+#[derive(Debug)]
+pub struct Reader<R, K> {
+    reader:    R,
+    remaining: Option<u64>,
+    phantom:   PhantomData<K>,
+}
+
+impl<R, K> Reader<R, K>
+where
+    R: Read,
+    K: BinaryCode,
+{
+    /// Read and validate the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if reading fails, if the magic bytes or the version do
+    /// not match, or if the encoding is not the encoding of `K`.
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        if read_bytes(&mut reader)? != MAGIC {
+            return Err(Error::Parse {
+                msg: "binary: invalid magic bytes".to_string(),
+            });
+        }
+        let version = u16::from_le_bytes(read_bytes(&mut reader)?);
+        if version != VERSION {
+            return Err(Error::Parse {
+                msg: format!("binary: unsupported version: {version}"),
+            });
+        }
+        let encoding = u16::from_le_bytes(read_bytes(&mut reader)?);
+        if encoding != K::ENCODING {
+            return Err(Error::Parse {
+                msg: format!(
+                    "binary: expected encoding {}, found: {encoding}",
+                    K::ENCODING
+                ),
+            });
+        }
+        let num_terms = u64::from_le_bytes(read_bytes(&mut reader)?);
+        let remaining = (num_terms != UNKNOWN_NUM_TERMS).then_some(num_terms);
+
+        Ok(Self {
+            reader,
+            remaining,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Number of terms left to read, if known.
+    #[must_use]
+    pub fn num_terms(&self) -> Option<u64> {
+        self.remaining
+    }
+}
+
+impl<R, K> Iterator for Reader<R, K>
+where
+    R: Read,
+    K: BinaryCode,
+{
+    type Item = Result<(f64, K), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let coeff = match self.remaining {
+            Some(0) => return None,
+            Some(remaining) => {
+                self.remaining = Some(remaining - 1);
+                read_bytes(&mut self.reader).map(Some)
+            }
+            None => read_bytes_or_eof(&mut self.reader),
+        };
+        let term = match coeff {
+            Ok(Some(buf)) => K::read_code(&mut self.reader)
+                .map(|code| (f64::from_le_bytes(buf), code)),
+            Ok(None) => {
+                self.remaining = Some(0);
+                return None;
+            }
+            Err(e) => Err(e),
+        };
+        if term.is_err() {
+            self.remaining = Some(0);
+        }
+
+        Some(term)
+    }
+}
+
+/// Write the sum of terms in the binary format.
+///
+/// Terms are sorted by code: this is the canonical order of the format.
+///
+/// # Errors
+///
+/// Returns error if writing fails.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::fermions::Fermions,
+/// #     io::binary::{read_sum, write_sum},
+/// #     terms::SumRepr,
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// let repr = SumRepr::from([
+///     (1.0, Fermions::Offset),
+///     (0.5, Fermions::try_from((0, 1, 3, 2))?),
+/// ]);
+///
+/// let mut buf = Vec::new();
+/// write_sum(&repr, &mut buf).unwrap();
+/// assert_eq!(buf.len(), 16 + 9 + 25);
+///
+/// let other: SumRepr<f64, Fermions> = read_sum(buf.as_slice())?;
+/// assert_eq!(other.coeff(Fermions::try_from((0, 1, 3, 2))?), Some(&0.5));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn write_sum<K, W>(
+    repr: &SumRepr<f64, K>,
+    writer: W,
+) -> std::io::Result<()>
+where
+    K: BinaryCode + Ord,
+    W: Write,
+{
+    let num_terms = u64::try_from(repr.len()).expect("fewer than 2^64 terms");
+    let mut terms: Vec<_> = repr.iter().collect();
+    terms.sort_by_key(|(_, code)| *code);
+
+    let mut writer = Writer::new(writer, num_terms)?;
+    for (&coeff, code) in terms {
+        writer.write_term(coeff, code)?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// Read the sum of terms from the binary format.
+///
+/// # Errors
+///
+/// Returns error if reading fails, or if the data is invalid.
+pub fn read_sum<K, R>(reader: R) -> Result<SumRepr<f64, K>, Error>
+where
+    K: BinaryCode,
+    R: Read,
+{
+    let reader = Reader::new(reader)?;
+    let mut repr = SumRepr::with_capacity(
+        reader
+            .num_terms()
+            .and_then(|num_terms| usize::try_from(num_terms).ok())
+            .unwrap_or(0)
+            .min(1 << 20),
+    );
+    for term in reader {
+        let (coeff, code) = term?;
+        repr.add_term(code, coeff);
+    }
+
+    Ok(repr)
+}
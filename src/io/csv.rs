@@ -0,0 +1,282 @@
+//! Delimiter-separated values (CSV, TSV) of sums of terms.
+//!
+//! The first line is a header with column names, followed by one term per
+//! line.  Fermionic terms have columns for up to four spin-orbital indices
+//! in the canonical order, left empty if the term has fewer of them:
+//!
+//! ```text
+//! p,q,r,s,value
+//! ,,,,0.5
+//! 0,1,,,1
+//! 0,1,3,2,-0.25
+//! ```
+//!
+//! Pauli strings are written in the dense notation, together with their
+//! weight, i.e. the number of non-trivial operators:
+//!
+//! ```text
+//! pauli,weight,value
+//! I,0,-1
+//! XIZ,2,0.5
+//! ```
+//!
+//! Terms are written sorted by code.  On input, both notations of Pauli
+//! strings are accepted, and the weight is ignored.  Fields are not quoted:
+//! none of them can contain the delimiter.  Empty lines are skipped.
+
+use std::{
+    fmt::Display,
+    io::{
+        BufRead,
+        Write,
+    },
+    str::FromStr,
+};
+
+use num::Num;
+
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::Pauli,
+        Code,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+/// Delimiter of comma-separated values.
+pub const CSV: char = ',';
+
+/// Delimiter of tab-separated values.
+pub const TSV: char = '\t';
+
+/// Codes that can be stored as delimiter-separated values.
+pub trait CsvCode: Code {
+    /// Names of the columns holding the code, without the value column.
+    const COLUMNS: &'static [&'static str];
+
+    /// Fields of the code, one for each column.
+    fn to_fields(&self) -> Vec<String>;
+
+    /// Parse the code from fields, one for each column.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the fields do not describe a valid code.
+    fn from_fields(fields: &[&str]) -> Result<Self, Error>;
+}
+
+impl CsvCode for Fermions {
+    const COLUMNS: &'static [&'static str] = &["p", "q", "r", "s"];
+
+    fn to_fields(&self) -> Vec<String> {
+        let indices = match self {
+            Fermions::Offset => vec![],
+            Fermions::One {
+                cr,
+                an,
+            } => vec![cr.index(), an.index()],
+            Fermions::Two {
+                cr,
+                an,
+            } => vec![cr.0.index(), cr.1.index(), an.0.index(), an.1.index()],
+        };
+        let mut fields: Vec<_> =
+            indices.iter().map(ToString::to_string).collect();
+        fields.resize(Self::COLUMNS.len(), String::new());
+
+        fields
+    }
+
+    fn from_fields(fields: &[&str]) -> Result<Self, Error> {
+        let mut indices = Vec::with_capacity(fields.len());
+        for field in fields.iter().take_while(|field| !field.is_empty()) {
+            indices.push(field.parse::<u32>().map_err(|_| Error::Parse {
+                msg: format!("invalid orbital index: {field:?}"),
+            })?);
+        }
+        if fields[indices.len()..]
+            .iter()
+            .any(|field| !field.is_empty())
+        {
+            return Err(Error::Parse {
+                msg: "orbital indices must not be interleaved with empty \
+                      fields"
+                    .to_string(),
+            });
+        }
+        match indices[..] {
+            [] => Ok(Fermions::Offset),
+            [p, q] => Fermions::try_from((p, q)),
+            [p, q, r, s] => Fermions::try_from((p, q, r, s)),
+            _ => Err(Error::Parse {
+                msg: format!(
+                    "number of orbital indices must be 0, 2 or 4: {}",
+                    indices.len()
+                ),
+            }),
+        }
+    }
+}
+
+impl CsvCode for Pauli {
+    const COLUMNS: &'static [&'static str] = &["pauli", "weight"];
+
+    fn to_fields(&self) -> Vec<String> {
+        vec![self.to_string(), self.num_nontrivial().to_string()]
+    }
+
+    fn from_fields(fields: &[&str]) -> Result<Self, Error> {
+        fields[0].parse()
+    }
+}
+
+/// Write the sum of terms as delimiter-separated values, with a header.
+///
+/// Use [`CSV`] or [`TSV`] as the delimiter.  Terms are sorted by code, so
+/// that equal sums are written as identical files.
+///
+/// # Errors
+///
+/// Returns error if writing fails.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     io::csv::{write_sum, TSV},
+/// #     terms::SumRepr,
+/// # };
+/// use PauliOp::{
+///     I,
+///     X,
+///     Z,
+/// };
+///
+/// let repr = SumRepr::from([(0.5, Pauli::with_ops([X, I, Z]))]);
+///
+/// let mut buf = Vec::new();
+/// write_sum(&repr, &mut buf, TSV).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     "pauli\tweight\tvalue\nXIZ\t2\t0.5\n"
+/// );
+/// ```
+pub fn write_sum<T, K, W>(
+    repr: &SumRepr<T, K>,
+    mut writer: W,
+    delimiter: char,
+) -> std::io::Result<()>
+where
+    T: Display,
+    K: CsvCode + Ord,
+    W: Write,
+{
+    let mut terms: Vec<_> = repr.iter().collect();
+    terms.sort_by_key(|(_, code)| *code);
+
+    let delimiter = delimiter.to_string();
+    writeln!(writer, "{}{delimiter}value", K::COLUMNS.join(&delimiter))?;
+    for (coeff, code) in terms {
+        writeln!(
+            writer,
+            "{}{delimiter}{coeff}",
+            code.to_fields().join(&delimiter)
+        )?;
+    }
+    writer.flush()
+}
+
+fn parse_err(
+    line_no: usize,
+    msg: &dyn Display,
+) -> Error {
+    Error::Parse {
+        msg: format!("csv: line {line_no}: {msg}"),
+    }
+}
+
+/// Read the sum of terms from delimiter-separated values.
+///
+/// The header is required and must name the columns as written by
+/// [`write_sum`].  Terms with the same code are summed up.
+///
+/// # Errors
+///
+/// Returns error if reading fails, if the header does not match, or if a
+/// line is invalid.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::fermions::Fermions,
+/// #     io::csv::{read_sum, CSV},
+/// #     terms::SumRepr,
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// let data = "p,q,r,s,value\n,,,,0.5\n0,1,,,1.0\n0,1,,,0.25\n";
+/// let repr: SumRepr<f64, Fermions> = read_sum(data.as_bytes(), CSV)?;
+///
+/// assert_eq!(repr.coeff(Fermions::Offset), Some(&0.5));
+/// assert_eq!(repr.coeff(Fermions::try_from((0, 1))?), Some(&1.25));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn read_sum<T, K, R>(
+    reader: R,
+    delimiter: char,
+) -> Result<SumRepr<T, K>, Error>
+where
+    T: Num + FromStr,
+    K: CsvCode,
+    R: BufRead,
+{
+    let num_columns = K::COLUMNS.len() + 1;
+    let mut repr = SumRepr::new();
+    let mut header = true;
+    for (line, line_no) in reader.lines().zip(1..) {
+        let line = line.map_err(|e| parse_err(line_no, &e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<_> = line.split(delimiter).map(str::trim).collect();
+        if fields.len() != num_columns {
+            return Err(parse_err(
+                line_no,
+                &format!("number of fields should be: {num_columns}"),
+            ));
+        }
+
+        if header {
+            if fields[..num_columns - 1] != *K::COLUMNS
+                || fields[num_columns - 1] != "value"
+            {
+                return Err(parse_err(
+                    line_no,
+                    &format!(
+                        "header should be: {}, value",
+                        K::COLUMNS.join(", ")
+                    ),
+                ));
+            }
+            header = false;
+            continue;
+        }
+
+        let code = K::from_fields(&fields[..num_columns - 1])
+            .map_err(|e| parse_err(line_no, &e))?;
+        let coeff = fields[num_columns - 1].parse().map_err(|_| {
+            parse_err(
+                line_no,
+                &format!("invalid value: {:?}", fields[num_columns - 1]),
+            )
+        })?;
+        repr.add_term(code, coeff);
+    }
+
+    Ok(repr)
+}
@@ -0,0 +1,355 @@
+//! JSON Lines format of sums of terms.
+//!
+//! Each line is a single term, an object with keys `code` and `value`, as
+//! in the `terms` of the JSON representation of [`SumRepr`].  The first line
+//! can be a header with keys `type` and `encoding`:
+//!
+//! ```text
+//! {"type":"sumrepr","encoding":"fermions"}
+//! {"code":[],"value":0.5}
+//! {"code":[0,1],"value":1.0}
+//! {"code":[0,1,3,2],"value":-0.25}
+//! ```
+//!
+//! Empty lines are skipped.  Since every line is independent, files can be
+//! appended to, split and concatenated with standard Unix tools (skipping
+//! headers other than the first one), and read incrementally with
+//! [`Reader`].  [`write_sum`] writes terms sorted by code.  Pauli strings
+//! are written in the dense notation.  Both notations are accepted on
+//! input.
+
+use std::{
+    io::{
+        BufRead,
+        Write,
+    },
+    marker::PhantomData,
+};
+
+use serde::{
+    de::DeserializeOwned,
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::Pauli,
+        Code,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+/// Codes that can be stored in the JSON Lines format.
+pub trait JsonLinesCode: Code + Serialize + DeserializeOwned {
+    /// Encoding in the header.
+    const ENCODING: &'static str;
+}
+
+impl JsonLinesCode for Fermions {
+    const ENCODING: &'static str = "fermions";
+}
+
+impl JsonLinesCode for Pauli {
+    const ENCODING: &'static str = "qubits";
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Header<'a> {
+    r#type:   &'a str,
+    encoding: &'a str,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Term<T, K> {
+    code:  K,
+    value: T,
+}
+
+/// Write terms one per line.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::fermions::Fermions,
+/// #     io::jsonl::{Reader, Writer},
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// let mut writer = Writer::with_header(Vec::new()).unwrap();
+/// writer.write_term(0.5, &Fermions::Offset).unwrap();
+/// writer
+///     .write_term(1.0, &Fermions::try_from((0, 1))?)
+///     .unwrap();
+/// let buf = writer.finish().unwrap();
+///
+/// let text = String::from_utf8(buf.clone()).unwrap();
+/// let lines: Vec<_> = text.lines().collect();
+/// assert_eq!(lines[0], r#"{"type":"sumrepr","encoding":"fermions"}"#);
+/// assert_eq!(lines[1], r#"{"code":[],"value":0.5}"#);
+/// assert_eq!(lines[2], r#"{"code":[0,1],"value":1.0}"#);
+///
+/// let terms = Reader::<_, f64, Fermions>::new(buf.as_slice())
+///     .collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(terms[1], (1.0, Fermions::try_from((0, 1))?));
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Writer<W, K> {
+    writer:  W,
+    phantom: PhantomData<K>,
+}
+
+impl<W, K> Writer<W, K>
+where
+    W: Write,
+    K: JsonLinesCode,
+{
+    /// Write terms without a header, e.g. to append them to a file.
+    #[must_use]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Write the header line.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if writing fails.
+    pub fn with_header(writer: W) -> std::io::Result<Self> {
+        let mut this = Self::new(writer);
+        serde_json::to_writer(
+            &mut this.writer,
+            &Header {
+                r#type:   "sumrepr",
+                encoding: K::ENCODING,
+            },
+        )?;
+        writeln!(this.writer)?;
+
+        Ok(this)
+    }
+
+    /// Write a term.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if writing fails.
+    pub fn write_term<T>(
+        &mut self,
+        coeff: T,
+        code: &K,
+    ) -> std::io::Result<()>
+    where
+        T: Serialize,
+    {
+        serde_json::to_writer(
+            &mut self.writer,
+            &Term {
+                code,
+                value: coeff,
+            },
+        )?;
+        writeln!(self.writer)
+    }
+
+    /// Flush and return the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if flushing fails.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+/// Iterate over terms stored in the JSON Lines format.
+///
+/// The header is optional.  If present, it must be the first non-empty line
+/// and match the encoding of `K`.  See [`Writer`] for an example.
+#[derive(Debug)]
+pub struct Reader<R, T, K> {
+    reader:  R,
+    line:    String,
+    line_no: usize,
+    done:    bool,
+    phantom: PhantomData<(T, K)>,
+}
+
+impl<R, T, K> Reader<R, T, K>
+where
+    R: BufRead,
+    T: DeserializeOwned,
+    K: JsonLinesCode,
+{
+    /// Read terms line by line.
+    ///
+    /// Nothing is read until the first call to `next()`.  The header, if
+    /// any, is then checked together with the first term.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_no: 0,
+            done: false,
+            phantom: PhantomData,
+        }
+    }
+
+    fn parse_err(
+        &self,
+        msg: &dyn std::fmt::Display,
+    ) -> Error {
+        Error::Parse {
+            msg: format!("jsonl: line {}: {msg}", self.line_no),
+        }
+    }
+
+    // Read the next non-empty line into `self.line`.  Returns `false` at
+    // the end of input.
+    fn read_line(&mut self) -> Result<bool, Error> {
+        loop {
+            self.line.clear();
+            self.line_no += 1;
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return Ok(false),
+                Ok(_) if self.line.trim().is_empty() => (),
+                Ok(_) => return Ok(true),
+                Err(e) => return Err(self.parse_err(&e)),
+            }
+        }
+    }
+
+    fn next_term(&mut self) -> Result<Option<(T, K)>, Error> {
+        let first = self.line_no == 0;
+        if !self.read_line()? {
+            return Ok(None);
+        }
+        if first {
+            if let Ok(header) = serde_json::from_str::<Header>(&self.line) {
+                if header.r#type != "sumrepr" {
+                    return Err(self.parse_err(&"type should be: 'sumrepr'"));
+                }
+                if header.encoding != K::ENCODING {
+                    return Err(self.parse_err(&format!(
+                        "encoding should be: '{}'",
+                        K::ENCODING
+                    )));
+                }
+                if !self.read_line()? {
+                    return Ok(None);
+                }
+            }
+        }
+        let term: Term<T, K> =
+            serde_json::from_str(&self.line).map_err(|e| self.parse_err(&e))?;
+
+        Ok(Some((term.value, term.code)))
+    }
+}
+
+impl<R, T, K> Iterator for Reader<R, T, K>
+where
+    R: BufRead,
+    T: DeserializeOwned,
+    K: JsonLinesCode,
+{
+    type Item = Result<(T, K), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let term = self.next_term().transpose();
+        if !matches!(term, Some(Ok(_))) {
+            self.done = true;
+        }
+
+        term
+    }
+}
+
+/// Write the sum of terms in the JSON Lines format, with a header.
+///
+/// Terms are sorted by code, so that equal sums are written as identical
+/// files.
+///
+/// # Errors
+///
+/// Returns error if writing fails.
+pub fn write_sum<T, K, W>(
+    repr: &SumRepr<T, K>,
+    writer: W,
+) -> std::io::Result<()>
+where
+    T: Serialize,
+    K: JsonLinesCode + Ord,
+    W: Write,
+{
+    let mut terms: Vec<_> = repr.iter().collect();
+    terms.sort_by_key(|(_, code)| *code);
+
+    let mut writer = Writer::with_header(writer)?;
+    for (coeff, code) in terms {
+        writer.write_term(coeff, code)?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// Read the sum of terms from the JSON Lines format.
+///
+/// Terms with the same code are summed up.
+///
+/// # Errors
+///
+/// Returns error if reading fails, or if a line is invalid.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     io::jsonl::read_sum,
+/// #     terms::SumRepr,
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// let data = r#"
+/// {"code":"XZ","value":0.5}
+///
+/// {"code":"X0 Z1","value":0.25}
+/// "#;
+/// let repr: SumRepr<f64, Pauli> = read_sum(data.as_bytes())?;
+///
+/// assert_eq!(
+///     repr.coeff(Pauli::with_ops([PauliOp::X, PauliOp::Z])),
+///     Some(&0.75)
+/// );
+/// #   Ok(())
+/// # }
+/// ```
+pub fn read_sum<T, K, R>(reader: R) -> Result<SumRepr<T, K>, Error>
+where
+    T: num::Num + DeserializeOwned,
+    K: JsonLinesCode,
+    R: BufRead,
+{
+    let mut repr = SumRepr::new();
+    for term in Reader::new(reader) {
+        let (coeff, code) = term?;
+        repr.add_term(code, coeff);
+    }
+
+    Ok(repr)
+}
@@ -0,0 +1,276 @@
+//! NumPy's `.npy` format of integral tensors and Pauli tables.
+//!
+//! Arrays are written in the version 1.0 of the format: little-endian, in
+//! C order.  They can be loaded with `numpy.load()` without any parsing on
+//! the Python side.
+//!
+//! A sum of fermionic terms is written as three consecutive arrays: the
+//! offset `c` (0-dimensional), the one-body array `h1` of shape `(n, n)`
+//! and the two-body array `h2` of shape `(n, n, n, n)`, where `n` is the
+//! number of spin-orbitals, at most [`MAX_SPIN_ORBITALS`], such that the
+//! Hamiltonian is:
+//!
+//! ```text
+//! H = c + sum_pq h1[p,q] a_p^ a_q + sum_pqrs h2[p,q,r,s] a_p^ a_q^ a_r a_s
+//! ```
+//!
+//! as in OpenFermion's `InteractionOperator`.  Each term of
+//! [`SumRepr<T, Fermions>`] other than the offset contributes together with
+//! its Hermitian conjugate.  The two-body array is not antisymmetrized.
+//! Consecutive arrays are read by calling `numpy.load()` repeatedly on the
+//! same file:
+//!
+//! ```python
+//! with open("h.npy", "rb") as f:
+//!     c, h1, h2 = np.load(f), np.load(f), np.load(f)
+//! ```
+//!
+//! A sum of Pauli strings is written as a one-dimensional structured array
+//! with fields `lo` and `hi` (`u64`): qubits 0..32 and 32..64 of the Pauli
+//! string, 2 bits each (see [`Pauli::index`]), and `coeff` (`f64`).  Rows
+//! are sorted by the Pauli string.
+
+use std::io::Write;
+
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::Pauli,
+    },
+    terms::SumRepr,
+};
+
+/// Magic bytes at the beginning of the file.
+pub const MAGIC: [u8; 6] = *b"\x93NUMPY";
+
+/// Largest number of spin-orbitals of the integral arrays.
+///
+/// The two-body array of 256 spin-orbitals takes 32 GiB.
+pub const MAX_SPIN_ORBITALS: usize = 256;
+
+/// Data type of the Pauli table.
+pub const PAULI_DESCR: &str =
+    "[('lo', '<u8'), ('hi', '<u8'), ('coeff', '<f8')]";
+
+// Magic, version and the length of the header, padded with spaces and
+// terminated by a newline, so that the data is aligned to 64 bytes.
+fn write_header<W: Write>(
+    writer: &mut W,
+    descr: &str,
+    shape: &[usize],
+) -> std::io::Result<()> {
+    let shape = match shape {
+        [n] => format!("({n},)"),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': {descr}, 'fortran_order': False, 'shape': {shape}, }}"
+    );
+    let len = MAGIC.len() + 4 + header.len() + 1;
+    header.extend(std::iter::repeat(' ').take((64 - len % 64) % 64));
+    header.push('\n');
+    let header_len = u16::try_from(header.len()).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "npy: header too long",
+        )
+    })?;
+
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&header_len.to_le_bytes())?;
+    writer.write_all(header.as_bytes())
+}
+
+fn write_f64_array<W: Write>(
+    writer: &mut W,
+    shape: &[usize],
+    data: &[f64],
+) -> std::io::Result<()> {
+    write_header(writer, "'<f8'", shape)?;
+    for x in data {
+        writer.write_all(&x.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Number of spin-orbitals needed to accommodate all terms.
+fn num_spin_orbitals(repr: &SumRepr<f64, Fermions>) -> u64 {
+    repr.iter()
+        .filter_map(|(_, code)| code.max_index())
+        .max()
+        .map_or(0, |index| u64::from(index) + 1)
+}
+
+fn invalid_input(msg: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, msg)
+}
+
+// Zero array of `len` elements, or error if it cannot be allocated.
+fn zeros(len: usize) -> std::io::Result<Vec<f64>> {
+    let mut v = Vec::new();
+    v.try_reserve_exact(len).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::OutOfMemory,
+            format!("npy: cannot allocate array of {len} elements"),
+        )
+    })?;
+    v.resize(len, 0.);
+
+    Ok(v)
+}
+
+/// Write the offset, the one-body and the two-body array of the sum of
+/// fermionic terms as three consecutive `.npy` arrays.
+///
+/// The arrays are dense: the two-body array of `n` spin-orbitals takes
+/// `8 n^4` bytes.
+///
+/// # Errors
+///
+/// Returns error of the kind [`InvalidInput`] if the number of
+/// spin-orbitals exceeds [`MAX_SPIN_ORBITALS`], of the kind
+/// [`OutOfMemory`] if the arrays cannot be allocated, or if writing fails.
+///
+/// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
+/// [`OutOfMemory`]: std::io::ErrorKind::OutOfMemory
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::fermions::Fermions,
+/// #     io::npy::write_integrals,
+/// #     terms::SumRepr,
+/// # };
+/// let repr = SumRepr::from([
+///     (1.5, Fermions::Offset),
+///     (0.5, Fermions::try_from((0, 1)).unwrap()),
+/// ]);
+///
+/// let mut buf = Vec::new();
+/// write_integrals(&repr, &mut buf).unwrap();
+///
+/// // The offset follows the first header
+/// assert_eq!(&buf[..6], b"\x93NUMPY");
+/// let header_len = usize::from(u16::from_le_bytes([buf[8], buf[9]]));
+/// let offset = &buf[10 + header_len..18 + header_len];
+/// assert_eq!(offset, &1.5_f64.to_le_bytes());
+///
+/// // Three arrays of two spin-orbitals, with headers of equal length
+/// assert_eq!(buf.len(), 3 * (10 + header_len) + 8 * (1 + 4 + 16));
+/// ```
+pub fn write_integrals<W>(
+    repr: &SumRepr<f64, Fermions>,
+    mut writer: W,
+) -> std::io::Result<()>
+where
+    W: Write,
+{
+    let n = num_spin_orbitals(repr);
+    let n = usize::try_from(n)
+        .ok()
+        .filter(|&n| n <= MAX_SPIN_ORBITALS)
+        .ok_or_else(|| {
+            invalid_input(format!(
+                "npy: number of spin-orbitals should be at most \
+                 {MAX_SPIN_ORBITALS}: {n}"
+            ))
+        })?;
+    let too_large =
+        || invalid_input(format!("npy: arrays of {n} spin-orbitals too large"));
+    let len2 = n.checked_mul(n).ok_or_else(too_large)?;
+    let len4 = len2.checked_mul(len2).ok_or_else(too_large)?;
+    let mut h1 = zeros(len2)?;
+    let mut h2 = zeros(len4)?;
+    let idx2 = |p: u32, q: u32| p as usize * n + q as usize;
+    let idx4 = |p: u32, q: u32, r: u32, s: u32| {
+        ((p as usize * n + q as usize) * n + r as usize) * n + s as usize
+    };
+
+    for (&coeff, code) in repr.iter() {
+        match code {
+            Fermions::Offset => (),
+            Fermions::One {
+                cr,
+                an,
+            } => {
+                let (p, q) = (cr.index(), an.index());
+                h1[idx2(p, q)] += coeff;
+                h1[idx2(q, p)] += coeff;
+            }
+            Fermions::Two {
+                cr,
+                an,
+            } => {
+                let (p, q) = (cr.0.index(), cr.1.index());
+                let (r, s) = (an.0.index(), an.1.index());
+                h2[idx4(p, q, r, s)] += coeff;
+                h2[idx4(s, r, q, p)] += coeff;
+            }
+        }
+    }
+
+    let offset = repr.coeff(Fermions::Offset).copied().unwrap_or(0.);
+    write_f64_array(&mut writer, &[], &[offset])?;
+    write_f64_array(&mut writer, &[n, n], &h1)?;
+    write_f64_array(&mut writer, &[n, n, n, n], &h2)?;
+    writer.flush()
+}
+
+/// Write the sum of Pauli strings as a `.npy` structured array of packed
+/// Pauli strings and coefficients.
+///
+/// Rows are sorted by the Pauli string, so that equal sums are written as
+/// identical files.  The data type of the array is [`PAULI_DESCR`].
+///
+/// # Errors
+///
+/// Returns error if writing fails.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::Pauli,
+/// #     io::npy::write_pauli_table,
+/// #     terms::SumRepr,
+/// # };
+/// let repr = SumRepr::from([(0.5, Pauli::new((3, 4)))]);
+///
+/// let mut buf = Vec::new();
+/// write_pauli_table(&repr, &mut buf).unwrap();
+///
+/// assert_eq!(buf.len() % 64, 24);
+/// let row = &buf[buf.len() - 24..];
+/// assert_eq!(&row[..8], &3_u64.to_le_bytes());
+/// assert_eq!(&row[8..16], &4_u64.to_le_bytes());
+/// assert_eq!(&row[16..], &0.5_f64.to_le_bytes());
+/// ```
+pub fn write_pauli_table<W>(
+    repr: &SumRepr<f64, Pauli>,
+    mut writer: W,
+) -> std::io::Result<()>
+where
+    W: Write,
+{
+    let mut terms: Vec<_> = repr.iter().collect();
+    terms.sort_by_key(|(_, code)| *code);
+
+    write_header(&mut writer, PAULI_DESCR, &[repr.len()])?;
+    for (coeff, code) in terms {
+        let index = code.index();
+        writer.write_all(&(index as u64).to_le_bytes())?;
+        writer.write_all(&((index >> 64) as u64).to_le_bytes())?;
+        writer.write_all(&coeff.to_le_bytes())?;
+    }
+    writer.flush()
+}
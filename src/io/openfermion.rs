@@ -0,0 +1,425 @@
+//! Text formats of OpenFermion operators.
+//!
+//! OpenFermion writes a `FermionOperator` as a sum of terms:
+//!
+//! ```text
+//! 0.5 [0^ 1^ 3 2] +
+//! -0.25 [1^ 0]
+//! ```
+//!
+//! where `p^` is the creation and `p` the annihilation operator of the
+//! spin-orbital `p`, and a `QubitOperator` as:
+//!
+//! ```text
+//! 0.25 [X0 Z1 Y3] +
+//! 1.0 []
+//! ```
+//!
+//! Spin-orbital indices are the same as the indices of [`Orbital`], i.e.
+//! OpenFermion's interleaved ordering.  Coefficients can be real numbers,
+//! or complex numbers in Python notation with vanishing imaginary part,
+//! e.g. `(0.5+0j)`.
+//!
+//! Each term `h` of [`SumRepr<T, Fermions>`] other than the offset stands
+//! for `h (T + T^\dagger)`.  Terms of a `FermionOperator` are brought to
+//! normal order and to the canonical order of [`Fermions`], and terms equal
+//! up to the Hermitian conjugate are combined.  Only the offset, one- and
+//! two-electron terms are supported.
+
+use std::{
+    io::Write,
+    str::FromStr,
+};
+
+use num::Float;
+
+use crate::{
+    code::{
+        fermions::{
+            An,
+            Cr,
+            Fermions,
+            Orbital,
+        },
+        qubits::Pauli,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+// Split text into pairs: (coefficient, content of square brackets).
+fn split_terms(text: &str) -> Result<Vec<(&str, &str)>, Error> {
+    let mut terms = Vec::new();
+    let mut rest = text.trim();
+    if rest == "0" {
+        return Ok(terms);
+    }
+    while let Some(open) = rest.find('[') {
+        let close = rest[open..].find(']').ok_or_else(|| Error::Parse {
+            msg: "missing closing bracket".to_string(),
+        })? + open;
+        terms.push((&rest[..open], &rest[open + 1..close]));
+        rest = &rest[close + 1..];
+    }
+    if !rest.trim().is_empty() {
+        return Err(Error::Parse {
+            msg: format!("unexpected text after the last term: {rest:?}"),
+        });
+    }
+
+    Ok(terms)
+}
+
+// Parse a real coefficient, or a complex one with vanishing imaginary part.
+// Leading '+' separating terms is skipped.  A missing coefficient is one.
+fn parse_coeff<T>(text: &str) -> Result<T, Error>
+where
+    T: Float + FromStr,
+{
+    let err = || Error::Parse {
+        msg: format!("invalid coefficient: {text:?}"),
+    };
+    let parse = |s: &str| s.trim().parse::<T>().map_err(|_| err());
+
+    let s = text.trim();
+    let s = s.strip_prefix('+').unwrap_or(s).trim();
+    match s {
+        "" | "+" => return Ok(T::one()),
+        "-" => return Ok(-T::one()),
+        _ => (),
+    }
+    let s = s
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap_or(s);
+    let Some(s) = s.strip_suffix('j') else {
+        return parse(s);
+    };
+
+    // Split complex number into real and imaginary parts.
+    let bytes = s.as_bytes();
+    let split = (1..bytes.len())
+        .rev()
+        .find(|&i| {
+            (bytes[i] == b'+' || bytes[i] == b'-')
+                && !matches!(bytes[i - 1], b'e' | b'E')
+        })
+        .unwrap_or(0);
+    let (re, im) = s.split_at(split);
+    let re = if re.is_empty() { T::zero() } else { parse(re)? };
+    if parse(im)? != T::zero() {
+        return Err(Error::Parse {
+            msg: format!("complex coefficient is not supported: {text:?}"),
+        });
+    }
+
+    Ok(re)
+}
+
+// Fermionic operator: (spin-orbital index, is creation operator)
+type Op = (u32, bool);
+
+fn parse_ops(text: &str) -> Result<Vec<Op>, Error> {
+    text.split_whitespace()
+        .map(|token| {
+            let (index, cr) = match token.strip_suffix('^') {
+                Some(index) => (index, true),
+                None => (token, false),
+            };
+            index
+                .parse()
+                .map(|index| (index, cr))
+                .map_err(|_| Error::Parse {
+                    msg: format!("invalid fermionic operator: {token:?}"),
+                })
+        })
+        .collect()
+}
+
+// Bring the product of operators to normal order, using the
+// anticommutation relation: a_p a_q^ = delta_pq - a_q^ a_p.
+fn normal_order(ops: Vec<Op>) -> Vec<(bool, Vec<Op>)> {
+    let mut stack = vec![(false, ops)];
+    let mut ordered = Vec::new();
+    while let Some((negative, ops)) = stack.pop() {
+        let Some(i) = ops.windows(2).position(|w| !w[0].1 && w[1].1) else {
+            ordered.push((negative, ops));
+            continue;
+        };
+        let mut swapped = ops.clone();
+        swapped.swap(i, i + 1);
+        stack.push((!negative, swapped));
+        if ops[i].0 == ops[i + 1].0 {
+            let mut contracted = ops;
+            contracted.drain(i..i + 2);
+            stack.push((negative, contracted));
+        }
+    }
+
+    ordered
+}
+
+// Canonical term of normal-ordered operators with the coefficient of
+// `SumRepr<T, Fermions>`.  Returns `None`, if the product vanishes.
+fn canonical<T>(
+    coeff: T,
+    ops: &[Op],
+) -> Result<Option<(T, Fermions)>, Error>
+where
+    T: Float,
+{
+    let orb = |i: usize| Orbital::with_index(ops[i].0);
+    let half = coeff / (T::one() + T::one());
+    let num_cr = ops.iter().filter(|op| op.1).count();
+
+    Ok(match (num_cr, ops.len()) {
+        (0, 0) => Some((coeff, Fermions::Offset)),
+        (1, 2) => Fermions::One {
+            cr: Cr(orb(0)),
+            an: An(orb(1)),
+        }
+        .relabel(half, |o| o),
+        (2, 4) => Fermions::Two {
+            cr: (Cr(orb(0)), Cr(orb(1))),
+            an: (An(orb(2)), An(orb(3))),
+        }
+        .relabel(half, |o| o)
+        .map(|(coeff, code)| match code {
+            // a_p^ a_q^ a_r a_p and its conjugate a_p^ a_r^ a_q a_p are both
+            // canonical.  Choose the one with q < r.
+            Fermions::Two {
+                cr: (p, q),
+                an: (r, s),
+            } if p.index() == s.index() && q.index() > r.index() => (
+                coeff,
+                Fermions::Two {
+                    cr: (p, Cr(r.0)),
+                    an: (An(q.0), s),
+                },
+            ),
+            _ => (coeff, code),
+        }),
+        _ => {
+            return Err(Error::Parse {
+                msg: "only offset, one- and two-electron terms are supported"
+                    .to_string(),
+            })
+        }
+    })
+}
+
+/// Parse OpenFermion's `FermionOperator` text format.
+///
+/// # Errors
+///
+/// Returns error if the text is malformed, if a coefficient is complex, or
+/// if a term is not an offset, a one- or a two-electron term.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::fermions::Fermions,
+/// #     io::openfermion::parse_fermion_operator,
+/// #     terms::SumRepr,
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// let repr: SumRepr<f64, _> =
+///     parse_fermion_operator("1.5 [] +\n0.5 [0^ 1] +\n0.5 [1^ 0]")?;
+///
+/// assert_eq!(repr.coeff(Fermions::Offset), Some(&1.5));
+/// assert_eq!(repr.coeff(Fermions::try_from((0, 1)).unwrap()), Some(&0.5));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn parse_fermion_operator<T>(
+    text: &str
+) -> Result<SumRepr<T, Fermions>, Error>
+where
+    T: Float + FromStr,
+{
+    let mut repr = SumRepr::new();
+    for (coeff, ops) in split_terms(text)? {
+        let coeff: T = parse_coeff(coeff)?;
+        for (negative, ops) in normal_order(parse_ops(ops)?) {
+            let coeff = if negative { -coeff } else { coeff };
+            if let Some((coeff, code)) = canonical(coeff, &ops)? {
+                repr.add_term(code, coeff);
+            }
+        }
+    }
+
+    Ok(repr)
+}
+
+/// Write the sum of fermionic terms in OpenFermion's `FermionOperator` text
+/// format.
+///
+/// Each term is written together with its Hermitian conjugate.
+///
+/// # Errors
+///
+/// Returns error if writing fails.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::fermions::Fermions,
+/// #     io::openfermion::write_fermion_operator,
+/// #     terms::SumRepr,
+/// # };
+/// let repr = SumRepr::from([(0.5, Fermions::try_from((0, 1)).unwrap())]);
+///
+/// let mut buf = Vec::new();
+/// write_fermion_operator(&repr, &mut buf).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     "0.5 [0^ 1] +\n0.5 [1^ 0]\n"
+/// );
+/// ```
+pub fn write_fermion_operator<T, W>(
+    repr: &SumRepr<T, Fermions>,
+    mut writer: W,
+) -> std::io::Result<()>
+where
+    T: Float + std::fmt::Display,
+    W: Write,
+{
+    let mut sep = "";
+    let mut write_term = |coeff: T, ops: String| {
+        let result = write!(writer, "{sep}{coeff} [{ops}]");
+        sep = " +\n";
+        result
+    };
+    for (&coeff, code) in repr.iter() {
+        match *code {
+            Fermions::Offset => write_term(coeff, String::new())?,
+            Fermions::One {
+                cr,
+                an,
+            } => {
+                let (p, q) = (cr.index(), an.index());
+                if p == q {
+                    write_term(coeff + coeff, format!("{p}^ {q}"))?;
+                } else {
+                    write_term(coeff, format!("{p}^ {q}"))?;
+                    write_term(coeff, format!("{q}^ {p}"))?;
+                }
+            }
+            Fermions::Two {
+                cr,
+                an,
+            } => {
+                let (p, q) = (cr.0.index(), cr.1.index());
+                let (r, s) = (an.0.index(), an.1.index());
+                if (p, q) == (s, r) {
+                    write_term(coeff + coeff, format!("{p}^ {q}^ {r} {s}"))?;
+                } else {
+                    write_term(coeff, format!("{p}^ {q}^ {r} {s}"))?;
+                    write_term(coeff, format!("{s}^ {r}^ {q} {p}"))?;
+                }
+            }
+        }
+    }
+    if !repr.is_empty() {
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/// Parse OpenFermion's `QubitOperator` text format.
+///
+/// # Errors
+///
+/// Returns error if the text is malformed, or if a coefficient is complex.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     io::openfermion::parse_qubit_operator,
+/// #     terms::SumRepr,
+/// # };
+/// use PauliOp::{
+///     I,
+///     X,
+///     Y,
+///     Z,
+/// };
+///
+/// # fn main() -> Result<(), f2q::Error> {
+/// let repr: SumRepr<f64, _> =
+///     parse_qubit_operator("(0.25+0j) [X0 Z1 Y3] +\n-1.0 []")?;
+///
+/// assert_eq!(repr.coeff(Pauli::with_ops([X, Z, I, Y])), Some(&0.25));
+/// assert_eq!(repr.coeff(Pauli::identity()), Some(&-1.0));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn parse_qubit_operator<T>(text: &str) -> Result<SumRepr<T, Pauli>, Error>
+where
+    T: Float + FromStr,
+{
+    let mut repr = SumRepr::new();
+    for (coeff, code) in split_terms(text)? {
+        repr.add_term(Pauli::from_sparse(code)?, parse_coeff(coeff)?);
+    }
+
+    Ok(repr)
+}
+
+/// Write the sum of Pauli strings in OpenFermion's `QubitOperator` text
+/// format.
+///
+/// # Errors
+///
+/// Returns error if writing fails.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     io::openfermion::write_qubit_operator,
+/// #     terms::SumRepr,
+/// # };
+/// use PauliOp::{
+///     I,
+///     X,
+///     Y,
+/// };
+///
+/// let repr = SumRepr::from([(0.25, Pauli::with_ops([X, I, Y]))]);
+///
+/// let mut buf = Vec::new();
+/// write_qubit_operator(&repr, &mut buf).unwrap();
+///
+/// assert_eq!(String::from_utf8(buf).unwrap(), "0.25 [X0 Y2]\n");
+/// ```
+pub fn write_qubit_operator<T, W>(
+    repr: &SumRepr<T, Pauli>,
+    mut writer: W,
+) -> std::io::Result<()>
+where
+    T: Float + std::fmt::Display,
+    W: Write,
+{
+    let mut sep = "";
+    for (&coeff, code) in repr.iter() {
+        if *code == Pauli::identity() {
+            write!(writer, "{sep}{coeff} []")?;
+        } else {
+            write!(writer, "{sep}{coeff} [{}]", code.to_sparse())?;
+        }
+        sep = " +\n";
+    }
+    if !repr.is_empty() {
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,255 @@
+//! Qiskit's `SparsePauliOp` as JSON.
+//!
+//! A sum of Pauli strings is a list of pairs: `[label, coeff]`, as returned
+//! by `SparsePauliOp.to_list()`:
+//!
+//! ```text
+//! [["IZYX", [0.5, 0.0]], ["IIII", [-1.0, 0.0]]]
+//! ```
+//!
+//! Qiskit's labels are little-endian: the *last* character of the label is
+//! the operator on qubit 0.  All labels have the same length: the size of
+//! the register.  Complex coefficients are pairs: `[re, im]`.  On import,
+//! a coefficient can also be a real number, and the imaginary part must be
+//! negligible: at most [`IMAG_TOLERANCE`] times the real part or one,
+//! whichever is larger, in absolute value.  The list can be loaded in Python
+//! with:
+//!
+//! ```python
+//! SparsePauliOp.from_list([(l, complex(*c)) for l, c in json.load(f)])
+//! ```
+
+use std::io::{
+    Read,
+    Write,
+};
+
+use num::Float;
+use serde::{
+    de::DeserializeOwned,
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+/// Qiskit label of the Pauli string on `num_qubits` qubits.
+///
+/// Operators outside of the register are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     io::qiskit::to_label,
+/// # };
+/// use PauliOp::{
+///     I,
+///     X,
+///     Y,
+/// };
+///
+/// let code = Pauli::with_ops([X, I, Y]);
+///
+/// assert_eq!(to_label(&code, 4), "IYIX");
+/// ```
+#[must_use]
+pub fn to_label(
+    code: &Pauli,
+    num_qubits: u16,
+) -> String {
+    let mut label: Vec<_> = code
+        .into_iter()
+        .take(usize::from(num_qubits))
+        .map(String::from)
+        .collect();
+    label.reverse();
+    label.concat()
+}
+
+/// Parse Qiskit label of a Pauli string.
+///
+/// # Errors
+///
+/// Returns error if the label is longer than 64 characters, or if it
+/// contains a character other than `I`, `X`, `Y`, `Z`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     io::qiskit::from_label,
+/// # };
+/// use PauliOp::{
+///     I,
+///     X,
+///     Y,
+/// };
+///
+/// # fn main() -> Result<(), f2q::Error> {
+/// assert_eq!(from_label("IYIX")?, Pauli::with_ops([X, I, Y]));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn from_label(label: &str) -> Result<Pauli, Error> {
+    if label.len() > 64 {
+        return Err(Error::QubitIndex {
+            msg: format!("label longer than 64 qubits: {label:?}"),
+        });
+    }
+    label
+        .chars()
+        .rev()
+        .map(|ch| match ch {
+            'I' => Ok(PauliOp::I),
+            'X' => Ok(PauliOp::X),
+            'Y' => Ok(PauliOp::Y),
+            'Z' => Ok(PauliOp::Z),
+            _ => Err(Error::Parse {
+                msg: format!("unknown Pauli operator in label: {label:?}"),
+            }),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Pauli::with_ops)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Coeff<T> {
+    Real(T),
+    Complex([T; 2]),
+}
+
+/// Largest imaginary part of an imported coefficient, relative to its real
+/// part, or absolute if the real part is smaller than one.
+///
+/// Coefficients of Hermitian operators computed numerically, e.g. with
+/// `SparsePauliOp.simplify()`, can carry an imaginary part of the order of
+/// the machine precision.  It is discarded on import.
+pub const IMAG_TOLERANCE: f64 = 1e-12;
+
+/// Write the sum of Pauli strings as a JSON list of `[label, [re, im]]`
+/// pairs.
+///
+/// The size of the register is the smallest one that fits all terms, but
+/// at least one qubit.
+///
+/// # Errors
+///
+/// Returns error if writing fails.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     io::qiskit::write_sparse_pauli_op,
+/// #     terms::SumRepr,
+/// # };
+/// use PauliOp::{
+///     X,
+///     Z,
+/// };
+///
+/// let repr = SumRepr::from([(0.5, Pauli::with_ops([X, Z]))]);
+///
+/// let mut buf = Vec::new();
+/// write_sparse_pauli_op(&repr, &mut buf).unwrap();
+///
+/// assert_eq!(String::from_utf8(buf).unwrap(), r#"[["ZX",[0.5,0.0]]]"#);
+/// ```
+pub fn write_sparse_pauli_op<T, W>(
+    repr: &SumRepr<T, Pauli>,
+    writer: W,
+) -> std::io::Result<()>
+where
+    T: Float + Serialize,
+    W: Write,
+{
+    let num_qubits = repr
+        .iter()
+        .map(|(_, code)| u16::from(code.min_register_size()))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let list: Vec<_> = repr
+        .iter()
+        .map(|(&coeff, code)| (to_label(code, num_qubits), [coeff, T::zero()]))
+        .collect();
+
+    Ok(serde_json::to_writer(writer, &list)?)
+}
+
+/// Read the sum of Pauli strings from a JSON list of `[label, coeff]`
+/// pairs.
+///
+/// # Errors
+///
+/// Returns error if the input is malformed, if a label is invalid, or if
+/// the imaginary part of a coefficient is larger than [`IMAG_TOLERANCE`]
+/// times its real part or one, whichever is larger, in absolute value.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     io::qiskit::read_sparse_pauli_op,
+/// #     terms::SumRepr,
+/// # };
+/// use PauliOp::{
+///     X,
+///     Z,
+/// };
+///
+/// # fn main() -> Result<(), f2q::Error> {
+/// let data = r#"[["ZX", [0.5, 0.0]], ["II", -1.0]]"#;
+/// let repr: SumRepr<f64, _> = read_sparse_pauli_op(data.as_bytes())?;
+///
+/// assert_eq!(repr.coeff(Pauli::with_ops([X, Z])), Some(&0.5));
+/// assert_eq!(repr.coeff(Pauli::identity()), Some(&-1.0));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn read_sparse_pauli_op<T, R>(reader: R) -> Result<SumRepr<T, Pauli>, Error>
+where
+    T: Float + DeserializeOwned,
+    R: Read,
+{
+    let list: Vec<(String, Coeff<T>)> = serde_json::from_reader(reader)
+        .map_err(|e| Error::Parse {
+            msg: format!("{e}"),
+        })?;
+
+    let tolerance = T::from(IMAG_TOLERANCE).expect("conversion from f64");
+    let mut repr = SumRepr::with_capacity(list.len());
+    for (label, coeff) in list {
+        let coeff = match coeff {
+            Coeff::Real(re) => re,
+            Coeff::Complex([re, im])
+                if im.abs() <= tolerance * re.abs().max(T::one()) =>
+            {
+                re
+            }
+            Coeff::Complex(_) => {
+                return Err(Error::Parse {
+                    msg: format!(
+                        "complex coefficient is not supported: {label:?}"
+                    ),
+                })
+            }
+        };
+        repr.add_term(from_label(&label)?, coeff);
+    }
+
+    Ok(repr)
+}
@@ -0,0 +1,692 @@
+//! Lanczos eigensolver for Pauli and fermionic Hamiltonians.
+//!
+//! The Hamiltonian is never stored as a matrix.  Instead, it is applied
+//! directly to state vectors using the packed representation of Pauli
+//! strings (see [`sparse`](crate::sparse)).
+//!
+//! The lowest eigenvalues are found one by one: each eigenvector found is
+//! projected out of the Krylov space of the next Lanczos run.  Each run keeps
+//! a Krylov basis of at most [`Lanczos::basis_size`] vectors: new vectors are
+//! fully reorthogonalized against it.  Once the basis is full, the run is
+//! restarted from the current Ritz vector and the residual vector (thick
+//! restart).
+//!
+//! Memory use is at most `basis_size + num_eigenvalues + 2` vectors of the
+//! dimension of the space, 16 bytes per coordinate.  With the default basis
+//! size, the full Hilbert space of 26 qubits takes about 24 GiB.
+
+use num::{
+    complex::Complex,
+    Float,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use rayon::prelude::*;
+
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::Pauli,
+    },
+    map::JordanWigner,
+    sparse::{
+        apply_blocks,
+        blocks,
+        Block,
+    },
+    terms::{
+        SumRepr,
+        Terms,
+    },
+    Error,
+};
+
+type C64 = Complex<f64>;
+
+/// Largest register accepted by the solver.
+///
+/// A state vector of 32 qubits takes 64 GiB, so the full Hilbert space of
+/// this size is out of reach of most machines.  Subspaces of fixed particle
+/// number are much smaller.
+pub const MAX_QUBITS: u16 = 32;
+
+/// Lanczos eigensolver.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lanczos {
+    /// Number of lowest eigenvalues to find
+    pub num_eigenvalues: usize,
+    /// Maximal number of iterations for each eigenvalue
+    pub max_iterations:  usize,
+    /// Maximal number of Krylov vectors kept in memory (at least 2)
+    pub basis_size:      usize,
+    /// Relative tolerance of the residual norm
+    pub tolerance:       f64,
+    /// Compute eigenvectors
+    pub eigenvectors:    bool,
+    /// Seed of the random initial vector
+    pub seed:            u64,
+}
+
+impl Default for Lanczos {
+    fn default() -> Self {
+        Self {
+            num_eigenvalues: 1,
+            max_iterations:  500,
+            basis_size:      20,
+            tolerance:       1e-10,
+            eigenvectors:    false,
+            seed:            0,
+        }
+    }
+}
+
+/// Eigenvalues and eigenvectors found by [`Lanczos`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Eigen {
+    /// Lowest eigenvalues in the increasing order
+    pub values:  Vec<f64>,
+    /// Normalized eigenvectors, if requested, with coordinates in `basis`
+    pub vectors: Vec<Vec<C64>>,
+    /// Computational basis states spanning the subspace, in which
+    /// eigenvectors are expressed.  `None` means the full Hilbert space,
+    /// where the coordinate `b` corresponds to the basis state `|b>`.
+    pub basis:   Option<Vec<u64>>,
+}
+
+impl Lanczos {
+    /// Find the lowest eigenvalues of a Pauli Hamiltonian acting on
+    /// `num_qubits` qubits.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a term does not fit into the register, if the
+    /// Hilbert space is too small to contain the requested number of
+    /// eigenvalues, or if the solver does not converge.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     lanczos::Lanczos,
+    /// #     terms::SumRepr,
+    /// # };
+    /// use PauliOp::{
+    ///     X,
+    ///     Y,
+    ///     Z,
+    /// };
+    ///
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// // Heisenberg model on two qubits: singlet and triplet
+    /// let repr = SumRepr::from([
+    ///     (1.0, Pauli::with_ops([X, X])),
+    ///     (1.0, Pauli::with_ops([Y, Y])),
+    ///     (1.0, Pauli::with_ops([Z, Z])),
+    /// ]);
+    ///
+    /// let solver = Lanczos {
+    ///     num_eigenvalues: 2,
+    ///     ..Default::default()
+    /// };
+    /// let eigen = solver.solve(&repr, 2)?;
+    ///
+    /// assert!((eigen.values[0] + 3.0).abs() < 1e-10);
+    /// assert!((eigen.values[1] - 1.0).abs() < 1e-10);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn solve<T>(
+        &self,
+        repr: &SumRepr<T, Pauli>,
+        num_qubits: u16,
+    ) -> Result<Eigen, Error>
+    where
+        T: Float,
+    {
+        check_register(repr, num_qubits)?;
+        let space = Space::Full(1 << num_qubits);
+        let mut eigen = self.run(&Operator::new(repr), &space)?;
+        eigen.basis = None;
+
+        Ok(eigen)
+    }
+
+    /// Find the lowest eigenvalues of a fermionic Hamiltonian restricted to
+    /// states of `num_electrons` electrons in `num_qubits` spin-orbitals.
+    ///
+    /// The Hamiltonian is mapped to qubits with the Jordan-Wigner mapping in
+    /// the interleaved ordering of spin-orbitals.  The states of fixed
+    /// particle number are then basis states of fixed Hamming weight.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a term does not fit into the register, if the
+    /// subspace is too small to contain the requested number of eigenvalues,
+    /// or if the solver does not converge.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::fermions::{An, Cr, Fermions, Orbital},
+    /// #     lanczos::Lanczos,
+    /// #     terms::SumRepr,
+    /// # };
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// // Two sites with hopping: -t (a_0^+ a_2 + a_2^+ a_0), t = 1.0
+    /// let hopping = Fermions::one_electron(
+    ///     Cr(Orbital::with_index(0)),
+    ///     An(Orbital::with_index(2)),
+    /// )
+    /// .unwrap();
+    /// let repr = SumRepr::from([(-1.0, hopping)]);
+    ///
+    /// let eigen = Lanczos::default().solve_fermions(&repr, 4, 1)?;
+    /// assert!((eigen.values[0] + 1.0).abs() < 1e-10);
+    /// assert_eq!(eigen.basis.unwrap().len(), 4);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn solve_fermions<T>(
+        &self,
+        repr: &SumRepr<T, Fermions>,
+        num_qubits: u16,
+        num_electrons: u16,
+    ) -> Result<Eigen, Error>
+    where
+        T: Float,
+    {
+        let mut qubit_repr = SumRepr::with_capacity(repr.len() * 4);
+        JordanWigner::new(repr).add_to(&mut qubit_repr)?;
+        check_register(&qubit_repr, num_qubits)?;
+        if num_electrons > num_qubits {
+            return Err(Error::Eigensolver {
+                msg: format!(
+                    "{num_electrons} electrons do not fit into {num_qubits} \
+                     spin-orbitals"
+                ),
+            });
+        }
+
+        let space = Space::fixed_weight(num_qubits, num_electrons);
+        let mut eigen = self.run(&Operator::new(&qubit_repr), &space)?;
+        if let Space::Fixed(states) = space {
+            eigen.basis = Some(states);
+        }
+
+        Ok(eigen)
+    }
+
+    fn run(
+        &self,
+        op: &Operator,
+        space: &Space,
+    ) -> Result<Eigen, Error> {
+        if self.num_eigenvalues > space.dim() {
+            return Err(Error::Eigensolver {
+                msg: format!(
+                    "cannot find {} eigenvalues in a space of dimension {}",
+                    self.num_eigenvalues,
+                    space.dim()
+                ),
+            });
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut values = Vec::with_capacity(self.num_eigenvalues);
+        let mut locked: Vec<Vec<C64>> =
+            Vec::with_capacity(self.num_eigenvalues);
+        for k in 0..self.num_eigenvalues {
+            let mut start = zeros(space.dim())?;
+            for x in &mut start {
+                *x = C64::new(
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
+                );
+            }
+            let keep = self.eigenvectors || k + 1 < self.num_eigenvalues;
+            let (value, vector) =
+                self.lowest(op, space, &locked, start, keep)?;
+            values.push(value);
+            locked.extend(vector);
+        }
+        if !self.eigenvectors {
+            locked.clear();
+        }
+
+        Ok(Eigen {
+            values,
+            vectors: locked,
+            basis: None,
+        })
+    }
+
+    // Lowest eigenvalue in the complement of `locked` and, if `vector` is
+    // true, its Ritz vector.
+    fn lowest(
+        &self,
+        op: &Operator,
+        space: &Space,
+        locked: &[Vec<C64>],
+        start: Vec<C64>,
+        vector: bool,
+    ) -> Result<(f64, Option<Vec<C64>>), Error> {
+        let dim = space.dim() - locked.len();
+        let max_iterations = self.max_iterations.max(1);
+        let basis_size = self.basis_size.max(2);
+        let mut recurrence = Recurrence::new(op, space, locked, start)?;
+        for j in 0..max_iterations {
+            let beta = recurrence.step()?;
+            let len = recurrence.alphas.len();
+            let full = len == basis_size;
+            let check = j % 10 == 9
+                || j + 1 == max_iterations
+                || beta < 1e-12
+                || full
+                || len == dim;
+            if !check {
+                continue;
+            }
+            let (values, vectors) =
+                tridiagonal_eigen(&recurrence.alphas, &recurrence.betas)?;
+            let theta = values[0];
+            let residual = beta * vectors[0].last().expect("nonempty").abs();
+            if beta < 1e-12
+                || residual <= self.tolerance * theta.abs().max(1.0)
+                || len == dim
+            {
+                let ritz = vector
+                    .then(|| recurrence.ritz_vector(&vectors[0]))
+                    .transpose()?;
+                return Ok((theta, ritz));
+            }
+            if full {
+                recurrence.restart(theta, &vectors[0]);
+            }
+        }
+
+        Err(Error::Eigensolver {
+            msg: format!(
+                "Lanczos did not converge in {max_iterations} iterations"
+            ),
+        })
+    }
+}
+
+fn check_register<T>(
+    repr: &SumRepr<T, Pauli>,
+    num_qubits: u16,
+) -> Result<(), Error>
+where
+    T: Float,
+{
+    if num_qubits > MAX_QUBITS {
+        return Err(Error::QubitIndex {
+            msg: format!("state vector of {num_qubits} qubits is too large"),
+        });
+    }
+    if repr
+        .iter()
+        .any(|(_, code)| u16::from(code.min_register_size()) > num_qubits)
+    {
+        return Err(Error::QubitIndex {
+            msg: format!("term does not fit into {num_qubits} qubits"),
+        });
+    }
+
+    Ok(())
+}
+
+// Subspace spanned by computational basis states.
+enum Space {
+    Full(usize),
+    // Sorted list of states
+    Fixed(Vec<u64>),
+}
+
+impl Space {
+    fn fixed_weight(
+        num_qubits: u16,
+        weight: u16,
+    ) -> Self {
+        let mut states = Vec::new();
+        if weight == 0 {
+            states.push(0);
+            return Self::Fixed(states);
+        }
+        // Gosper's hack: next number with the same number of set bits.
+        let mut b: u64 = (1 << weight) - 1;
+        while b < 1 << num_qubits {
+            states.push(b);
+            let c = b & b.wrapping_neg();
+            let r = b + c;
+            b = (((r ^ b) >> 2) / c) | r;
+        }
+        Self::Fixed(states)
+    }
+
+    fn dim(&self) -> usize {
+        match self {
+            Self::Full(dim) => *dim,
+            Self::Fixed(states) => states.len(),
+        }
+    }
+
+    fn state(
+        &self,
+        index: usize,
+    ) -> u64 {
+        match self {
+            Self::Full(_) => index as u64,
+            Self::Fixed(states) => states[index],
+        }
+    }
+
+    fn index(
+        &self,
+        state: u64,
+    ) -> Option<usize> {
+        match self {
+            Self::Full(dim) => {
+                usize::try_from(state).ok().filter(|&i| i < *dim)
+            }
+            Self::Fixed(states) => states.binary_search(&state).ok(),
+        }
+    }
+}
+
+pub(crate) struct Operator {
+    blocks: Vec<Block<f64>>,
+}
+
+impl Operator {
+    pub(crate) fn new<T>(repr: &SumRepr<T, Pauli>) -> Self
+    where
+        T: Float,
+    {
+        let to_c64 = |z: Complex<T>| {
+            C64::new(
+                z.re.to_f64().expect("conversion to f64"),
+                z.im.to_f64().expect("conversion to f64"),
+            )
+        };
+        let blocks = blocks(repr)
+            .into_iter()
+            .map(|block| Block {
+                x:     block.x,
+                terms: block
+                    .terms
+                    .into_iter()
+                    .map(|(value, z)| (to_c64(value), z))
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            blocks,
+        }
+    }
+
+    // output = H input, restricted to the subspace.
+    fn apply(
+        &self,
+        space: &Space,
+        input: &[C64],
+        output: &mut [C64],
+    ) {
+        apply_blocks(
+            &self.blocks,
+            input,
+            output,
+            |r| space.state(r),
+            |c| space.index(c),
+        );
+    }
+}
+
+fn dot(
+    a: &[C64],
+    b: &[C64],
+) -> C64 {
+    a.par_iter()
+        .zip(b)
+        .map(|(x, y)| x.conj() * y)
+        .reduce(|| C64::new(0., 0.), |x, y| x + y)
+}
+
+fn norm(a: &[C64]) -> f64 {
+    a.par_iter().map(C64::norm_sqr).sum::<f64>().sqrt()
+}
+
+fn project_out(
+    locked: &[Vec<C64>],
+    v: &mut [C64],
+) {
+    for u in locked {
+        let overlap = dot(u, v);
+        v.par_iter_mut().zip(u).for_each(|(x, y)| *x -= overlap * y);
+    }
+}
+
+// Allocate a zero vector, or return error if there is not enough memory.
+fn zeros(dim: usize) -> Result<Vec<C64>, Error> {
+    let mut v = Vec::new();
+    v.try_reserve_exact(dim).map_err(|_| Error::Eigensolver {
+        msg: format!("cannot allocate a vector of dimension {dim}"),
+    })?;
+    v.resize(dim, C64::new(0., 0.));
+
+    Ok(v)
+}
+
+// Three-term Lanczos recurrence: H v_j = beta_{j-1} v_{j-1} + alpha_j v_j
+// + beta_j v_{j+1}.
+struct Recurrence<'a> {
+    op:     &'a Operator,
+    space:  &'a Space,
+    locked: &'a [Vec<C64>],
+    // Lanczos vectors v_0, ..., v_j
+    basis:  Vec<Vec<C64>>,
+    work:   Vec<C64>,
+    alphas: Vec<f64>,
+    betas:  Vec<f64>,
+}
+
+impl<'a> Recurrence<'a> {
+    fn new(
+        op: &'a Operator,
+        space: &'a Space,
+        locked: &'a [Vec<C64>],
+        mut start: Vec<C64>,
+    ) -> Result<Self, Error> {
+        project_out(locked, &mut start);
+        let n = norm(&start);
+        if n < 1e-12 {
+            return Err(Error::Eigensolver {
+                msg: "initial vector lies in the span of eigenvectors"
+                    .to_string(),
+            });
+        }
+        start.par_iter_mut().for_each(|x| *x /= n);
+
+        Ok(Self {
+            op,
+            space,
+            locked,
+            work: zeros(start.len())?,
+            basis: vec![start],
+            alphas: Vec::new(),
+            betas: Vec::new(),
+        })
+    }
+
+    // Extend the Krylov basis by one vector.  Returns the last beta.
+    fn step(&mut self) -> Result<f64, Error> {
+        let j = self.basis.len() - 1;
+        let curr = &self.basis[j];
+        self.op.apply(self.space, curr, &mut self.work);
+        let alpha = dot(curr, &self.work).re;
+        self.work
+            .par_iter_mut()
+            .zip(curr)
+            .for_each(|(w, c)| *w -= c * alpha);
+        if let Some(&beta_prev) = self.betas.last() {
+            self.work
+                .par_iter_mut()
+                .zip(&self.basis[j - 1])
+                .for_each(|(w, p)| *w -= p * beta_prev);
+        }
+        project_out(self.locked, &mut self.work);
+        // Full reorthogonalization against the Krylov basis.
+        project_out(&self.basis, &mut self.work);
+        let beta = norm(&self.work);
+        self.alphas.push(alpha);
+        self.betas.push(beta);
+
+        if beta >= 1e-12 {
+            self.work.par_iter_mut().for_each(|w| *w /= beta);
+            let next = zeros(self.work.len())?;
+            self.basis.push(std::mem::replace(&mut self.work, next));
+        }
+
+        Ok(beta)
+    }
+
+    // Thick restart from the Ritz vector y = sum_j coeffs[j] v_j with Ritz
+    // value theta, and the last Lanczos vector v_m.  Since H y = theta y +
+    // beta_{m-1} coeffs[m-1] v_m, the recurrence continues from v_m with a
+    // tridiagonal matrix of the new basis y, v_m.
+    fn restart(
+        &mut self,
+        theta: f64,
+        coeffs: &[f64],
+    ) {
+        let m = self.alphas.len();
+        let beta = self.betas[m - 1] * coeffs[m - 1];
+        self.work.par_iter_mut().for_each(|x| *x = C64::new(0., 0.));
+        for (v, &s) in self.basis.iter().zip(coeffs) {
+            self.work
+                .par_iter_mut()
+                .zip(v)
+                .for_each(|(x, y)| *x += y * s);
+        }
+        let n = norm(&self.work);
+        self.work.par_iter_mut().for_each(|x| *x /= n);
+
+        let last = self.basis.pop().expect("basis extended by the last step");
+        self.basis.truncate(1);
+        std::mem::swap(&mut self.basis[0], &mut self.work);
+        self.basis.push(last);
+        self.alphas = vec![theta];
+        self.betas = vec![beta];
+    }
+
+    // Ritz vector: sum_j coeffs[j] v_j, where v_j are Lanczos vectors.
+    fn ritz_vector(
+        &self,
+        coeffs: &[f64],
+    ) -> Result<Vec<C64>, Error> {
+        let mut vector = zeros(self.work.len())?;
+        for (v, &s) in self.basis.iter().zip(coeffs) {
+            vector.par_iter_mut().zip(v).for_each(|(x, y)| *x += y * s);
+        }
+        project_out(self.locked, &mut vector);
+        let n = norm(&vector);
+        vector.par_iter_mut().for_each(|x| *x /= n);
+
+        Ok(vector)
+    }
+}
+
+// Eigenvalues (increasing) and eigenvectors of a real symmetric tridiagonal
+// matrix with diagonal `diag` and off-diagonal `off` (the last element of
+// `off` is ignored), by the implicit QL algorithm.  Returns error if an
+// eigenvalue does not converge in 100 iterations.
+pub(crate) fn tridiagonal_eigen(
+    diag: &[f64],
+    off: &[f64],
+) -> Result<(Vec<f64>, Vec<Vec<f64>>), Error> {
+    let n = diag.len();
+    let mut d = diag.to_vec();
+    let mut e: Vec<_> = off.iter().copied().take(n - 1).collect();
+    e.resize(n, 0.);
+    // z[k][i] is the k-th component of the i-th eigenvector
+    let mut z = vec![vec![0.; n]; n];
+    for (i, row) in z.iter_mut().enumerate() {
+        row[i] = 1.;
+    }
+
+    for l in 0..n {
+        let mut iter = 0;
+        loop {
+            let mut m = l;
+            while m + 1 < n {
+                let dd = d[m].abs() + d[m + 1].abs();
+                if e[m].abs() <= f64::EPSILON * dd {
+                    break;
+                }
+                m += 1;
+            }
+            if m == l {
+                break;
+            }
+            if iter == 100 {
+                return Err(Error::Eigensolver {
+                    msg: "tridiagonal QL did not converge in 100 iterations"
+                        .to_string(),
+                });
+            }
+            iter += 1;
+
+            let mut g = (d[l + 1] - d[l]) / (2. * e[l]);
+            let mut r = g.hypot(1.);
+            g = d[m] - d[l] + e[l] / (g + r.copysign(g));
+            let (mut s, mut c, mut p) = (1., 1., 0.);
+            let mut underflow = false;
+            for i in (l..m).rev() {
+                let f = s * e[i];
+                let b = c * e[i];
+                r = f.hypot(g);
+                e[i + 1] = r;
+                if r == 0. {
+                    d[i + 1] -= p;
+                    e[m] = 0.;
+                    underflow = true;
+                    break;
+                }
+                s = f / r;
+                c = g / r;
+                g = d[i + 1] - p;
+                r = (d[i] - g) * s + 2. * c * b;
+                p = s * r;
+                d[i + 1] = g + p;
+                g = c * r - b;
+                for row in &mut z {
+                    let f = row[i + 1];
+                    row[i + 1] = s * row[i] + c * f;
+                    row[i] = c * row[i] - s * f;
+                }
+            }
+            if underflow {
+                continue;
+            }
+            d[l] -= p;
+            e[l] = g;
+            e[m] = 0.;
+        }
+    }
+
+    let mut order: Vec<_> = (0..n).collect();
+    order.sort_by(|&a, &b| d[a].total_cmp(&d[b]));
+    let values = order.iter().map(|&i| d[i]).collect();
+    let vectors = order
+        .iter()
+        .map(|&i| z.iter().map(|row| row[i]).collect())
+        .collect();
+
+    Ok((values, vectors))
+}
@@ -8,18 +8,39 @@
 
 use std::fmt::Display;
 
+pub mod circuit;
 pub mod code;
+pub mod grouping;
+pub mod io;
+pub mod lanczos;
 pub mod map;
+pub mod measure;
+pub mod metrics;
+pub mod reference;
+pub mod sparse;
+pub mod stabilizer;
+pub mod statevector;
 pub mod terms;
+pub mod trotter;
 
 pub(crate) mod math;
 
 mod serialize;
 
+pub use math::Root4;
+
 #[derive(Debug, PartialEq)]
 pub enum Error {
     /// Invalid qubit index in a Pauli string
     QubitIndex { msg: String },
+    /// Pauli strings that should commute do not
+    NonCommuting { msg: String },
+    /// Eigensolver failed
+    Eigensolver { msg: String },
+    /// Invalid text representation
+    Parse { msg: String },
+    /// Coefficient that cannot be used, e.g. not finite
+    Coefficient { msg: String },
 }
 
 impl Display for Error {
@@ -31,6 +52,18 @@ impl Display for Error {
             Self::QubitIndex {
                 msg,
             } => write!(f, "PauliIndex: {msg}"),
+            Self::NonCommuting {
+                msg,
+            } => write!(f, "NonCommuting: {msg}"),
+            Self::Eigensolver {
+                msg,
+            } => write!(f, "Eigensolver: {msg}"),
+            Self::Parse {
+                msg,
+            } => write!(f, "Parse: {msg}"),
+            Self::Coefficient {
+                msg,
+            } => write!(f, "Coefficient: {msg}"),
         }
     }
 }
@@ -1,5 +1,9 @@
 //! Mappings between various encodings.
 
-pub use jordan_wigner::JordanWigner;
+pub use jordan_wigner::{
+    jordan_wigner_term,
+    JordanWigner,
+};
 
 mod jordan_wigner;
+pub mod reorder;
@@ -6,7 +6,7 @@ use crate::{
             An,
             Cr,
             Fermions,
-            Orbital,
+            OrbitalOrdering,
         },
         qubits::{
             Pauli,
@@ -22,24 +22,37 @@ use crate::{
 };
 
 enum Map {
-    An(Orbital),
-    Cr(Orbital),
+    An(u16),
+    Cr(u16),
 }
 
 macro_rules! impl_tryfrom_map {
     ($($Typ:tt)* ) => {
         $(
-            impl TryFrom<$Typ> for Map {
+            impl TryFrom<($Typ, OrbitalOrdering)> for Map {
                 type Error = Error;
 
-                fn try_from(value: $Typ) -> Result<Self, Self::Error> {
-                    (value.index() < 64)
-                        .then_some(Self::$Typ(value.0))
+                fn try_from(
+                    (value, ordering): ($Typ, OrbitalOrdering),
+                ) -> Result<Self, Self::Error> {
+                    ordering
+                        .checked_index(&value.0)
+                        .and_then(|index| u16::try_from(index).ok())
+                        .filter(|&index| index < 64)
+                        .map(Self::$Typ)
                         .ok_or_else(|| Error::QubitIndex {
                             msg: "orbital index must be within 0..=63".to_string(),
                         })
                 }
             }
+
+            impl TryFrom<$Typ> for Map {
+                type Error = Error;
+
+                fn try_from(value: $Typ) -> Result<Self, Self::Error> {
+                    Self::try_from((value, OrbitalOrdering::default()))
+                }
+            }
         )*
     };
 }
@@ -65,11 +78,9 @@ fn pauli_codes_from_index(index: u16) -> (Pauli, Pauli) {
 
 impl Map {
     fn index(&self) -> u16 {
-        u16::try_from(match self {
-            Self::An(an) => an.index(),
-            Self::Cr(cr) => cr.index(),
-        })
-        .expect("index within 0..=63")
+        match self {
+            Self::An(index) | Self::Cr(index) => *index,
+        }
     }
 
     fn mul_iter<'a, T, I>(
@@ -195,17 +206,143 @@ fn jw_map_four<'a, T: Float + 'a>(
 /// #   Ok(())
 /// # }
 /// ```
+///
+/// By default, orbitals are placed on the qubit register according to
+/// [`OrbitalOrdering::Interleaved`].  Use [`JordanWigner::with_ordering()`]
+/// to choose a different ordering.
 pub struct JordanWigner<'a, T> {
-    repr: &'a SumRepr<T, Fermions>,
+    repr:     &'a SumRepr<T, Fermions>,
+    ordering: OrbitalOrdering,
 }
 
 impl<'a, T> JordanWigner<'a, T> {
     #[must_use]
     pub fn new(repr: &'a SumRepr<T, Fermions>) -> Self {
+        Self::with_ordering(repr, OrbitalOrdering::default())
+    }
+
+    /// Map orbitals onto qubits according to `ordering`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::{
+    /// #         fermions::{An, Cr, Fermions, Orbital, OrbitalOrdering, Spin},
+    /// #         qubits::{Pauli, PauliOp},
+    /// #     },
+    /// #     map::JordanWigner,
+    /// #     terms::{PauliSum, SumRepr, Terms},
+    /// # };
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// let p = Orbital::new(1, Spin::Up);
+    /// let fermi_repr =
+    ///     SumRepr::from([(1.0, Fermions::one_electron(Cr(p), An(p)).unwrap())]);
+    ///
+    /// let ordering = OrbitalOrdering::Block {
+    ///     num_orbitals: 2,
+    /// };
+    /// let mut pauli_repr = PauliSum::new();
+    /// JordanWigner::with_ordering(&fermi_repr, ordering)
+    ///     .add_to(&mut pauli_repr)?;
+    ///
+    /// // The orbital is mapped onto qubit 3
+    /// let code_z3 = Pauli::with_ops([PauliOp::I, PauliOp::I, PauliOp::I, PauliOp::Z]);
+    /// assert_eq!(pauli_repr.coeff(code_z3), Some(&-1.0));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_ordering(
+        repr: &'a SumRepr<T, Fermions>,
+        ordering: OrbitalOrdering,
+    ) -> Self {
         Self {
             repr,
+            ordering,
+        }
+    }
+}
+
+/// Jordan-Wigner mapping of a single fermionic term.
+///
+/// Pauli strings of the mapped term are added to `repr`.  Use this function
+/// to map terms one at a time, e.g. as they are read from a file, without
+/// holding the whole sum in memory.
+///
+/// # Errors
+///
+/// Returns error if an orbital does not fit into the qubit register.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::{
+/// #         fermions::{Fermions, OrbitalOrdering},
+/// #         qubits::{Pauli, PauliOp},
+/// #     },
+/// #     map::jordan_wigner_term,
+/// #     terms::PauliSum,
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// let terms = [(0.5, Fermions::Offset), (1.0, Fermions::try_from((0, 0))?)];
+///
+/// let mut pauli_repr = PauliSum::new();
+/// for (coeff, code) in terms {
+///     jordan_wigner_term(
+///         coeff,
+///         code,
+///         OrbitalOrdering::Interleaved,
+///         &mut pauli_repr,
+///     )?;
+/// }
+///
+/// assert_eq!(pauli_repr.coeff(Pauli::identity()), Some(&1.5));
+/// assert_eq!(pauli_repr.coeff(Pauli::with_ops([PauliOp::Z])), Some(&-1.0));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn jordan_wigner_term<T>(
+    coeff: T,
+    code: Fermions,
+    ordering: OrbitalOrdering,
+    repr: &mut impl Extend<(T, Pauli)>,
+) -> Result<(), Error>
+where
+    T: Float,
+{
+    match code {
+        Fermions::Offset => {
+            repr.extend(Some((coeff, Pauli::identity())));
+        }
+        Fermions::One {
+            cr,
+            an,
+        } => {
+            let jw_cr = Map::try_from((cr, ordering))?;
+            let jw_an = Map::try_from((an, ordering))?;
+            repr.extend(jw_map_two(&jw_cr, &jw_an, coeff));
+        }
+        Fermions::Two {
+            cr,
+            an,
+        } => {
+            let jw_cr = (
+                Map::try_from((cr.0, ordering))?,
+                Map::try_from((cr.1, ordering))?,
+            );
+            let jw_an = (
+                Map::try_from((an.0, ordering))?,
+                Map::try_from((an.1, ordering))?,
+            );
+            repr.extend(jw_map_four(
+                &jw_cr.0, &jw_cr.1, &jw_an.0, &jw_an.1, coeff,
+            ));
         }
     }
+
+    Ok(())
 }
 
 impl<'a, T> Terms<(T, Pauli)> for JordanWigner<'a, T>
@@ -219,29 +356,7 @@ where
         repr: &mut impl Extend<(T, Pauli)>,
     ) -> Result<(), Error> {
         for (&coeff, &code) in self.repr.iter() {
-            match code {
-                Fermions::Offset => {
-                    repr.extend(Some((coeff, Pauli::identity())));
-                }
-                Fermions::One {
-                    cr,
-                    an,
-                } => {
-                    let jw_cr = Map::try_from(cr)?;
-                    let jw_an = Map::try_from(an)?;
-                    repr.extend(jw_map_two(&jw_cr, &jw_an, coeff));
-                }
-                Fermions::Two {
-                    cr,
-                    an,
-                } => {
-                    let jw_cr = (Map::try_from(cr.0)?, Map::try_from(cr.1)?);
-                    let jw_an = (Map::try_from(an.0)?, Map::try_from(an.1)?);
-                    repr.extend(jw_map_four(
-                        &jw_cr.0, &jw_cr.1, &jw_an.0, &jw_an.1, coeff,
-                    ));
-                }
-            }
+            jordan_wigner_term(coeff, code, self.ordering, repr)?;
         }
 
         Ok(())
@@ -255,6 +370,7 @@ mod tests {
     use ReIm::*;
 
     use super::*;
+    use crate::code::fermions::Orbital;
 
     #[test]
     fn mul_iter_01() {
@@ -0,0 +1,555 @@
+//! Reordering of spin-orbitals to reduce Jordan-Wigner string weight.
+//!
+//! The Pauli weight of a term produced by the Jordan-Wigner mapping depends
+//! on how far apart the orbitals of the term are placed on the qubit
+//! register.  The optimizer in this module looks for a permutation of
+//! orbital indices that reduces the total or maximum weight of the mapped
+//! Hamiltonian.
+
+use num::Float;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::{
+    code::fermions::{
+        Fermions,
+        Orbital,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+/// Quantity to minimize.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Objective {
+    /// Sum of weights over all terms
+    #[default]
+    Total,
+    /// Maximal weight of a term
+    Max,
+}
+
+/// Search strategy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Heuristic {
+    /// Place strongly connected orbitals next to each other.
+    #[default]
+    Greedy,
+    /// Simulated annealing with random transpositions of orbitals, starting
+    /// from the result of the greedy heuristic.
+    Annealing { iterations: u64, seed: u64 },
+}
+
+/// Weight of Pauli strings produced by the Jordan-Wigner mapping.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Weight {
+    /// Sum of weights over all terms
+    pub total: u64,
+    /// Maximal weight of a term
+    pub max:   u32,
+}
+
+/// Result of the optimization.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reordering {
+    /// Indices of orbitals present in the sum, in increasing order.
+    pub orbitals:    Vec<u32>,
+    /// New index of the orbital with index `orbitals[i]` is
+    /// `permutation[i]`.  This is a permutation of `0..orbitals.len()`.
+    pub permutation: Vec<u32>,
+    /// Weight before reordering
+    pub initial:     Weight,
+    /// Weight after reordering
+    pub optimized:   Weight,
+}
+
+impl Reordering {
+    /// Relabel orbitals in `repr` according to the permutation.
+    ///
+    /// Orbitals not listed in [`Reordering::orbitals`] are left intact.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the permutation maps two orbitals of a term onto the
+    /// same orbital.
+    pub fn apply<T>(
+        &self,
+        repr: &SumRepr<T, Fermions>,
+    ) -> Result<SumRepr<T, Fermions>, Error>
+    where
+        T: Float,
+    {
+        repr.relabel(|orb| {
+            self.orbitals
+                .binary_search(&orb.index())
+                .map_or(orb, |i| Orbital::with_index(self.permutation[i]))
+        })
+    }
+}
+
+/// Maximal weight of a Pauli string in the Jordan-Wigner image of a term.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{code::fermions::Fermions, map::reorder::jordan_wigner_weight};
+/// let code = Fermions::try_from((1, 4)).unwrap();
+/// assert_eq!(jordan_wigner_weight(&code), 4);
+///
+/// let code = Fermions::try_from((0, 5, 5, 0)).unwrap();
+/// assert_eq!(jordan_wigner_weight(&code), 2);
+/// ```
+#[must_use]
+pub fn jordan_wigner_weight(code: &Fermions) -> u32 {
+    match code {
+        Fermions::Offset => 0,
+        Fermions::One {
+            cr,
+            an,
+        } => indices_weight(&[cr.index(), an.index()]),
+        Fermions::Two {
+            cr,
+            an,
+        } => indices_weight(&[
+            cr.0.index(),
+            cr.1.index(),
+            an.0.index(),
+            an.1.index(),
+        ]),
+    }
+}
+
+// Each index appears at most twice.  Indices that appear once are paired
+// consecutively and the Jordan-Wigner strings cover the whole range between
+// them.  Indices that appear twice contribute a number operator.
+fn indices_weight(indices: &[u32]) -> u32 {
+    let mut sorted = [0; 4];
+    let sorted = &mut sorted[..indices.len()];
+    sorted.copy_from_slice(indices);
+    sorted.sort_unstable();
+
+    let mut singles = [0; 4];
+    let mut num_singles = 0;
+    let mut doubles = [0; 2];
+    let mut num_doubles = 0;
+    let mut i = 0;
+    while i < sorted.len() {
+        if i + 1 < sorted.len() && sorted[i] == sorted[i + 1] {
+            doubles[num_doubles] = sorted[i];
+            num_doubles += 1;
+            i += 2;
+        } else {
+            singles[num_singles] = sorted[i];
+            num_singles += 1;
+            i += 1;
+        }
+    }
+
+    let ranges = singles[..num_singles].chunks_exact(2);
+    let weight: u32 = ranges.clone().map(|r| r[1] - r[0] + 1).sum();
+    let outside = doubles[..num_doubles]
+        .iter()
+        .filter(|&&d| !ranges.clone().any(|r| r[0] < d && d < r[1]))
+        .count();
+
+    weight + u32::try_from(outside).expect("at most two doubled indices")
+}
+
+/// Weight of the Jordan-Wigner image of a sum.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::fermions::Fermions,
+/// #     map::reorder::{jordan_wigner_sum_weight, Weight},
+/// #     terms::SumRepr,
+/// # };
+/// let repr = SumRepr::from([
+///     (1.0, Fermions::try_from((0, 0)).unwrap()),
+///     (1.0, Fermions::try_from((0, 3)).unwrap()),
+/// ]);
+///
+/// assert_eq!(
+///     jordan_wigner_sum_weight(&repr),
+///     Weight {
+///         total: 5, max: 4
+///     }
+/// );
+/// ```
+#[must_use]
+pub fn jordan_wigner_sum_weight<T>(repr: &SumRepr<T, Fermions>) -> Weight {
+    repr.iter()
+        .map(|(_, code)| jordan_wigner_weight(code))
+        .fold(Weight::default(), |acc, w| Weight {
+            total: acc.total + u64::from(w),
+            max:   acc.max.max(w),
+        })
+}
+
+/// Find permutation of orbitals that reduces the Jordan-Wigner weight.
+///
+/// Orbitals present in `repr` are relabeled with indices `0..n`, where `n`
+/// is their number, so that indices not used by any term do not take up
+/// space on the qubit register.  The result is never worse than the input.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::fermions::Fermions,
+/// #     map::reorder::{reorder, Heuristic, Objective},
+/// #     terms::SumRepr,
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// let repr = SumRepr::from([
+///     (1.0, Fermions::try_from((0, 4))?),
+///     (1.0, Fermions::try_from((1, 5))?),
+/// ]);
+///
+/// let reordering = reorder(&repr, Objective::Total, Heuristic::Greedy);
+/// assert_eq!(reordering.initial.total, 10);
+/// assert_eq!(reordering.optimized.total, 4);
+///
+/// let repr = reordering.apply(&repr)?;
+/// assert_eq!(repr.len(), 2);
+/// #   Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn reorder<T>(
+    repr: &SumRepr<T, Fermions>,
+    objective: Objective,
+    heuristic: Heuristic,
+) -> Reordering {
+    let initial = jordan_wigner_sum_weight(repr);
+
+    // Both heuristics keep the current permutation unless they improve on
+    // it, and the initial one preserves the order of orbitals.
+    let mut state = State::new(repr);
+    state.greedy(objective);
+    if let Heuristic::Annealing {
+        iterations,
+        seed,
+    } = heuristic
+    {
+        state.anneal(objective, iterations, seed);
+    }
+
+    Reordering {
+        optimized: state.weight(),
+        orbitals: state.orbitals,
+        permutation: state.permutation,
+        initial,
+    }
+}
+
+impl Objective {
+    // Break ties in the maximal weight with the total weight.
+    #[allow(clippy::cast_precision_loss)]
+    fn cost(
+        self,
+        weight: Weight,
+    ) -> f64 {
+        match self {
+            Self::Total => weight.total as f64,
+            Self::Max => {
+                f64::from(weight.max)
+                    + weight.total as f64 / (weight.total as f64 + 1.0)
+            }
+        }
+    }
+}
+
+/// Terms reduced to positions of orbitals in the list of orbitals present in
+/// the sum, with the current permutation and the histogram of weights.
+struct State {
+    orbitals:    Vec<u32>,
+    terms:       Vec<Vec<u32>>,
+    // Terms that contain the given orbital
+    incidence:   Vec<Vec<usize>>,
+    permutation: Vec<u32>,
+    histogram:   [u64; 65],
+}
+
+impl State {
+    fn new<T>(repr: &SumRepr<T, Fermions>) -> Self {
+        let terms: Vec<Vec<u32>> = repr
+            .iter()
+            .filter_map(|(_, code)| match code {
+                Fermions::Offset => None,
+                Fermions::One {
+                    cr,
+                    an,
+                } => Some(vec![cr.index(), an.index()]),
+                Fermions::Two {
+                    cr,
+                    an,
+                } => Some(vec![
+                    cr.0.index(),
+                    cr.1.index(),
+                    an.0.index(),
+                    an.1.index(),
+                ]),
+            })
+            .collect();
+
+        let mut orbitals: Vec<_> = terms.iter().flatten().copied().collect();
+        orbitals.sort_unstable();
+        orbitals.dedup();
+        let position = |index| {
+            let i = orbitals.binary_search(&index).expect("orbital is listed");
+            u32::try_from(i).expect("position fits into u32")
+        };
+        let terms: Vec<Vec<u32>> = terms
+            .into_iter()
+            .map(|term| term.into_iter().map(position).collect())
+            .collect();
+        let num_orbitals = orbitals.len();
+
+        let mut incidence = vec![Vec::new(); num_orbitals];
+        for (k, term) in terms.iter().enumerate() {
+            for &i in term {
+                let list = &mut incidence[i as usize];
+                if list.last() != Some(&k) {
+                    list.push(k);
+                }
+            }
+        }
+
+        let permutation = (0..num_orbitals)
+            .map(|i| u32::try_from(i).expect("index fits into u32"))
+            .collect();
+
+        let mut state = Self {
+            orbitals,
+            terms,
+            incidence,
+            permutation,
+            histogram: [0; 65],
+        };
+        state.rebuild_histogram();
+        state
+    }
+
+    fn term_weight(
+        &self,
+        k: usize,
+    ) -> usize {
+        let mut indices = [0; 4];
+        let term = &self.terms[k];
+        for (j, &i) in term.iter().enumerate() {
+            indices[j] = self.permutation[i as usize];
+        }
+        (indices_weight(&indices[..term.len()]) as usize).min(64)
+    }
+
+    fn rebuild_histogram(&mut self) {
+        self.histogram = [0; 65];
+        for k in 0..self.terms.len() {
+            let w = self.term_weight(k);
+            self.histogram[w] += 1;
+        }
+    }
+
+    fn weight(&self) -> Weight {
+        Weight {
+            total: self
+                .histogram
+                .iter()
+                .zip(0..)
+                .map(|(count, w)| count * w)
+                .sum(),
+            max:   self
+                .histogram
+                .iter()
+                .rposition(|&count| count > 0)
+                .map_or(0, |w| u32::try_from(w).expect("weight within 0..=64")),
+        }
+    }
+
+    // Affected terms of a transposition of orbitals i and j
+    fn affected(
+        &self,
+        i: usize,
+        j: usize,
+    ) -> Vec<usize> {
+        let mut affected: Vec<_> = self.incidence[i]
+            .iter()
+            .chain(&self.incidence[j])
+            .copied()
+            .collect();
+        affected.sort_unstable();
+        affected.dedup();
+        affected
+    }
+
+    fn swap(
+        &mut self,
+        i: usize,
+        j: usize,
+        affected: &[usize],
+    ) {
+        for &k in affected {
+            let w = self.term_weight(k);
+            self.histogram[w] -= 1;
+        }
+        self.permutation.swap(i, j);
+        for &k in affected {
+            let w = self.term_weight(k);
+            self.histogram[w] += 1;
+        }
+    }
+
+    // Build a linear arrangement of orbitals: start with a peripheral orbital,
+    // i.e. with the smallest number of connections, and keep appending the
+    // orbital most strongly connected to the recently placed ones.
+    fn greedy(
+        &mut self,
+        objective: Objective,
+    ) {
+        let n = self.permutation.len();
+        if n == 0 {
+            return;
+        }
+
+        // Lists of neighbours with the number of shared terms.
+        let mut adjacency = vec![Vec::new(); n];
+        for term in &self.terms {
+            for &a in term {
+                for &b in term {
+                    if a != b {
+                        adjacency[a as usize].push(b as usize);
+                    }
+                }
+            }
+        }
+        let adjacency: Vec<Vec<(usize, u64)>> = adjacency
+            .into_iter()
+            .map(|mut list| {
+                list.sort_unstable();
+                let mut counts: Vec<(usize, u64)> = Vec::new();
+                for j in list {
+                    match counts.last_mut() {
+                        Some((last, count)) if *last == j => *count += 1,
+                        _ => counts.push((j, 1)),
+                    }
+                }
+                counts
+            })
+            .collect();
+
+        let degree: Vec<u64> = adjacency
+            .iter()
+            .map(|list| list.iter().map(|&(_, count)| count).sum())
+            .collect();
+        let mut placed = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        // Connection of unplaced orbitals to the placed ones, discounted
+        // with the distance from the end of the arrangement.
+        let mut score = vec![0_f64; n];
+
+        while order.len() < n {
+            let next = (0..n)
+                .filter(|&i| !placed[i])
+                .max_by(|&a, &b| {
+                    score[a]
+                        .total_cmp(&score[b])
+                        .then(degree[b].cmp(&degree[a]))
+                        .then(b.cmp(&a))
+                })
+                .expect("there is an unplaced orbital");
+
+            placed[next] = true;
+            order.push(next);
+            for s in &mut score {
+                *s *= 0.5;
+            }
+            for &(i, count) in &adjacency[next] {
+                #[allow(clippy::cast_precision_loss)]
+                let conn = count as f64;
+                score[i] += conn;
+            }
+        }
+
+        let greedy: Vec<u32> = {
+            let mut perm = vec![0; n];
+            for (new, &old) in order.iter().enumerate() {
+                perm[old] = u32::try_from(new).expect("index fits into u32");
+            }
+            perm
+        };
+
+        let current = objective.cost(self.weight());
+        let previous = std::mem::replace(&mut self.permutation, greedy);
+        self.rebuild_histogram();
+        if objective.cost(self.weight()) > current {
+            self.permutation = previous;
+            self.rebuild_histogram();
+        }
+    }
+
+    fn anneal(
+        &mut self,
+        objective: Objective,
+        iterations: u64,
+        seed: u64,
+    ) {
+        let n = self.permutation.len();
+        if n < 2 || iterations == 0 {
+            return;
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let random_pair = |rng: &mut StdRng| {
+            let i = rng.gen_range(0..n);
+            let j = (i + rng.gen_range(1..n)) % n;
+            (i, j)
+        };
+
+        // Initial temperature: mean cost change of a random transposition
+        let mut cost = objective.cost(self.weight());
+        let samples = 64;
+        let mut t0 = 0.0;
+        for _ in 0..samples {
+            let (i, j) = random_pair(&mut rng);
+            let affected = self.affected(i, j);
+            self.swap(i, j, &affected);
+            t0 += (objective.cost(self.weight()) - cost).abs();
+            self.swap(i, j, &affected);
+        }
+        t0 /= f64::from(samples);
+        if t0 == 0.0 {
+            return;
+        }
+        let t1 = t0 * 1e-3;
+        #[allow(clippy::cast_precision_loss)]
+        let decay = (t1 / t0).powf(1.0 / iterations as f64);
+
+        let mut best_cost = cost;
+        let mut best = self.permutation.clone();
+        let mut temp = t0;
+        for _ in 0..iterations {
+            let (i, j) = random_pair(&mut rng);
+            let affected = self.affected(i, j);
+            self.swap(i, j, &affected);
+            let new_cost = objective.cost(self.weight());
+            let delta = new_cost - cost;
+            if delta <= 0.0 || rng.gen::<f64>() < (-delta / temp).exp() {
+                cost = new_cost;
+                if cost < best_cost {
+                    best_cost = cost;
+                    best.clone_from(&self.permutation);
+                }
+            } else {
+                self.swap(i, j, &affected);
+            }
+            temp *= decay;
+        }
+
+        self.permutation = best;
+        self.rebuild_histogram();
+    }
+}
@@ -0,0 +1,570 @@
+//! Measurement circuits for groups of commuting Pauli strings.
+//!
+//! A group of commuting Pauli strings can be measured simultaneously by
+//! rotating the basis so that each string becomes a product of Pauli Z
+//! operators, and then measuring all qubits in the computational basis.
+
+use std::{
+    cmp::Ordering,
+    ops::Neg,
+};
+
+use num::{
+    Float,
+    Num,
+};
+
+use crate::{
+    circuit::{
+        Circuit,
+        Gate,
+        QasmVersion,
+    },
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    grouping::Compatibility,
+    terms::SumRepr,
+    Error,
+};
+
+/// Basis rotation for a group of commuting Pauli strings.
+#[derive(Debug)]
+pub struct Measurement<T> {
+    /// Circuit to apply before measuring all qubits in the computational
+    /// basis.
+    pub circuit:  Circuit,
+    /// Terms of the group after the basis rotation.
+    ///
+    /// Each code is a product of Pauli Z operators, and the expectation
+    /// value of the group is the weighted sum of parities of measured bits.
+    pub diagonal: SumRepr<T, Pauli>,
+}
+
+impl<T> Measurement<T> {
+    /// Write the measurement circuit in the OpenQASM format.
+    #[must_use]
+    pub fn to_qasm(
+        &self,
+        version: QasmVersion,
+    ) -> String {
+        self.circuit.to_qasm(version, true)
+    }
+}
+
+/// Find basis rotation that measures all terms of `group` simultaneously.
+///
+/// For [`Compatibility::QubitWise`], the circuit consists of single-qubit
+/// rotations only: `H` for Pauli X, and `S^\dagger` followed by `H` for
+/// Pauli Y.  For [`Compatibility::FullyCommuting`], the circuit is a
+/// Clifford circuit composed of `H`, `S`, `CNOT` and `CZ` gates.
+///
+/// # Errors
+///
+/// Returns error if the terms of `group` are not compatible.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     circuit::Gate,
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     grouping::Compatibility,
+/// #     measure::basis_rotation,
+/// #     terms::SumRepr,
+/// # };
+/// use PauliOp::{
+///     I,
+///     X,
+///     Y,
+///     Z,
+/// };
+///
+/// # fn main() -> Result<(), f2q::Error> {
+/// let group = SumRepr::from([
+///     (1.0, Pauli::with_ops([X, I, Z])),
+///     (2.0, Pauli::with_ops([X, Y])),
+/// ]);
+///
+/// let measurement = basis_rotation(&group, Compatibility::QubitWise)?;
+/// assert_eq!(
+///     measurement.circuit.gates(),
+///     &[Gate::H(0), Gate::Sdg(1), Gate::H(1)]
+/// );
+/// assert_eq!(
+///     measurement.diagonal.coeff(Pauli::with_ops([Z, Z])),
+///     Some(&2.0)
+/// );
+/// #   Ok(())
+/// # }
+/// ```
+pub fn basis_rotation<T>(
+    group: &SumRepr<T, Pauli>,
+    compatibility: Compatibility,
+) -> Result<Measurement<T>, Error>
+where
+    T: Num + Neg<Output = T> + Copy,
+{
+    let codes: Vec<_> = group.iter().map(|(_, &code)| code).collect();
+    for (i, a) in codes.iter().enumerate() {
+        if codes[i + 1..].iter().any(|b| !compatibility.check(a, b)) {
+            return Err(Error::NonCommuting {
+                msg: format!("{a} is not compatible with other terms"),
+            });
+        }
+    }
+
+    let num_qubits = codes
+        .iter()
+        .map(|code| u16::from(code.min_register_size()))
+        .max()
+        .unwrap_or(0);
+    let circuit = match compatibility {
+        Compatibility::QubitWise => qubitwise_circuit(&codes, num_qubits),
+        Compatibility::FullyCommuting => clifford_circuit(&codes, num_qubits),
+    };
+
+    let diagonal = circuit.conjugate_sum(group);
+    debug_assert!(
+        diagonal.iter().all(|(_, code)| code.xz_masks().0 == 0),
+        "rotated codes are diagonal"
+    );
+
+    Ok(Measurement {
+        circuit,
+        diagonal,
+    })
+}
+
+fn qubitwise_circuit(
+    codes: &[Pauli],
+    num_qubits: u16,
+) -> Circuit {
+    let mut circuit = Circuit::new(num_qubits);
+    for i in 0..num_qubits {
+        let op = codes
+            .iter()
+            .filter_map(|code| code.pauli(i))
+            .find(|&op| op != PauliOp::I);
+        let gates: &[Gate] = match op {
+            Some(PauliOp::X) => &[Gate::H(i)],
+            Some(PauliOp::Y) => &[Gate::Sdg(i), Gate::H(i)],
+            _ => &[],
+        };
+        for &gate in gates {
+            circuit.push(gate).expect("single-qubit Clifford gate");
+        }
+    }
+
+    circuit
+}
+
+// Stabilizer tableau tracking the symplectic part of the generators
+// of the group, while the diagonalizing circuit is being built.
+struct Tableau {
+    rows:    Vec<(u64, u64)>,
+    circuit: Circuit,
+}
+
+impl Tableau {
+    fn new(
+        codes: &[Pauli],
+        num_qubits: u16,
+    ) -> Self {
+        // Find an independent set of generators by Gaussian elimination.
+        let mut basis: Vec<u128> = Vec::new();
+        for &code in codes {
+            let (x, z) = code.xz_masks();
+            let mut row = u128::from(x) << 64 | u128::from(z);
+            for &b in &basis {
+                row = row.min(row ^ b);
+            }
+            if row != 0 {
+                basis.push(row);
+                basis.sort_unstable_by(|a, b| b.cmp(a));
+            }
+        }
+
+        Self {
+            rows:    basis
+                .into_iter()
+                .map(|row| ((row >> 64) as u64, row as u64))
+                .collect(),
+            circuit: Circuit::new(num_qubits),
+        }
+    }
+
+    fn apply(
+        &mut self,
+        gate: Gate,
+    ) {
+        let bit = |mask: u64, q: u16| mask >> q & 1;
+        for (x, z) in &mut self.rows {
+            match gate {
+                Gate::H(q) => {
+                    let flip = (bit(*x, q) ^ bit(*z, q)) << q;
+                    *x ^= flip;
+                    *z ^= flip;
+                }
+                Gate::S(q) | Gate::Sdg(q) => *z ^= bit(*x, q) << q,
+                Gate::Cnot(c, t) => {
+                    *x ^= bit(*x, c) << t;
+                    *z ^= bit(*z, t) << c;
+                }
+                Gate::Cz(a, b) => {
+                    *z ^= bit(*x, a) << b;
+                    *z ^= bit(*x, b) << a;
+                }
+                Gate::Swap(a, b) => {
+                    for m in [&mut *x, &mut *z] {
+                        let flip = bit(*m, a) ^ bit(*m, b);
+                        *m ^= flip << a | flip << b;
+                    }
+                }
+                Gate::Rz(..) => unreachable!("non-Clifford gate"),
+            }
+        }
+        self.circuit
+            .push(gate)
+            .expect("Clifford gate acting on distinct qubits");
+    }
+
+    // Bring the X part of the tableau into the reduced row echelon form.
+    // Return pivot columns.  Rows with no X part are moved to the end.
+    fn reduce_x(&mut self) -> Vec<u16> {
+        let mut pivots = Vec::new();
+        for col in 0..64 {
+            let rank = pivots.len();
+            let Some(r) = (rank..self.rows.len())
+                .find(|&r| self.rows[r].0 >> col & 1 == 1)
+            else {
+                continue;
+            };
+            self.rows.swap(rank, r);
+            let (px, pz) = self.rows[rank];
+            for (i, (x, z)) in self.rows.iter_mut().enumerate() {
+                if i != rank && *x >> col & 1 == 1 {
+                    *x ^= px;
+                    *z ^= pz;
+                }
+            }
+            pivots.push(col);
+        }
+
+        pivots
+    }
+}
+
+fn clifford_circuit(
+    codes: &[Pauli],
+    num_qubits: u16,
+) -> Circuit {
+    let mut tab = Tableau::new(codes, num_qubits);
+
+    // Make the X part full rank by swapping X and Z on free qubits.
+    let pivots = loop {
+        let pivots = tab.reduce_x();
+        let Some(&(_, z)) = tab.rows.get(pivots.len()) else {
+            break pivots;
+        };
+        let pivot_mask = pivots.iter().fold(0, |m, &c| m | 1 << c);
+        // Commutation with pivot rows guarantees z has a free site.
+        let q = (z & !pivot_mask).trailing_zeros() as u16;
+        tab.apply(Gate::H(q));
+    };
+    let pivot_mask: u64 = pivots.iter().fold(0, |m, &c| m | 1 << c);
+
+    // Clear X part outside of pivot columns.
+    for (i, &c) in pivots.iter().enumerate() {
+        let x = tab.rows[i].0 & !(1 << c);
+        for j in (0..64).filter(|j| x >> j & 1 == 1) {
+            tab.apply(Gate::Cnot(c, j));
+        }
+    }
+
+    // Clear Z part.  Commutation makes it symmetric on pivot columns.
+    for (i, &c) in pivots.iter().enumerate() {
+        let z = tab.rows[i].1 & !pivot_mask;
+        for j in (0..64).filter(|j| z >> j & 1 == 1) {
+            tab.apply(Gate::Cz(c, j));
+        }
+        for &d in &pivots[i + 1..] {
+            if tab.rows[i].1 >> d & 1 == 1 {
+                tab.apply(Gate::Cz(c, d));
+            }
+        }
+        if tab.rows[i].1 >> c & 1 == 1 {
+            tab.apply(Gate::S(c));
+        }
+    }
+
+    // Rotate X to Z.
+    for &c in &pivots {
+        tab.apply(Gate::H(c));
+    }
+
+    tab.circuit
+}
+
+/// Upper bound on the variance of a single-shot estimate of a group.
+///
+/// The identity term is measured with no variance and is ignored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VarianceBound {
+    /// Worst-case bound: `(sum |c_i|)^2`.
+    #[default]
+    WorstCase,
+    /// Bound for uncorrelated terms: `sum |c_i|^2`.
+    Uncorrelated,
+}
+
+impl VarianceBound {
+    /// Standard deviation bound of a single-shot estimate of `group`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     measure::VarianceBound,
+    /// #     terms::SumRepr,
+    /// # };
+    /// use PauliOp::Z;
+    ///
+    /// let group = SumRepr::from([
+    ///     (3.0, Pauli::with_ops([Z])),
+    ///     (-4.0, Pauli::with_ops([Z, Z])),
+    ///     (10.0, Pauli::identity()),
+    /// ]);
+    ///
+    /// assert_eq!(VarianceBound::WorstCase.std_dev(&group), 7.0);
+    /// assert_eq!(VarianceBound::Uncorrelated.std_dev(&group), 5.0);
+    /// ```
+    #[must_use]
+    pub fn std_dev<T>(
+        self,
+        group: &SumRepr<T, Pauli>,
+    ) -> T
+    where
+        T: Float,
+    {
+        let coeffs = group
+            .iter()
+            .filter(|(_, &code)| code != Pauli::identity())
+            .map(|(coeff, _)| coeff.abs());
+        match self {
+            Self::WorstCase => coeffs.fold(T::zero(), |acc, c| acc + c),
+            Self::Uncorrelated => {
+                coeffs.fold(T::zero(), |acc, c| acc + c * c).sqrt()
+            }
+        }
+    }
+}
+
+fn finite_std_devs<T>(
+    groups: &[SumRepr<T, Pauli>],
+    bound: VarianceBound,
+) -> Result<Vec<T>, Error>
+where
+    T: Float,
+{
+    groups
+        .iter()
+        .enumerate()
+        .map(|(i, group)| {
+            let std_dev = bound.std_dev(group);
+            if std_dev.is_finite() {
+                Ok(std_dev)
+            } else {
+                Err(Error::Coefficient {
+                    msg: format!(
+                        "standard deviation bound of group {i} is not finite"
+                    ),
+                })
+            }
+        })
+        .collect()
+}
+
+fn to_shots<T>(x: T) -> Result<u64, Error>
+where
+    T: Float,
+{
+    x.to_u64().ok_or_else(|| Error::Coefficient {
+        msg: "number of shots does not fit into u64".to_string(),
+    })
+}
+
+/// Distribution of measurement shots across groups of commuting terms.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShotAllocation<T> {
+    /// Number of shots for each group.
+    pub shots:    Vec<u64>,
+    /// Standard deviation bound of a single-shot estimate for each group.
+    pub std_devs: Vec<T>,
+}
+
+impl<T> ShotAllocation<T>
+where
+    T: Float,
+{
+    /// Allocate `total` shots to minimize the variance of the estimate of the
+    /// sum of all groups.
+    ///
+    /// The number of shots for each group is proportional to its standard
+    /// deviation bound.  Shots are rounded so that they sum up to `total`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a standard deviation bound is not finite, e.g. if a
+    /// coefficient is NaN or infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     measure::{ShotAllocation, VarianceBound},
+    /// #     terms::SumRepr,
+    /// # };
+    /// use PauliOp::{
+    ///     X,
+    ///     Z,
+    /// };
+    ///
+    /// let groups = [
+    ///     SumRepr::from([(3.0, Pauli::with_ops([Z]))]),
+    ///     SumRepr::from([(1.0, Pauli::with_ops([X]))]),
+    /// ];
+    ///
+    /// let alloc =
+    ///     ShotAllocation::with_total(&groups, VarianceBound::WorstCase, 1000)
+    ///         .unwrap();
+    /// assert_eq!(alloc.shots, [750, 250]);
+    /// ```
+    pub fn with_total(
+        groups: &[SumRepr<T, Pauli>],
+        bound: VarianceBound,
+        total: u64,
+    ) -> Result<Self, Error> {
+        let std_devs = finite_std_devs(groups, bound)?;
+        let norm = std_devs.iter().fold(T::zero(), |acc, &s| acc + s);
+        if norm == T::zero() {
+            return Ok(Self {
+                shots: vec![0; groups.len()],
+                std_devs,
+            });
+        }
+
+        let total_t = T::from(total).ok_or_else(|| Error::Coefficient {
+            msg: format!("number of shots not representable: {total}"),
+        })?;
+        let exact: Vec<_> =
+            std_devs.iter().map(|&s| total_t * s / norm).collect();
+        let mut shots = exact
+            .iter()
+            .map(|x| to_shots(x.floor()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Distribute the remaining shots by the largest remainder.  All
+        // remainders are finite, since the standard deviations are.
+        let mut order: Vec<_> = (0..groups.len()).collect();
+        order.sort_by(|&a, &b| {
+            let rem = |i: usize| exact[i] - exact[i].floor();
+            rem(b).partial_cmp(&rem(a)).unwrap_or(Ordering::Equal)
+        });
+        let assigned: u64 = shots.iter().sum();
+        for &i in order
+            .iter()
+            .filter(|&&i| std_devs[i] > T::zero())
+            .cycle()
+            .take(usize::try_from(total.saturating_sub(assigned)).unwrap_or(0))
+        {
+            shots[i] += 1;
+        }
+
+        Ok(Self {
+            shots,
+            std_devs,
+        })
+    }
+
+    /// Allocate the smallest number of shots such that the standard deviation
+    /// of the estimate of the sum of all groups is at most `precision`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `precision` is not positive, if a standard deviation
+    /// bound is not finite, or if the number of shots does not fit into
+    /// `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     measure::{ShotAllocation, VarianceBound},
+    /// #     terms::SumRepr,
+    /// # };
+    /// use PauliOp::{
+    ///     X,
+    ///     Z,
+    /// };
+    ///
+    /// let groups = [
+    ///     SumRepr::from([(3.0, Pauli::with_ops([Z]))]),
+    ///     SumRepr::from([(1.0, Pauli::with_ops([X]))]),
+    /// ];
+    ///
+    /// let alloc =
+    ///     ShotAllocation::with_precision(&groups, VarianceBound::WorstCase, 0.1)
+    ///         .unwrap();
+    /// assert_eq!(alloc.shots, [1200, 400]);
+    /// assert!(alloc.std_dev() <= 0.1);
+    /// ```
+    pub fn with_precision(
+        groups: &[SumRepr<T, Pauli>],
+        bound: VarianceBound,
+        precision: T,
+    ) -> Result<Self, Error> {
+        if precision.is_nan() || precision <= T::zero() {
+            return Err(Error::Coefficient {
+                msg: "precision must be positive".to_string(),
+            });
+        }
+
+        let std_devs = finite_std_devs(groups, bound)?;
+        let norm = std_devs.iter().fold(T::zero(), |acc, &s| acc + s);
+        let shots = std_devs
+            .iter()
+            .map(|&s| to_shots((norm * s / (precision * precision)).ceil()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            shots,
+            std_devs,
+        })
+    }
+
+    /// Total number of shots.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.shots.iter().sum()
+    }
+
+    /// Standard deviation bound of the estimate of the sum of all groups.
+    ///
+    /// Returns infinity, if a group with nonzero variance receives no shots.
+    #[must_use]
+    pub fn std_dev(&self) -> T {
+        self.std_devs
+            .iter()
+            .zip(&self.shots)
+            .filter(|(&s, _)| s > T::zero())
+            .fold(T::zero(), |acc, (&s, &n)| {
+                acc + s * s / T::from(n).expect("conversion from u64")
+            })
+            .sqrt()
+    }
+}
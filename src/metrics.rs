@@ -0,0 +1,120 @@
+//! Norms and resource metrics of Pauli Hamiltonians.
+
+use num::Float;
+use serde::Serialize;
+
+use crate::{
+    code::qubits::Pauli,
+    terms::SumRepr,
+};
+
+/// Summary of a sum of Pauli strings.
+///
+/// Norms and the maximal coefficient do not include the identity term,
+/// whose coefficient is reported separately.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Metrics<T> {
+    /// Number of terms, including the identity
+    pub num_terms:     usize,
+    /// Size of the smallest register that fits all terms
+    pub num_qubits:    u8,
+    /// Largest number of non-trivial Pauli operators in a term
+    pub locality:      u8,
+    /// Coefficient of the identity term
+    pub identity:      T,
+    /// Sum of absolute values of coefficients (λ for LCU and qubitization)
+    pub l1_norm:       T,
+    /// Square root of the sum of squares of coefficients
+    pub l2_norm:       T,
+    /// Largest absolute value of a coefficient
+    pub max_coeff:     T,
+    /// Number of terms of each Pauli weight: `0..=locality`
+    pub weight_counts: Vec<usize>,
+    /// Sum of absolute values of coefficients of terms of each Pauli weight
+    pub weight_l1:     Vec<T>,
+}
+
+impl<T> Metrics<T>
+where
+    T: Float,
+{
+    /// Compute metrics of `repr`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     metrics::Metrics,
+    /// #     terms::SumRepr,
+    /// # };
+    /// use PauliOp::{
+    ///     I,
+    ///     X,
+    ///     Z,
+    /// };
+    ///
+    /// let repr = SumRepr::from([
+    ///     (2.0, Pauli::identity()),
+    ///     (-3.0, Pauli::with_ops([I, Z])),
+    ///     (4.0, Pauli::with_ops([X, X, I, Z])),
+    /// ]);
+    ///
+    /// let metrics = Metrics::new(&repr);
+    ///
+    /// assert_eq!(metrics.num_terms, 3);
+    /// assert_eq!(metrics.num_qubits, 4);
+    /// assert_eq!(metrics.locality, 3);
+    /// assert_eq!(metrics.identity, 2.0);
+    /// assert_eq!(metrics.l1_norm, 7.0);
+    /// assert_eq!(metrics.l2_norm, 5.0);
+    /// assert_eq!(metrics.max_coeff, 4.0);
+    /// assert_eq!(metrics.weight_counts, [1, 1, 0, 1]);
+    /// assert_eq!(metrics.weight_l1, [0.0, 3.0, 0.0, 4.0]);
+    /// ```
+    #[must_use]
+    pub fn new(repr: &SumRepr<T, Pauli>) -> Self {
+        let num_qubits = repr
+            .iter()
+            .map(|(_, code)| code.min_register_size())
+            .max()
+            .unwrap_or(0);
+        let locality = repr
+            .iter()
+            .map(|(_, code)| code.num_nontrivial())
+            .max()
+            .unwrap_or(0);
+
+        let mut identity = T::zero();
+        let mut l1_norm = T::zero();
+        let mut l2_norm = T::zero();
+        let mut max_coeff = T::zero();
+        let mut weight_counts = vec![0; usize::from(locality) + 1];
+        let mut weight_l1 = vec![T::zero(); usize::from(locality) + 1];
+        for (&coeff, code) in repr.iter() {
+            let weight = usize::from(code.num_nontrivial());
+            weight_counts[weight] += 1;
+            if weight == 0 {
+                identity = coeff;
+                continue;
+            }
+            let abs = coeff.abs();
+            weight_l1[weight] = weight_l1[weight] + abs;
+            l1_norm = l1_norm + abs;
+            l2_norm = l2_norm + abs * abs;
+            max_coeff = max_coeff.max(abs);
+        }
+
+        Self {
+            num_terms: repr.len(),
+            num_qubits,
+            locality,
+            identity,
+            l1_norm,
+            l2_norm: l2_norm.sqrt(),
+            max_coeff,
+            weight_counts,
+            weight_l1,
+        }
+    }
+}
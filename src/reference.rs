@@ -0,0 +1,118 @@
+//! Computational basis states as reference states.
+//!
+//! A basis state of up to 64 qubits is a bitstring `b: u64`, whose bit `i` is
+//! the state of the qubit `i`.  After the Jordan-Wigner mapping, bit `i` is
+//! the occupation number of the spin-orbital with index `i`.
+
+use num::Float;
+
+use crate::{
+    code::{
+        fermions::{
+            Orbital,
+            OrbitalOrdering,
+            Spin,
+        },
+        qubits::Pauli,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+/// Expectation value `<b|H|b>` of the sum of Pauli strings on the basis
+/// state `|b>`.
+///
+/// Only diagonal terms, i.e. products of `I` and `Z`, contribute.  No state
+/// vector is formed, so this works for all 64 qubits.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     reference::basis_expectation,
+/// #     terms::SumRepr,
+/// # };
+/// use PauliOp::{
+///     I,
+///     X,
+///     Z,
+/// };
+///
+/// let repr = SumRepr::from([
+///     (0.5, Pauli::identity()),
+///     (1.0, Pauli::with_ops([Z, Z])),
+///     (2.0, Pauli::with_ops([I, Z])),
+///     (3.0, Pauli::with_ops([X, X])),
+/// ]);
+///
+/// // |01>: qubit 0 in state 1, qubit 1 in state 0
+/// assert_eq!(basis_expectation(&repr, 0b01), 0.5 - 1.0 + 2.0);
+/// ```
+pub fn basis_expectation<T>(
+    repr: &SumRepr<T, Pauli>,
+    bits: u64,
+) -> T
+where
+    T: Float,
+{
+    repr.iter()
+        .filter_map(|(&coeff, code)| {
+            let (x, z) = code.xz_masks();
+            (x == 0).then(|| {
+                if (bits & z).count_ones() % 2 == 0 {
+                    coeff
+                } else {
+                    -coeff
+                }
+            })
+        })
+        .fold(T::zero(), |acc, value| acc + value)
+}
+
+/// Occupation bitstring of the Hartree-Fock state with `num_electrons`
+/// electrons.
+///
+/// Electrons fill spatial orbitals in the increasing order, first with
+/// `Spin::Down` and then with `Spin::Up`.  Bit `i` of the result is set,
+/// if the spin-orbital with index `i` in `ordering` is occupied.
+///
+/// # Errors
+///
+/// Returns error if an occupied spin-orbital cannot be enumerated by
+/// `ordering`, or if its index does not fit into 64 qubits.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::fermions::OrbitalOrdering,
+/// #     reference::hartree_fock,
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// assert_eq!(hartree_fock(3, OrbitalOrdering::Interleaved)?, 0b0111);
+///
+/// let ordering = OrbitalOrdering::Block {
+///     num_orbitals: 4
+/// };
+/// assert_eq!(hartree_fock(3, ordering)?, 0b0001_0011);
+/// #   Ok(())
+/// # }
+/// ```
+pub fn hartree_fock(
+    num_electrons: u32,
+    ordering: OrbitalOrdering,
+) -> Result<u64, Error> {
+    (0..num_electrons).try_fold(0, |bits, k| {
+        let orbital = Orbital::new(k / 2, Spin::from(k % 2 == 1));
+        ordering
+            .checked_index(&orbital)
+            .filter(|&index| index < 64)
+            .map(|index| bits | 1 << index)
+            .ok_or_else(|| Error::QubitIndex {
+                msg: format!(
+                    "{num_electrons} electrons do not fit into the register"
+                ),
+            })
+    })
+}
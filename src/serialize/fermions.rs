@@ -1,10 +1,14 @@
 use std::marker::PhantomData;
 
-use num::Num;
+use num::{
+    Float,
+    Num,
+};
 use serde::{
     de::Visitor,
     ser::SerializeSeq,
     Deserialize,
+    Deserializer,
     Serialize,
 };
 
@@ -14,6 +18,7 @@ use crate::{
         Cr,
         Fermions,
         Orbital,
+        OrbitalOrdering,
     },
     serialize::Encoding,
     terms::SumRepr,
@@ -266,3 +271,73 @@ where
         Ok(sumde.terms.0)
     }
 }
+
+impl<T> SumRepr<T, Fermions>
+where
+    T: Float,
+{
+    /// Deserialize the sum of fermionic terms with orbital indices given in
+    /// `ordering`.
+    ///
+    /// Terms are brought to the canonical order of [`Orbital`], as with
+    /// [`SumRepr::relabel()`].  The default [`Deserialize`] implementation
+    /// is the same as the interleaved ordering.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if deserialization fails, or if an index does not fit
+    /// into the ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::fermions::{Fermions, OrbitalOrdering},
+    /// #     terms::SumRepr,
+    /// # };
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// let json = r#"{
+    ///     "type": "sumrepr",
+    ///     "encoding": "fermions",
+    ///     "terms": [{"code": [1, 4], "value": 1.0}]
+    /// }"#;
+    /// let ordering = OrbitalOrdering::Block {
+    ///     num_orbitals: 3
+    /// };
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(json);
+    /// let repr: SumRepr<f64, _> =
+    ///     SumRepr::deserialize_with_ordering(&mut de, ordering).unwrap();
+    ///
+    /// assert_eq!(repr.coeff(Fermions::try_from((2, 3))?), Some(&1.0));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn deserialize_with_ordering<'de, D>(
+        deserializer: D,
+        ordering: OrbitalOrdering,
+    ) -> Result<Self, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let repr = Self::deserialize(deserializer)?;
+        if ordering == OrbitalOrdering::Interleaved {
+            return Ok(repr);
+        }
+        if let Some(index) = repr
+            .iter()
+            .filter_map(|(_, code)| code.max_index())
+            .find(|&index| ordering.checked_orbital(index).is_none())
+        {
+            return Err(D::Error::custom(format!(
+                "orbital index out of bound: {index}"
+            )));
+        }
+
+        repr.relabel(|orb| ordering.orbital(orb.index()))
+            .map_err(D::Error::custom)
+    }
+}
@@ -11,7 +11,9 @@ use serde::{
 use crate::{
     code::qubits::{
         Pauli,
+        PauliNotation,
         PauliOp,
+        WithNotation,
     },
     serialize::Encoding,
     terms::SumRepr,
@@ -105,7 +107,8 @@ impl<'de> Visitor<'de> for PauliVisitor {
         formatter: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         formatter.write_str(
-            "string of 64 Pauli operators (trailing identities truncated)",
+            "string of 64 Pauli operators (trailing identities truncated), or \
+             sparse notation: X0 Y3 Z17",
         )
     }
 
@@ -116,28 +119,7 @@ impl<'de> Visitor<'de> for PauliVisitor {
     where
         E: serde::de::Error,
     {
-        if v.len() > 64 || v.is_empty() {
-            return Err(E::custom("str len out of range: 1..=64".to_string()));
-        }
-
-        let mut code = Pauli::default();
-
-        for (i, ch) in v.chars().enumerate() {
-            let pauli = match ch {
-                'I' => Ok(PauliOp::I),
-                'X' => Ok(PauliOp::X),
-                'Y' => Ok(PauliOp::Y),
-                'Z' => Ok(PauliOp::Z),
-                _ => Err(E::custom(
-                    "character must be one of: I, X, Y, Z".to_string(),
-                )),
-            }?;
-            let idx = u16::try_from(i)
-                .expect("index out of range for u16. This is a bug.");
-            code.set(idx, pauli);
-        }
-
-        Ok(code)
+        v.parse().map_err(E::custom)
     }
 }
 
@@ -150,13 +132,38 @@ impl<'de> Deserialize<'de> for Pauli {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Deserialize)]
 struct PauliSumTerm<T> {
     code:  Pauli,
     value: T,
 }
 
-struct PauliSumSerSequence<'a, T>(&'a SumRepr<T, Pauli>);
+struct NotatedPauli(Pauli, PauliNotation);
+
+impl Serialize for NotatedPauli {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.1 {
+            PauliNotation::Dense => self.0.serialize(serializer),
+            PauliNotation::Sparse => {
+                serializer.serialize_str(&self.0.to_sparse())
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PauliSumTermSer<T> {
+    code:  NotatedPauli,
+    value: T,
+}
+
+struct PauliSumSerSequence<'a, T>(&'a SumRepr<T, Pauli>, PauliNotation);
 
 impl<'a, T> Serialize for PauliSumSerSequence<'a, T>
 where
@@ -171,8 +178,8 @@ where
     {
         let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
         for (&coeff, &code) in self.0.iter() {
-            seq.serialize_element(&PauliSumTerm {
-                code,
+            seq.serialize_element(&PauliSumTermSer {
+                code:  NotatedPauli(code, self.1),
                 value: coeff,
             })?;
         }
@@ -192,6 +199,22 @@ where
 }
 
 impl<T> Serialize for SumRepr<T, Pauli>
+where
+    T: Num + Copy + Serialize,
+{
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.with_notation(PauliNotation::Dense)
+            .serialize(serializer)
+    }
+}
+
+impl<'a, T> Serialize for WithNotation<'a, T>
 where
     T: Num + Copy + Serialize,
 {
@@ -205,7 +228,7 @@ where
         (PauliSumSer {
             r#type:   "sumrepr",
             encoding: Encoding::Qubits,
-            terms:    PauliSumSerSequence(self),
+            terms:    PauliSumSerSequence(self.repr(), self.notation()),
         })
         .serialize(serializer)
     }
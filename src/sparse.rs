@@ -0,0 +1,336 @@
+//! Sparse matrices of Pauli sums.
+//!
+//! The matrix acts on the Hilbert space of `n` qubits of dimension `2^n`.
+//! The basis state `|b>` corresponds to the index `b`, whose bit `i` is the
+//! state of the qubit `i`.
+//!
+//! Each Pauli string has exactly one nonzero entry in each row: for `P`
+//! with X-mask `x` and Z-mask `z` (see [`Pauli`]), the entry in row `r` is
+//! in column `c = r ^ x` and equals `i^{|x & z|} (-1)^{|c & z|}`.
+
+use std::collections::HashMap;
+
+use num::{
+    complex::Complex,
+    Float,
+};
+use rayon::prelude::*;
+
+use crate::{
+    code::qubits::Pauli,
+    terms::SumRepr,
+    Error,
+};
+
+/// Sparse matrix in the compressed sparse row (CSR) format.
+///
+/// Column indices of each row are sorted in the increasing order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrMatrix<T> {
+    dim:     usize,
+    indptr:  Vec<usize>,
+    indices: Vec<usize>,
+    values:  Vec<Complex<T>>,
+}
+
+/// Sparse matrix in the coordinate (COO) format.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CooMatrix<T> {
+    /// Number of rows and columns
+    pub dim:    usize,
+    /// Row indices of nonzero entries
+    pub rows:   Vec<usize>,
+    /// Column indices of nonzero entries
+    pub cols:   Vec<usize>,
+    /// Values of nonzero entries
+    pub values: Vec<Complex<T>>,
+}
+
+// Terms sharing the same X-mask contribute to the same entry in each row.
+pub(crate) struct Block<T> {
+    pub(crate) x:     u64,
+    // (coefficient times phase, Z-mask)
+    pub(crate) terms: Vec<(Complex<T>, u64)>,
+}
+
+impl<T> Block<T>
+where
+    T: Float,
+{
+    // Entry of the block in the column `c`.
+    pub(crate) fn value(
+        &self,
+        c: u64,
+    ) -> Complex<T> {
+        self.terms.iter().fold(
+            Complex::new(T::zero(), T::zero()),
+            |acc, &(value, z)| {
+                if (c & z).count_ones() % 2 == 0 {
+                    acc + value
+                } else {
+                    acc - value
+                }
+            },
+        )
+    }
+}
+
+pub(crate) fn blocks<T>(repr: &SumRepr<T, Pauli>) -> Vec<Block<T>>
+where
+    T: Float,
+{
+    let i_pow = |k: u32| match k % 4 {
+        0 => Complex::new(T::one(), T::zero()),
+        1 => Complex::new(T::zero(), T::one()),
+        2 => Complex::new(-T::one(), T::zero()),
+        _ => Complex::new(T::zero(), -T::one()),
+    };
+
+    let mut map: HashMap<u64, Vec<(Complex<T>, u64)>> = HashMap::new();
+    for (&coeff, code) in repr.iter() {
+        let (x, z) = code.xz_masks();
+        let value = i_pow((x & z).count_ones()) * coeff;
+        map.entry(x).or_default().push((value, z));
+    }
+
+    let mut blocks: Vec<_> = map
+        .into_iter()
+        .map(|(x, terms)| Block {
+            x,
+            terms,
+        })
+        .collect();
+    blocks.sort_by_key(|b| b.x);
+
+    blocks
+}
+
+// Amplitude of H|input> at the basis state `row`.  The basis state `c` has
+// the coordinate `index(c)` in `input`, or none outside of the subspace.
+pub(crate) fn row_value<T, I>(
+    blocks: &[Block<T>],
+    input: &[Complex<T>],
+    row: u64,
+    index: I,
+) -> Complex<T>
+where
+    T: Float,
+    I: Fn(u64) -> Option<usize>,
+{
+    blocks
+        .iter()
+        .fold(Complex::new(T::zero(), T::zero()), |acc, block| {
+            let col = row ^ block.x;
+            match index(col) {
+                Some(c) => acc + block.value(col) * input[c],
+                None => acc,
+            }
+        })
+}
+
+// output = H input, where the coordinate `r` of `output` is the amplitude at
+// the basis state `state(r)`.  Amplitudes are computed in parallel.
+pub(crate) fn apply_blocks<T, S, I>(
+    blocks: &[Block<T>],
+    input: &[Complex<T>],
+    output: &mut [Complex<T>],
+    state: S,
+    index: I,
+) where
+    T: Float + Send + Sync,
+    S: Fn(usize) -> u64 + Sync,
+    I: Fn(u64) -> Option<usize> + Sync,
+{
+    output.par_iter_mut().enumerate().for_each(|(r, out)| {
+        *out = row_value(blocks, input, state(r), &index);
+    });
+}
+
+impl<T> CsrMatrix<T>
+where
+    T: Float + Send + Sync,
+{
+    /// Build the matrix of `repr` acting on `num_qubits` qubits.
+    ///
+    /// Entries that sum up to zero are not stored.  Rows are computed in
+    /// parallel.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a term does not fit into the register, or if the
+    /// dimension `2^num_qubits` does not fit into `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     sparse::CsrMatrix,
+    /// #     terms::SumRepr,
+    /// # };
+    /// # use num::complex::Complex;
+    /// use PauliOp::{
+    ///     X,
+    ///     Z,
+    /// };
+    ///
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// let repr = SumRepr::from([
+    ///     (1.0, Pauli::with_ops([X])),
+    ///     (0.5, Pauli::with_ops([Z])),
+    /// ]);
+    ///
+    /// let matrix = CsrMatrix::from_sumrepr(&repr, 1)?;
+    ///
+    /// assert_eq!(matrix.dim(), 2);
+    /// assert_eq!(matrix.get(0, 0), Complex::new(0.5, 0.0));
+    /// assert_eq!(matrix.get(0, 1), Complex::new(1.0, 0.0));
+    /// assert_eq!(matrix.get(1, 0), Complex::new(1.0, 0.0));
+    /// assert_eq!(matrix.get(1, 1), Complex::new(-0.5, 0.0));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn from_sumrepr(
+        repr: &SumRepr<T, Pauli>,
+        num_qubits: u16,
+    ) -> Result<Self, Error> {
+        if u32::from(num_qubits) >= usize::BITS {
+            return Err(Error::QubitIndex {
+                msg: format!(
+                    "dimension of the matrix for {num_qubits} qubits is too \
+                     large"
+                ),
+            });
+        }
+        if repr
+            .iter()
+            .any(|(_, code)| u16::from(code.min_register_size()) > num_qubits)
+        {
+            return Err(Error::QubitIndex {
+                msg: format!("term does not fit into {num_qubits} qubits"),
+            });
+        }
+
+        let dim = 1_usize << num_qubits;
+        let blocks = blocks(repr);
+        let rows: Vec<Vec<(usize, Complex<T>)>> = (0..dim)
+            .into_par_iter()
+            .map(|r| {
+                let mut row: Vec<_> = blocks
+                    .iter()
+                    .filter_map(|block| {
+                        let c = r ^ block.x as usize;
+                        let value = block.value(c as u64);
+                        (value != Complex::new(T::zero(), T::zero()))
+                            .then_some((c, value))
+                    })
+                    .collect();
+                row.sort_unstable_by_key(|&(c, _)| c);
+                row
+            })
+            .collect();
+
+        let mut indptr = Vec::with_capacity(dim + 1);
+        indptr.push(0);
+        let nnz = rows.iter().map(Vec::len).sum();
+        let mut indices = Vec::with_capacity(nnz);
+        let mut values = Vec::with_capacity(nnz);
+        for row in rows {
+            for (c, value) in row {
+                indices.push(c);
+                values.push(value);
+            }
+            indptr.push(indices.len());
+        }
+
+        Ok(Self {
+            dim,
+            indptr,
+            indices,
+            values,
+        })
+    }
+}
+
+impl<T> CsrMatrix<T>
+where
+    T: Float,
+{
+    /// Number of rows and columns.
+    #[must_use]
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Number of stored entries.
+    #[must_use]
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Offsets of rows in [`CsrMatrix::indices`] and [`CsrMatrix::values`].
+    ///
+    /// The length of the slice is `dim + 1`.
+    #[must_use]
+    pub fn indptr(&self) -> &[usize] {
+        &self.indptr
+    }
+
+    /// Column indices of stored entries.
+    #[must_use]
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Values of stored entries.
+    #[must_use]
+    pub fn values(&self) -> &[Complex<T>] {
+        &self.values
+    }
+
+    /// Entry of the matrix at (`row`, `col`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds.
+    #[must_use]
+    pub fn get(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> Complex<T> {
+        let range = self.indptr[row]..self.indptr[row + 1];
+        self.indices[range.clone()]
+            .binary_search(&col)
+            .map_or(Complex::new(T::zero(), T::zero()), |i| {
+                self.values[range.start + i]
+            })
+    }
+
+    /// Convert to the coordinate format.
+    #[must_use]
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        let rows = self
+            .indptr
+            .windows(2)
+            .enumerate()
+            .flat_map(|(r, w)| std::iter::repeat(r).take(w[1] - w[0]))
+            .collect();
+
+        CooMatrix {
+            dim: self.dim,
+            rows,
+            cols: self.indices.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<T> From<CsrMatrix<T>> for CooMatrix<T>
+where
+    T: Float,
+{
+    fn from(value: CsrMatrix<T>) -> Self {
+        value.to_coo()
+    }
+}
@@ -0,0 +1,251 @@
+//! Stabilizer states.
+//!
+//! A stabilizer state of `n` qubits is the unique common eigenvector with
+//! eigenvalue 1 of `n` independent, commuting, signed Pauli strings: the
+//! stabilizers.  Together with `n` destabilizers, they form a tableau that
+//! is updated by Clifford gates in polynomial time.  Expectation values of
+//! Pauli strings are then computed without a state vector, for all 64 qubits.
+
+use num::Float;
+
+use crate::{
+    circuit::{
+        Circuit,
+        Gate,
+    },
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    math::{
+        Group,
+        Root4,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+/// Stabilizer state of a register of qubits.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     circuit::Gate,
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     stabilizer::StabilizerState,
+/// #     terms::SumRepr,
+/// # };
+/// use PauliOp::{
+///     X,
+///     Y,
+///     Z,
+/// };
+///
+/// # fn main() -> Result<(), f2q::Error> {
+/// // Bell state: (|00> + |11>) / sqrt(2)
+/// let mut state = StabilizerState::new(2);
+/// state.apply(Gate::H(0));
+/// state.apply(Gate::Cnot(0, 1));
+///
+/// let repr = SumRepr::from([
+///     (1.0, Pauli::with_ops([X, X])),
+///     (2.0, Pauli::with_ops([Y, Y])),
+///     (3.0, Pauli::with_ops([Z, Z])),
+///     (4.0, Pauli::with_ops([Z])),
+/// ]);
+///
+/// assert_eq!(state.expectation(&repr)?, 1.0 - 2.0 + 3.0);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct StabilizerState {
+    num_qubits:    u16,
+    // Stabilizers and destabilizers with signs.  The i-th stabilizer
+    // anticommutes with the i-th destabilizer and commutes with all other
+    // generators.
+    stabilizers:   Vec<(Root4, Pauli)>,
+    destabilizers: Vec<(Root4, Pauli)>,
+}
+
+impl StabilizerState {
+    /// Computational basis state `|0...0>` of `num_qubits` qubits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_qubits > 64`.
+    #[must_use]
+    pub fn new(num_qubits: u16) -> Self {
+        Self::with_bits(num_qubits, 0)
+    }
+
+    /// Computational basis state `|b>` of `num_qubits` qubits, where bit `i`
+    /// of `bits` is the state of the qubit `i`.
+    ///
+    /// Bits outside of the register are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_qubits > 64`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     stabilizer::StabilizerState,
+    /// # };
+    /// use PauliOp::{
+    ///     I,
+    ///     Z,
+    /// };
+    ///
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// let state = StabilizerState::with_bits(3, 0b010);
+    ///
+    /// assert_eq!(state.pauli_expectation(Pauli::with_ops([Z]))?, 1);
+    /// assert_eq!(state.pauli_expectation(Pauli::with_ops([I, Z]))?, -1);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_bits(
+        num_qubits: u16,
+        bits: u64,
+    ) -> Self {
+        assert!(num_qubits <= 64, "number of qubits must be at most 64");
+
+        let single = |q: u16, op: PauliOp| {
+            let mut code = Pauli::identity();
+            code.set(q, op);
+            code
+        };
+        let one = Root4::identity();
+        let stabilizers = (0..num_qubits)
+            .map(|q| {
+                let sign = if bits >> q & 1 == 1 { -one } else { one };
+                (sign, single(q, PauliOp::Z))
+            })
+            .collect();
+        let destabilizers = (0..num_qubits)
+            .map(|q| (one, single(q, PauliOp::X)))
+            .collect();
+
+        Self {
+            num_qubits,
+            stabilizers,
+            destabilizers,
+        }
+    }
+
+    /// State prepared by `circuit` from `|0...0>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the circuit contains a non-Clifford gate, or acts on more
+    /// than 64 qubits.
+    #[must_use]
+    pub fn from_circuit(circuit: &Circuit) -> Self {
+        let mut state = Self::new(circuit.num_qubits());
+        for &gate in circuit.gates() {
+            state.apply(gate);
+        }
+
+        state
+    }
+
+    /// Size of the qubit register.
+    #[must_use]
+    pub fn num_qubits(&self) -> u16 {
+        self.num_qubits
+    }
+
+    /// Apply a Clifford gate to the state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gate is not a Clifford gate, or if it acts on a qubit
+    /// outside of the register.
+    pub fn apply(
+        &mut self,
+        gate: Gate,
+    ) {
+        assert!(
+            gate.qubits().iter().all(|&q| q < self.num_qubits),
+            "gate acts outside of the register"
+        );
+        for (sign, code) in
+            self.stabilizers.iter_mut().chain(&mut self.destabilizers)
+        {
+            let (omega, image) = gate.conjugate(*code);
+            *sign = *sign * omega;
+            *code = image;
+        }
+    }
+
+    /// Expectation value of a Pauli string: 1, -1 or 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the Pauli string does not fit into the register.
+    pub fn pauli_expectation(
+        &self,
+        code: Pauli,
+    ) -> Result<i8, Error> {
+        if u16::from(code.min_register_size()) > self.num_qubits {
+            return Err(Error::QubitIndex {
+                msg: format!(
+                    "Pauli string does not fit into {} qubits",
+                    self.num_qubits
+                ),
+            });
+        }
+        if self
+            .stabilizers
+            .iter()
+            .any(|(_, s)| !s.commutes_with(&code))
+        {
+            return Ok(0);
+        }
+
+        // The Pauli string is, up to a sign, the product of stabilizers
+        // paired with the destabilizers it anticommutes with.
+        let (omega, product) = self
+            .stabilizers
+            .iter()
+            .zip(&self.destabilizers)
+            .filter(|(_, (_, d))| !d.commutes_with(&code))
+            .fold(
+                (Root4::identity(), Pauli::identity()),
+                |(omega, acc), (&(sign, s), _)| {
+                    let (w, p) = acc * s;
+                    (omega * sign * w, p)
+                },
+            );
+        debug_assert_eq!(product, code);
+
+        Ok(if omega == Root4::identity() { 1 } else { -1 })
+    }
+
+    /// Expectation value `<ψ|H|ψ>` of the sum of Pauli strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a term does not fit into the register.
+    pub fn expectation<T>(
+        &self,
+        repr: &SumRepr<T, Pauli>,
+    ) -> Result<T, Error>
+    where
+        T: Float,
+    {
+        repr.iter().try_fold(T::zero(), |acc, (&coeff, &code)| {
+            Ok(match self.pauli_expectation(code)? {
+                0 => acc,
+                1 => acc + coeff,
+                _ => acc - coeff,
+            })
+        })
+    }
+}
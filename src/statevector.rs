@@ -0,0 +1,161 @@
+//! Pauli sums acting on dense state vectors.
+//!
+//! A state of `n` qubits is a slice of `2^n` amplitudes.  The amplitude at
+//! index `b` corresponds to the basis state `|b>`, whose bit `i` is the state
+//! of the qubit `i` (see [`sparse`](crate::sparse)).
+
+use num::{
+    complex::Complex,
+    Float,
+};
+use rayon::prelude::*;
+
+use crate::{
+    code::qubits::Pauli,
+    sparse::{
+        apply_blocks,
+        blocks,
+        row_value,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+// Number of qubits of the state, if its length is a power of two and all
+// terms of `repr` fit into the register.
+fn num_qubits<T>(
+    repr: &SumRepr<T, Pauli>,
+    len: usize,
+) -> Result<u16, Error>
+where
+    T: Float,
+{
+    if !len.is_power_of_two() {
+        return Err(Error::QubitIndex {
+            msg: format!("length of state vector is not a power of two: {len}"),
+        });
+    }
+    let num_qubits =
+        u16::try_from(len.trailing_zeros()).expect("less than 64 qubits");
+    if repr
+        .iter()
+        .any(|(_, code)| u16::from(code.min_register_size()) > num_qubits)
+    {
+        return Err(Error::QubitIndex {
+            msg: format!("term does not fit into {num_qubits} qubits"),
+        });
+    }
+
+    Ok(num_qubits)
+}
+
+/// Replace `state` with `H|state>`, where `H` is the sum of Pauli strings.
+///
+/// Amplitudes are computed in parallel.  The input state is copied once.
+///
+/// # Errors
+///
+/// Returns error if the length of `state` is not a power of two, or if a
+/// term does not fit into the register.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     statevector,
+/// #     terms::SumRepr,
+/// # };
+/// # use num::complex::Complex;
+/// use PauliOp::{
+///     X,
+///     Z,
+/// };
+///
+/// # fn main() -> Result<(), f2q::Error> {
+/// let repr = SumRepr::from([
+///     (1.0, Pauli::with_ops([X])),
+///     (0.5, Pauli::with_ops([Z])),
+/// ]);
+///
+/// let mut state = [Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)];
+/// statevector::apply(&repr, &mut state)?;
+///
+/// assert_eq!(state, [Complex::new(0.5, 0.0), Complex::new(1.0, 0.0)]);
+/// #   Ok(())
+/// # }
+/// ```
+pub fn apply<T>(
+    repr: &SumRepr<T, Pauli>,
+    state: &mut [Complex<T>],
+) -> Result<(), Error>
+where
+    T: Float + Send + Sync,
+{
+    num_qubits(repr, state.len())?;
+    let blocks = blocks(repr);
+    let input = state.to_vec();
+    apply_blocks(&blocks, &input, state, |r| r as u64, |c| Some(c as usize));
+
+    Ok(())
+}
+
+/// Expectation value `<state|H|state>` of the sum of Pauli strings.
+///
+/// Since the coefficients are real, `H` is Hermitian and the expectation
+/// value is real.  The state is not normalized.  No copy of the state is
+/// made.
+///
+/// # Errors
+///
+/// Returns error if the length of `state` is not a power of two, or if a
+/// term does not fit into the register.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     statevector,
+/// #     terms::SumRepr,
+/// # };
+/// # use num::complex::Complex;
+/// use PauliOp::{
+///     X,
+///     Z,
+/// };
+///
+/// # fn main() -> Result<(), f2q::Error> {
+/// let repr = SumRepr::from([
+///     (1.0, Pauli::with_ops([X])),
+///     (0.5, Pauli::with_ops([Z])),
+/// ]);
+///
+/// // |+>
+/// let amp = Complex::new(0.5_f64.sqrt(), 0.0);
+/// let energy = statevector::expectation(&repr, &[amp, amp])?;
+///
+/// assert!((energy - 1.0).abs() < 1e-12);
+/// #   Ok(())
+/// # }
+/// ```
+pub fn expectation<T>(
+    repr: &SumRepr<T, Pauli>,
+    state: &[Complex<T>],
+) -> Result<T, Error>
+where
+    T: Float + Send + Sync,
+{
+    num_qubits(repr, state.len())?;
+    let blocks = blocks(repr);
+
+    Ok(state
+        .par_iter()
+        .enumerate()
+        .map(|(r, amp)| {
+            let value =
+                row_value(&blocks, state, r as u64, |c| Some(c as usize));
+            (amp.conj() * value).re
+        })
+        .reduce(T::zero, |a, b| a + b))
+}
@@ -2,11 +2,17 @@
 
 use std::collections::HashMap;
 
-use num::Num;
+use num::{
+    Float,
+    Num,
+};
 
 use crate::{
     code::{
-        fermions::Fermions,
+        fermions::{
+            Fermions,
+            Orbital,
+        },
         qubits::Pauli,
         Code,
     },
@@ -240,6 +246,290 @@ where
     }
 }
 
+impl<T> SumRepr<T, Fermions>
+where
+    T: Float,
+{
+    /// Relabel orbitals in every term of the sum.
+    ///
+    /// See [`Fermions::relabel()`] for how each term is brought back to the
+    /// canonical order.  This can be used e.g. to change the ordering of
+    /// spin-orbitals in a Hamiltonian.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `f` maps two distinct orbitals of a term to the same
+    /// orbital.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::fermions::{Fermions, Orbital, OrbitalOrdering},
+    /// #     terms::SumRepr,
+    /// # };
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// let ordering = OrbitalOrdering::Block {
+    ///     num_orbitals: 3
+    /// };
+    /// let repr = SumRepr::from([(1.0, Fermions::try_from((1, 4))?)]);
+    ///
+    /// // Interpret indices as given in the block ordering
+    /// let repr = repr.relabel(|orb| ordering.orbital(orb.index()))?;
+    ///
+    /// assert_eq!(repr.coeff(Fermions::try_from((2, 3))?), Some(&1.0));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn relabel<F>(
+        &self,
+        f: F,
+    ) -> Result<Self, Error>
+    where
+        F: Fn(Orbital) -> Orbital,
+    {
+        let mut repr = Self::with_capacity(self.len());
+        for (&coeff, code) in self.iter() {
+            let (coeff, code) =
+                code.relabel(coeff, &f).ok_or(Error::QubitIndex {
+                    msg: "relabeled orbitals must be distinct".to_string(),
+                })?;
+            repr.add_term(code, coeff);
+        }
+
+        Ok(repr)
+    }
+}
+
+impl<T> SumRepr<T, Pauli>
+where
+    T: Num + Copy,
+{
+    /// Relabel qubits in every term of the sum.
+    ///
+    /// The Pauli operator at site `i` is moved to site `perm[i]`.
+    /// See [`Pauli::permute()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `perm` is not a permutation of `0..perm.len()`, with
+    /// `perm.len() <= 64`, or if a term acts on a qubit outside of
+    /// `0..perm.len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{code::qubits::{Pauli, PauliOp}, terms::SumRepr};
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// use PauliOp::{
+    ///     I,
+    ///     X,
+    ///     Z,
+    /// };
+    ///
+    /// let repr = SumRepr::from([
+    ///     (1.0, Pauli::with_ops([X, Z])),
+    ///     (2.0, Pauli::with_ops([I, X])),
+    /// ]);
+    /// let repr = repr.permute(&[1, 0])?;
+    ///
+    /// assert_eq!(repr.coeff(Pauli::with_ops([Z, X])), Some(&1.0));
+    /// assert_eq!(repr.coeff(Pauli::with_ops([X])), Some(&2.0));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn permute(
+        &self,
+        perm: &[u16],
+    ) -> Result<Self, Error> {
+        let mut seen = [false; 64];
+        if perm.len() > 64
+            || !perm.iter().all(|&j| {
+                usize::from(j) < perm.len()
+                    && !std::mem::replace(&mut seen[usize::from(j)], true)
+            })
+        {
+            return Err(Error::QubitIndex {
+                msg: "qubit indices must be a permutation".to_string(),
+            });
+        }
+
+        let mut repr = Self::with_capacity(self.len());
+        for (&coeff, code) in self.iter() {
+            repr.add_term(code.permute(perm)?, coeff);
+        }
+
+        Ok(repr)
+    }
+
+    /// Embed the sum into a larger register, shifting qubit indices by
+    /// `offset`.
+    ///
+    /// See [`Pauli::embed()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a shifted term does not fit into 64 qubits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{code::qubits::{Pauli, PauliOp}, terms::SumRepr};
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// use PauliOp::{
+    ///     I,
+    ///     X,
+    /// };
+    ///
+    /// let repr = SumRepr::from([(1.0, Pauli::with_ops([X]))]);
+    /// let repr = repr.embed(3)?;
+    ///
+    /// assert_eq!(repr.coeff(Pauli::with_ops([I, I, I, X])), Some(&1.0));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn embed(
+        &self,
+        offset: u16,
+    ) -> Result<Self, Error> {
+        let mut repr = Self::with_capacity(self.len());
+        for (&coeff, code) in self.iter() {
+            repr.add_term(code.embed(offset)?, coeff);
+        }
+
+        Ok(repr)
+    }
+}
+
+fn check_register_size<T>(
+    repr: &SumRepr<T, Pauli>,
+    offset: u16,
+) -> Result<(), Error> {
+    if repr
+        .iter()
+        .all(|(_, code)| u16::from(code.min_register_size()) <= offset)
+    {
+        Ok(())
+    } else {
+        Err(Error::QubitIndex {
+            msg: format!("first operand must act on qubits: 0..{offset}"),
+        })
+    }
+}
+
+/// Tensor (Kronecker) product of two sums of Pauli strings.
+///
+/// The first sum, `a`, must act on qubits `0..offset`.  The second one, `b`,
+/// is placed on qubits starting from `offset`.
+///
+/// # Errors
+///
+/// Returns error if `a` acts on qubits outside of `0..offset`, or if the
+/// result does not fit into 64 qubits.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     terms::{tensor, SumRepr},
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// use PauliOp::{
+///     I,
+///     X,
+///     Z,
+/// };
+///
+/// let a =
+///     SumRepr::from([(2.0, Pauli::with_ops([X])), (1.0, Pauli::identity())]);
+/// let b = SumRepr::from([(3.0, Pauli::with_ops([Z]))]);
+///
+/// let repr = tensor(&a, &b, 2)?;
+///
+/// assert_eq!(repr.len(), 2);
+/// assert_eq!(repr.coeff(Pauli::with_ops([X, I, Z])), Some(&6.0));
+/// assert_eq!(repr.coeff(Pauli::with_ops([I, I, Z])), Some(&3.0));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn tensor<T>(
+    a: &SumRepr<T, Pauli>,
+    b: &SumRepr<T, Pauli>,
+    offset: u16,
+) -> Result<SumRepr<T, Pauli>, Error>
+where
+    T: Num + Copy,
+{
+    check_register_size(a, offset)?;
+    let b = b.embed(offset)?;
+
+    let mut repr = SumRepr::with_capacity(a.len() * b.len());
+    for (&coeff_a, code_a) in a.iter() {
+        for (&coeff_b, code_b) in b.iter() {
+            let code = Pauli::from(code_a.index() | code_b.index());
+            repr.add_term(code, coeff_a * coeff_b);
+        }
+    }
+
+    Ok(repr)
+}
+
+/// Kronecker sum of Hamiltonians acting on disjoint registers.
+///
+/// The first sum, `a`, must act on qubits `0..offset`.  The second one, `b`,
+/// is placed on qubits starting from `offset`.  The result represents:
+/// `a ⊗ I + I ⊗ b`, i.e. the Hamiltonian of two non-interacting systems.
+/// Identity terms of both sums add up.
+///
+/// # Errors
+///
+/// Returns error if `a` acts on qubits outside of `0..offset`, or if the
+/// result does not fit into 64 qubits.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     terms::{kronecker_sum, SumRepr},
+/// # };
+/// # fn main() -> Result<(), f2q::Error> {
+/// use PauliOp::{
+///     I,
+///     X,
+///     Z,
+/// };
+///
+/// let a =
+///     SumRepr::from([(2.0, Pauli::with_ops([X])), (1.0, Pauli::identity())]);
+/// let b =
+///     SumRepr::from([(3.0, Pauli::with_ops([Z])), (1.0, Pauli::identity())]);
+///
+/// let repr = kronecker_sum(&a, &b, 2)?;
+///
+/// assert_eq!(repr.len(), 3);
+/// assert_eq!(repr.coeff(Pauli::with_ops([X])), Some(&2.0));
+/// assert_eq!(repr.coeff(Pauli::with_ops([I, I, Z])), Some(&3.0));
+/// assert_eq!(repr.coeff(Pauli::identity()), Some(&2.0));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn kronecker_sum<T>(
+    a: &SumRepr<T, Pauli>,
+    b: &SumRepr<T, Pauli>,
+    offset: u16,
+) -> Result<SumRepr<T, Pauli>, Error>
+where
+    T: Num + Copy,
+{
+    check_register_size(a, offset)?;
+    let mut repr = b.embed(offset)?;
+    repr.extend(a.iter().map(|(&coeff, &code)| (coeff, code)));
+
+    Ok(repr)
+}
+
 impl<T, K> FromIterator<(T, K)> for SumRepr<T, K>
 where
     T: Num,
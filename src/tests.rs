@@ -1,5 +1,26 @@
+mod binary;
+mod circuit;
+mod csv;
 mod fermions;
+mod fixtures;
+mod grouping;
+// Sorted with `sort_by`, flagged by newer versions of clippy.
+#[allow(clippy::unnecessary_sort_by)]
 mod jordan_wigner;
+mod jsonl;
+mod lanczos;
 mod math;
+mod measure;
+mod metrics;
+mod npy;
+mod openfermion;
+mod qiskit;
 mod qubit;
+mod reference;
+mod reorder;
+mod sparse;
+mod stabilizer;
+mod statevector;
+#[allow(clippy::unnecessary_sort_by)]
 mod terms;
+mod trotter;
@@ -0,0 +1,193 @@
+use super::fixtures::{
+    assert_sums_eq,
+    random_fermi_sum,
+    random_pauli_sum,
+};
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::{
+            Pauli,
+            PauliOp,
+        },
+    },
+    io::binary::{
+        read_sum,
+        write_sum,
+        Reader,
+        Writer,
+        UNKNOWN_NUM_TERMS,
+    },
+    terms::{
+        FermiSum,
+        PauliSum,
+        SumRepr,
+    },
+};
+
+#[test]
+fn binary_header() {
+    let mut buf = Vec::new();
+    write_sum(&PauliSum::new(), &mut buf).unwrap();
+    assert_eq!(buf, b"F2QB\x01\x00\x01\x00\0\0\0\0\0\0\0\0");
+
+    let mut buf = Vec::new();
+    write_sum(&FermiSum::new(), &mut buf).unwrap();
+    assert_eq!(buf, b"F2QB\x01\x00\x02\x00\0\0\0\0\0\0\0\0");
+}
+
+#[test]
+fn binary_pauli_bytes() {
+    let mut code = Pauli::with_ops([PauliOp::Y]);
+    code.set(32, PauliOp::Z);
+    let repr = SumRepr::from([(1.0, code)]);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf).unwrap();
+
+    assert_eq!(&buf[8..16], &1_u64.to_le_bytes());
+    assert_eq!(&buf[16..24], &1.0_f64.to_le_bytes());
+    assert_eq!(&buf[24..32], &2_u64.to_le_bytes());
+    assert_eq!(&buf[32..40], &3_u64.to_le_bytes());
+    assert_eq!(buf.len(), 40);
+}
+
+#[test]
+fn binary_fermions_bytes() {
+    let repr = SumRepr::from([(-0.5, Fermions::try_from((1, 2)).unwrap())]);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf).unwrap();
+
+    assert_eq!(&buf[16..24], &(-0.5_f64).to_le_bytes());
+    assert_eq!(&buf[24..], &[2, 1, 0, 0, 0, 2, 0, 0, 0]);
+}
+
+#[test]
+fn binary_roundtrip_pauli() {
+    let repr = random_pauli_sum(46, 1000);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf).unwrap();
+    let other: PauliSum = read_sum(buf.as_slice()).unwrap();
+
+    assert_eq!(buf.len(), 16 + 24 * repr.len());
+    assert_eq!(other.len(), repr.len());
+    assert_sums_eq(&repr, &other, 0.0);
+}
+
+#[test]
+fn binary_roundtrip_fermions() {
+    // Indices above 255 take more than one byte
+    let repr = random_fermi_sum(46, 1000, 1 << 16);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf).unwrap();
+    let other: FermiSum = read_sum(buf.as_slice()).unwrap();
+
+    assert_eq!(other.len(), repr.len());
+    assert_sums_eq(&repr, &other, 0.0);
+}
+
+#[test]
+fn binary_invalid_header() {
+    let mut buf = Vec::new();
+    write_sum(&SumRepr::from([(1.0, Pauli::identity())]), &mut buf).unwrap();
+
+    // Wrong encoding
+    assert!(read_sum::<Fermions, _>(buf.as_slice()).is_err());
+
+    let mut data = buf.clone();
+    data[0] = b'X';
+    assert!(read_sum::<Pauli, _>(data.as_slice()).is_err());
+
+    let mut data = buf.clone();
+    data[4] = 2;
+    assert!(read_sum::<Pauli, _>(data.as_slice()).is_err());
+
+    assert!(read_sum::<Pauli, _>(&buf[..10]).is_err());
+}
+
+#[test]
+fn binary_invalid_terms() {
+    let mut buf = Vec::new();
+    write_sum(&SumRepr::from([(1.0, Pauli::identity())]), &mut buf).unwrap();
+    assert!(read_sum::<Pauli, _>(&buf[..buf.len() - 1]).is_err());
+
+    // Declared two terms, one stored
+    let mut data = buf.clone();
+    data[8] = 2;
+    let mut reader = Reader::<_, Pauli>::new(data.as_slice()).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+
+    // Non-canonical one-electron term
+    let repr = SumRepr::from([(1.0, Fermions::try_from((1, 2)).unwrap())]);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf).unwrap();
+    buf[25] = 3;
+    assert!(read_sum::<Fermions, _>(buf.as_slice()).is_err());
+
+    // Invalid number of indices
+    buf[24] = 3;
+    assert!(read_sum::<Fermions, _>(buf.as_slice()).is_err());
+}
+
+#[test]
+fn binary_canonical_order() {
+    let terms = [
+        (1.0, Fermions::try_from((0, 1, 3, 2)).unwrap()),
+        (2.0, Fermions::try_from((1, 2)).unwrap()),
+        (3.0, Fermions::Offset),
+        (4.0, Fermions::try_from((0, 3)).unwrap()),
+    ];
+    let mut buf = Vec::new();
+    write_sum(&SumRepr::from(terms), &mut buf).unwrap();
+    let mut other = Vec::new();
+    let mut reversed = terms;
+    reversed.reverse();
+    write_sum(&SumRepr::from(reversed), &mut other).unwrap();
+    assert_eq!(buf, other);
+
+    let codes: Vec<_> = Reader::<_, Fermions>::new(buf.as_slice())
+        .unwrap()
+        .map(|term| term.unwrap().1)
+        .collect();
+    assert_eq!(codes, [terms[2].1, terms[3].1, terms[1].1, terms[0].1]);
+}
+
+#[test]
+fn binary_writer_num_terms() {
+    let mut writer = Writer::<_, Pauli>::new(Vec::new(), 1).unwrap();
+    assert!(writer.write_term(1.0, &Pauli::identity()).is_ok());
+    assert!(writer.write_term(1.0, &Pauli::identity()).is_err());
+    assert!(writer.finish().is_ok());
+
+    let writer = Writer::<_, Pauli>::new(Vec::new(), 1).unwrap();
+    assert!(writer.finish().is_err());
+}
+
+#[test]
+fn binary_unknown_num_terms() {
+    let mut writer = Writer::with_unknown_num_terms(Vec::new()).unwrap();
+    for i in 0..10 {
+        let code = Fermions::try_from((i % 3, i % 3)).unwrap();
+        writer.write_term(f64::from(i), &code).unwrap();
+    }
+    let buf = writer.finish().unwrap();
+    assert_eq!(&buf[8..16], &UNKNOWN_NUM_TERMS.to_le_bytes());
+
+    let reader = Reader::<_, Fermions>::new(buf.as_slice()).unwrap();
+    assert_eq!(reader.num_terms(), None);
+    assert_eq!(reader.count(), 10);
+
+    let repr: FermiSum = read_sum(buf.as_slice()).unwrap();
+    assert_eq!(repr.len(), 3);
+    assert_eq!(repr.coeff(Fermions::try_from((0, 0)).unwrap()), Some(&18.0));
+    assert_eq!(repr.coeff(Fermions::try_from((1, 1)).unwrap()), Some(&12.0));
+    assert_eq!(repr.coeff(Fermions::try_from((2, 2)).unwrap()), Some(&15.0));
+
+    // Truncated term
+    assert!(read_sum::<Fermions, _>(&buf[..buf.len() - 1]).is_err());
+    assert!(read_sum::<Fermions, _>(&buf[..buf.len() - 13]).is_err());
+    // Header only
+    let repr: FermiSum = read_sum(&buf[..16]).unwrap();
+    assert!(repr.is_empty());
+}
@@ -0,0 +1,363 @@
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use crate::{
+    circuit::{
+        Circuit,
+        Gate,
+        QasmVersion,
+    },
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    math::Root4,
+    stabilizer::StabilizerState,
+    terms::{
+        PauliSum,
+        SumRepr,
+    },
+    Error,
+};
+
+#[test]
+fn gate_conjugate_single() {
+    let h = Gate::H(0);
+    assert_eq!(
+        h.conjugate(Pauli::with_ops([X])),
+        (Root4::R0, Pauli::with_ops([Z]))
+    );
+    assert_eq!(
+        h.conjugate(Pauli::with_ops([Z])),
+        (Root4::R0, Pauli::with_ops([X]))
+    );
+    assert_eq!(
+        h.conjugate(Pauli::with_ops([Y])),
+        (Root4::R1, Pauli::with_ops([Y]))
+    );
+
+    let s = Gate::S(1);
+    assert_eq!(
+        s.conjugate(Pauli::with_ops([I, X])),
+        (Root4::R0, Pauli::with_ops([I, Y]))
+    );
+    assert_eq!(
+        s.conjugate(Pauli::with_ops([I, Y])),
+        (Root4::R1, Pauli::with_ops([I, X]))
+    );
+    assert_eq!(
+        s.conjugate(Pauli::with_ops([Z, Z])),
+        (Root4::R0, Pauli::with_ops([Z, Z]))
+    );
+
+    let sdg = Gate::Sdg(0);
+    assert_eq!(
+        sdg.conjugate(Pauli::with_ops([X])),
+        (Root4::R1, Pauli::with_ops([Y]))
+    );
+    assert_eq!(
+        sdg.conjugate(Pauli::with_ops([Y])),
+        (Root4::R0, Pauli::with_ops([X]))
+    );
+}
+
+#[test]
+fn gate_conjugate_two_qubit() {
+    let cnot = Gate::Cnot(0, 2);
+    assert_eq!(
+        cnot.conjugate(Pauli::with_ops([X, Z, I])),
+        (Root4::R0, Pauli::with_ops([X, Z, X]))
+    );
+    assert_eq!(
+        cnot.conjugate(Pauli::with_ops([I, I, Z])),
+        (Root4::R0, Pauli::with_ops([Z, I, Z]))
+    );
+    assert_eq!(
+        cnot.conjugate(Pauli::with_ops([Y, I, Y])),
+        (Root4::R1, Pauli::with_ops([X, I, Z]))
+    );
+    assert_eq!(
+        cnot.conjugate(Pauli::with_ops([X, I, Z])),
+        (Root4::R1, Pauli::with_ops([Y, I, Y]))
+    );
+
+    let cz = Gate::Cz(0, 1);
+    assert_eq!(
+        cz.conjugate(Pauli::with_ops([X, I])),
+        (Root4::R0, Pauli::with_ops([X, Z]))
+    );
+    assert_eq!(
+        cz.conjugate(Pauli::with_ops([X, X])),
+        (Root4::R0, Pauli::with_ops([Y, Y]))
+    );
+    assert_eq!(
+        cz.conjugate(Pauli::with_ops([X, Y])),
+        (Root4::R1, Pauli::with_ops([Y, X]))
+    );
+
+    let swap = Gate::Swap(0, 1);
+    assert_eq!(
+        swap.conjugate(Pauli::with_ops([X, Y, Z])),
+        (Root4::R0, Pauli::with_ops([Y, X, Z]))
+    );
+}
+
+#[test]
+fn circuit_conjugate() {
+    let mut circuit = Circuit::new(2);
+    circuit.push(Gate::H(0)).unwrap();
+    circuit.push(Gate::Cnot(0, 1)).unwrap();
+
+    // Bell state stabilizers
+    assert_eq!(
+        circuit.conjugate(Pauli::with_ops([Z, I])),
+        (Root4::R0, Pauli::with_ops([X, X]))
+    );
+    assert_eq!(
+        circuit.conjugate(Pauli::with_ops([I, Z])),
+        (Root4::R0, Pauli::with_ops([Z, Z]))
+    );
+}
+
+#[test]
+fn circuit_push_enlarges_register() {
+    let mut circuit = Circuit::new(1);
+    assert!(circuit.is_empty());
+
+    circuit.push(Gate::Cz(0, 4)).unwrap();
+    assert_eq!(circuit.num_qubits(), 5);
+    assert_eq!(circuit.len(), 1);
+}
+
+#[test]
+fn circuit_push_invalid() {
+    let mut circuit = Circuit::new(3);
+
+    for gate in [Gate::Cnot(1, 1), Gate::Cz(2, 2), Gate::Swap(7, 7)] {
+        assert!(matches!(circuit.push(gate), Err(Error::QubitIndex { .. })));
+    }
+    for phi in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(matches!(
+            circuit.push(Gate::Rz(0, phi)),
+            Err(Error::Coefficient { .. })
+        ));
+    }
+    assert!(circuit.is_empty());
+    assert_eq!(circuit.num_qubits(), 3);
+}
+
+#[test]
+#[should_panic(expected = "two-qubit gate must act on distinct qubits")]
+fn gate_conjugate_same_qubit() {
+    let _ = Gate::Cnot(0, 0).conjugate(Pauli::with_ops([X]));
+}
+
+#[test]
+fn circuit_to_qasm() {
+    let mut circuit = Circuit::new(3);
+    circuit.push(Gate::Sdg(1)).unwrap();
+    circuit.push(Gate::H(1)).unwrap();
+    circuit.push(Gate::Cz(0, 2)).unwrap();
+    circuit.push(Gate::Swap(1, 2)).unwrap();
+    circuit.push(Gate::S(0)).unwrap();
+
+    assert_eq!(
+        circuit.to_qasm(QasmVersion::V2, false),
+        "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[3];\nsdg q[1];\nh \
+         q[1];\ncz q[0],q[2];\ncx q[1],q[2];\ncx q[2],q[1];\ncx q[1],q[2];\ns \
+         q[0];\n"
+    );
+}
+
+#[test]
+fn circuit_to_qasm_v3_swap() {
+    let mut circuit = Circuit::new(2);
+    circuit.push(Gate::Swap(0, 1)).unwrap();
+
+    assert_eq!(
+        circuit.to_qasm(QasmVersion::V3, false),
+        "OPENQASM 3.0;\ninclude \"stdgates.inc\";\nqubit[2] q;\nswap \
+         q[0],q[1];\n"
+    );
+}
+
+#[test]
+fn circuit_to_qasm_empty() {
+    let circuit = Circuit::default();
+
+    assert_eq!(
+        circuit.to_qasm(QasmVersion::V2, true),
+        "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[1];\ncreg \
+         c[1];\nmeasure q -> c;\n"
+    );
+}
+
+#[test]
+fn circuit_to_qasm_v3() {
+    let mut circuit = Circuit::new(3);
+    circuit.push(Gate::Sdg(1)).unwrap();
+    circuit.push(Gate::Cnot(1, 2)).unwrap();
+    circuit.push(Gate::Rz(2, -0.25)).unwrap();
+
+    assert_eq!(
+        circuit.to_qasm(QasmVersion::V3, false),
+        "OPENQASM 3.0;\ninclude \"stdgates.inc\";\nqubit[3] q;\nsdg q[1];\ncx \
+         q[1],q[2];\nrz(-0.25) q[2];\n"
+    );
+}
+
+#[test]
+fn circuit_write_qasm() {
+    let mut circuit = Circuit::new(1);
+    circuit.push(Gate::Rz(0, 1.5)).unwrap();
+
+    let mut buf = Vec::new();
+    circuit.write_qasm(&mut buf, QasmVersion::V3, true).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "OPENQASM 3.0;\ninclude \"stdgates.inc\";\nqubit[1] q;\nbit[1] \
+         c;\nrz(1.5) q[0];\nc = measure q;\n"
+    );
+}
+
+fn random_clifford(
+    rng: &mut StdRng,
+    num_qubits: u16,
+    num_gates: usize,
+) -> Circuit {
+    let mut circuit = Circuit::new(num_qubits);
+    for _ in 0..num_gates {
+        let a = rng.gen_range(0..num_qubits);
+        let b = (a + rng.gen_range(1..num_qubits)) % num_qubits;
+        circuit
+            .push(match rng.gen_range(0..6) {
+                0 => Gate::H(a),
+                1 => Gate::S(a),
+                2 => Gate::Sdg(a),
+                3 => Gate::Cnot(a, b),
+                4 => Gate::Cz(a, b),
+                _ => Gate::Swap(a, b),
+            })
+            .unwrap();
+    }
+    circuit
+}
+
+fn random_repr(
+    rng: &mut StdRng,
+    num_qubits: u16,
+    num_terms: usize,
+) -> PauliSum {
+    let mut repr = PauliSum::new();
+    for _ in 0..num_terms {
+        let mut code = Pauli::identity();
+        for i in 0..num_qubits {
+            code.set(i, PauliOp::try_from(rng.gen_range(0..4_u32)).unwrap());
+        }
+        repr.add_term(code, rng.gen_range(-1.0..1.0));
+    }
+    repr
+}
+
+#[test]
+fn gate_inverse() {
+    for gate in [
+        Gate::H(0),
+        Gate::S(1),
+        Gate::Sdg(1),
+        Gate::Cnot(0, 2),
+        Gate::Cz(2, 0),
+        Gate::Swap(1, 2),
+    ] {
+        assert!(gate.is_clifford());
+        for code in [
+            Pauli::with_ops([X, Y, Z]),
+            Pauli::with_ops([Y, I, X]),
+            Pauli::with_ops([Z, Z, Y]),
+        ] {
+            let (w1, image) = gate.conjugate(code);
+            let (w2, back) = gate.inverse().conjugate(image);
+            assert_eq!((w1 * w2, back), (Root4::R0, code));
+        }
+    }
+    assert!(!Gate::Rz(0, 1.0).is_clifford());
+}
+
+#[test]
+fn circuit_inverse() {
+    let mut circuit = Circuit::new(3);
+    circuit.push(Gate::H(0)).unwrap();
+    circuit.push(Gate::Rz(1, 0.25)).unwrap();
+    circuit.push(Gate::Sdg(2)).unwrap();
+
+    let inverse = circuit.inverse();
+    assert_eq!(inverse.num_qubits(), 3);
+    assert_eq!(
+        inverse.gates(),
+        &[Gate::S(2), Gate::Rz(1, -0.25), Gate::H(0)]
+    );
+    assert!(!circuit.is_clifford());
+    assert!(Circuit::new(2).is_clifford());
+}
+
+#[test]
+fn circuit_conjugate_sum_roundtrip() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for num_qubits in 2..=8 {
+        let circuit = random_clifford(&mut rng, num_qubits, 40);
+        let repr = random_repr(&mut rng, num_qubits, 30);
+
+        let image = circuit.conjugate_sum(&repr);
+        assert_eq!(image.len(), repr.len());
+
+        let back = circuit.inverse().conjugate_sum(&image);
+        for (coeff, code) in repr.iter() {
+            assert_eq!(back.coeff(*code), Some(coeff));
+        }
+    }
+}
+
+#[test]
+fn circuit_conjugate_sum_stabilizer() {
+    // <0|U^+ H U|0> = <U 0|H|U 0>
+    let mut rng = StdRng::seed_from_u64(142);
+    for num_qubits in 2..=8 {
+        let circuit = random_clifford(&mut rng, num_qubits, 40);
+        let repr = random_repr(&mut rng, num_qubits, 30);
+
+        let expected = StabilizerState::from_circuit(&circuit)
+            .expectation(&repr)
+            .unwrap();
+        let value = StabilizerState::new(num_qubits)
+            .expectation(&circuit.inverse().conjugate_sum(&repr))
+            .unwrap();
+        assert!((value - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn circuit_conjugate_sum_signs() {
+    let mut circuit = Circuit::new(1);
+    circuit.push(Gate::S(0)).unwrap();
+    let repr = SumRepr::from([
+        (1.0, Pauli::with_ops([X])),
+        (2.0, Pauli::with_ops([Y])),
+        (3.0, Pauli::with_ops([Z])),
+        (4.0, Pauli::identity()),
+    ]);
+    let image = circuit.conjugate_sum(&repr);
+
+    assert_eq!(image.coeff(Pauli::with_ops([Y])), Some(&1.0));
+    assert_eq!(image.coeff(Pauli::with_ops([X])), Some(&-2.0));
+    assert_eq!(image.coeff(Pauli::with_ops([Z])), Some(&3.0));
+    assert_eq!(image.coeff(Pauli::identity()), Some(&4.0));
+}
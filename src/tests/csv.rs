@@ -0,0 +1,118 @@
+use super::fixtures::{
+    assert_sums_eq,
+    random_fermi_sum,
+    random_pauli_sum,
+};
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::{
+            Pauli,
+            PauliOp,
+        },
+    },
+    io::csv::{
+        read_sum,
+        write_sum,
+        CSV,
+        TSV,
+    },
+    terms::{
+        FermiSum,
+        PauliSum,
+    },
+    Error,
+};
+
+#[test]
+fn csv_roundtrip_fermions() {
+    let repr = random_fermi_sum(49, 100, 16);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf, CSV).unwrap();
+    let other: FermiSum = read_sum(buf.as_slice(), CSV).unwrap();
+
+    assert_eq!(buf.iter().filter(|&&b| b == b'\n').count(), repr.len() + 1);
+    assert_eq!(other.len(), repr.len());
+    assert_sums_eq(&repr, &other, 0.0);
+}
+
+#[test]
+fn csv_roundtrip_pauli() {
+    let repr = random_pauli_sum(49, 100);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf, TSV).unwrap();
+    let other: PauliSum = read_sum(buf.as_slice(), TSV).unwrap();
+
+    assert_eq!(other.len(), repr.len());
+    assert_sums_eq(&repr, &other, 0.0);
+}
+
+#[test]
+fn csv_write_fermions() {
+    let repr = FermiSum::from([
+        (0.5, Fermions::Offset),
+        (-0.25, Fermions::try_from((0, 1, 3, 2)).unwrap()),
+    ]);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf, CSV).unwrap();
+    let text = String::from_utf8(buf).unwrap();
+    let lines: Vec<_> = text.lines().collect();
+
+    assert_eq!(lines, ["p,q,r,s,value", ",,,,0.5", "0,1,3,2,-0.25"]);
+}
+
+#[test]
+fn csv_read_pauli_notations() {
+    let data = "pauli,weight,value\nXIZ,2,0.5\n\nX0 Z2,,0.25\nI,0,-1\n";
+    let repr: PauliSum = read_sum(data.as_bytes(), CSV).unwrap();
+
+    assert_eq!(repr.len(), 2);
+    assert_eq!(
+        repr.coeff(Pauli::with_ops([PauliOp::X, PauliOp::I, PauliOp::Z])),
+        Some(&0.75)
+    );
+    assert_eq!(repr.coeff(Pauli::identity()), Some(&-1.0));
+}
+
+#[test]
+fn csv_read_errors() {
+    let read = |data: &str| read_sum::<f64, Fermions, _>(data.as_bytes(), CSV);
+
+    // missing header
+    assert!(matches!(read("0,1,,,1.0\n"), Err(Error::Parse { .. })));
+    // wrong number of fields
+    assert!(matches!(
+        read("p,q,r,s,value\n0,1,1.0\n"),
+        Err(Error::Parse { .. })
+    ));
+    // three indices
+    assert!(matches!(
+        read("p,q,r,s,value\n0,1,2,,1.0\n"),
+        Err(Error::Parse { .. })
+    ));
+    // gap between indices
+    assert!(matches!(
+        read("p,q,r,s,value\n0,,1,,1.0\n"),
+        Err(Error::Parse { .. })
+    ));
+    // not in the canonical order
+    assert!(matches!(
+        read("p,q,r,s,value\n1,0,,,1.0\n"),
+        Err(Error::Parse { .. })
+    ));
+    // invalid value
+    assert!(matches!(
+        read("p,q,r,s,value\n0,1,,,x\n"),
+        Err(Error::Parse { .. })
+    ));
+    // quoted field
+    assert!(matches!(
+        read("p,q,r,s,value\n\"0\",1,,,1.0\n"),
+        Err(Error::Parse { .. })
+    ));
+    // Pauli header
+    assert!(matches!(
+        read_sum::<f64, Pauli, _>("p,q,r,s,value\n".as_bytes(), CSV),
+        Err(Error::Parse { .. })
+    ));
+}
@@ -1,11 +1,15 @@
 use std::ops::Range;
 
-use crate::code::fermions::{
-    An,
-    Cr,
-    Fermions,
-    Orbital,
-    Spin,
+use crate::{
+    code::fermions::{
+        An,
+        Cr,
+        Fermions,
+        Orbital,
+        OrbitalOrdering,
+        Spin,
+    },
+    terms::SumRepr,
 };
 
 #[test]
@@ -187,3 +191,184 @@ fn fermions_two_from_01() {
     Fermions::try_from((0, 1, 1, 1)).unwrap_err();
     Fermions::try_from((1, 2, 1, 0)).unwrap_err();
 }
+
+#[test]
+fn orbital_ordering_interleaved_01() {
+    let ordering = OrbitalOrdering::Interleaved;
+    for index in 0..64 {
+        let orb = Orbital::with_index_in(index, ordering);
+        assert_eq!(orb, Orbital::with_index(index));
+        assert_eq!(orb.index_in(ordering), index);
+    }
+}
+
+#[test]
+fn orbital_ordering_block_01() {
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 5
+    };
+
+    assert_eq!(Orbital::new(0, Spin::Down).index_in(ordering), 0);
+    assert_eq!(Orbital::new(4, Spin::Down).index_in(ordering), 4);
+    assert_eq!(Orbital::new(0, Spin::Up).index_in(ordering), 5);
+    assert_eq!(Orbital::new(4, Spin::Up).index_in(ordering), 9);
+
+    for index in 0..10 {
+        let orb = Orbital::with_index_in(index, ordering);
+        assert_eq!(orb.index_in(ordering), index);
+    }
+}
+
+#[test]
+fn orbital_ordering_block_02() {
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 5
+    };
+
+    assert_eq!(ordering.checked_index(&Orbital::new(5, Spin::Down)), None);
+    assert_eq!(ordering.checked_index(&Orbital::new(5, Spin::Up)), None);
+    assert_eq!(ordering.checked_orbital(10), None);
+    assert_eq!(ordering.checked_orbital(u32::MAX), None);
+}
+
+#[test]
+#[should_panic(expected = "orbital index out of bound")]
+fn orbital_ordering_block_03() {
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 5
+    };
+    let _ = Orbital::with_index_in(10, ordering);
+}
+
+#[test]
+fn fermions_relabel_01() {
+    let identity = |orb| orb;
+
+    let code = Fermions::Offset;
+    assert_eq!(code.relabel(1.0, identity), Some((1.0, code)));
+
+    let code = Fermions::try_from((1, 2)).unwrap();
+    assert_eq!(code.relabel(1.0, identity), Some((1.0, code)));
+
+    let code = Fermions::try_from((0, 3, 2, 1)).unwrap();
+    assert_eq!(code.relabel(1.0, identity), Some((1.0, code)));
+}
+
+#[test]
+fn fermions_relabel_02() {
+    let reverse = |orb: Orbital| Orbital::with_index(9 - orb.index());
+
+    let code = Fermions::try_from((1, 2)).unwrap();
+    assert_eq!(
+        code.relabel(1.0, reverse),
+        Some((1.0, Fermions::try_from((7, 8)).unwrap()))
+    );
+
+    let code = Fermions::try_from((2, 2)).unwrap();
+    assert_eq!(
+        code.relabel(1.0, reverse),
+        Some((1.0, Fermions::try_from((7, 7)).unwrap()))
+    );
+
+    // a+_0 a+_3 a_2 a_1 -> a+_9 a+_6 a_7 a_8 = a+_6 a+_9 a_8 a_7
+    let code = Fermions::try_from((0, 3, 2, 1)).unwrap();
+    assert_eq!(
+        code.relabel(1.0, reverse),
+        Some((1.0, Fermions::try_from((6, 9, 8, 7)).unwrap()))
+    );
+
+    // a+_0 a+_1 a_2 a_0 -> a+_9 a+_8 a_7 a_9 = a+_8 a+_9 a_9 a_7
+    // and the Hermitian conjugate is: a+_7 a+_9 a_9 a_8
+    let code = Fermions::try_from((0, 1, 2, 0)).unwrap();
+    assert_eq!(
+        code.relabel(1.0, reverse),
+        Some((1.0, Fermions::try_from((7, 9, 9, 8)).unwrap()))
+    );
+
+    // a+_0 a+_1 a_3 a_2 -> a+_9 a+_8 a_6 a_7 = a+_8 a+_9 a_7 a_6
+    // and the Hermitian conjugate is: a+_6 a+_7 a_9 a_8
+    let code = Fermions::try_from((0, 1, 3, 2)).unwrap();
+    assert_eq!(
+        code.relabel(1.0, reverse),
+        Some((1.0, Fermions::try_from((6, 7, 9, 8)).unwrap()))
+    );
+
+    let swap = |orb: Orbital| match orb.index() {
+        0 => Orbital::with_index(1),
+        1 => Orbital::with_index(0),
+        _ => orb,
+    };
+
+    // a+_0 a+_2 a_3 a_1 -> a+_1 a+_2 a_3 a_0
+    // and the Hermitian conjugate is: a+_0 a+_3 a_2 a_1
+    let code = Fermions::try_from((0, 2, 3, 1)).unwrap();
+    assert_eq!(
+        code.relabel(1.0, swap),
+        Some((1.0, Fermions::try_from((0, 3, 2, 1)).unwrap()))
+    );
+
+    // a+_0 a+_1 a_3 a_2 -> a+_1 a+_0 a_3 a_2 = - a+_0 a+_1 a_3 a_2
+    let code = Fermions::try_from((0, 1, 3, 2)).unwrap();
+    assert_eq!(
+        code.relabel(1.0, swap),
+        Some((-1.0, Fermions::try_from((0, 1, 3, 2)).unwrap()))
+    );
+}
+
+#[test]
+fn fermions_relabel_03() {
+    let collapse = |orb: Orbital| Orbital::with_index(orb.index() / 2);
+
+    let code = Fermions::try_from((0, 1, 3, 2)).unwrap();
+    assert_eq!(code.relabel(1.0, collapse), None);
+}
+
+#[test]
+fn fermions_deserialize_with_ordering_01() {
+    let json = r#"{"type":"sumrepr","encoding":"fermions","terms":[
+        {"code":[],"value":0.5},
+        {"code":[1,4],"value":1.0},
+        {"code":[0,1,5,2],"value":2.0}
+    ]}"#;
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 3
+    };
+
+    let mut de = serde_json::Deserializer::from_str(json);
+    let repr =
+        SumRepr::<f64, Fermions>::deserialize_with_ordering(&mut de, ordering)
+            .unwrap();
+    let expected = serde_json::from_str::<SumRepr<f64, Fermions>>(json)
+        .unwrap()
+        .relabel(|orb| ordering.orbital(orb.index()))
+        .unwrap();
+
+    assert_eq!(repr.len(), 3);
+    for (coeff, &code) in expected.iter() {
+        assert_eq!(repr.coeff(code), Some(coeff));
+    }
+
+    let mut de = serde_json::Deserializer::from_str(json);
+    let repr = SumRepr::<f64, Fermions>::deserialize_with_ordering(
+        &mut de,
+        OrbitalOrdering::Interleaved,
+    )
+    .unwrap();
+    assert_eq!(repr.coeff(Fermions::try_from((1, 4)).unwrap()), Some(&1.0));
+}
+
+#[test]
+fn fermions_deserialize_with_ordering_02() {
+    let json = r#"{"type":"sumrepr","encoding":"fermions","terms":[
+        {"code":[0,6],"value":1.0}
+    ]}"#;
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 3
+    };
+
+    let mut de = serde_json::Deserializer::from_str(json);
+    let err =
+        SumRepr::<f64, Fermions>::deserialize_with_ordering(&mut de, ordering)
+            .unwrap_err();
+    assert!(err.to_string().contains("out of bound"));
+}
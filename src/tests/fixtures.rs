@@ -0,0 +1,78 @@
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::Pauli,
+        Code,
+    },
+    terms::{
+        FermiSum,
+        PauliSum,
+        SumRepr,
+    },
+};
+
+// Offset, and up to `num_terms` one- and two-electron terms each, with
+// spin-orbital indices less than `num_orbitals`.
+pub(super) fn random_fermi_sum(
+    seed: u64,
+    num_terms: usize,
+    num_orbitals: u32,
+) -> FermiSum {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut repr = FermiSum::new();
+    repr.add_term(Fermions::Offset, rng.gen_range(-1.0..1.0));
+    for _ in 0..num_terms {
+        let (p, q, r, s) = (
+            rng.gen_range(0..num_orbitals),
+            rng.gen_range(0..num_orbitals),
+            rng.gen_range(0..num_orbitals),
+            rng.gen_range(0..num_orbitals),
+        );
+        if let Ok(code) = Fermions::try_from((p.min(q), p.max(q))) {
+            repr.add_term(code, rng.gen_range(-1.0..1.0));
+        }
+        if let Ok(code) = Fermions::try_from((p, q, r, s)) {
+            repr.add_term(code, rng.gen_range(-1.0..1.0));
+        }
+    }
+
+    repr
+}
+
+// Pauli strings on 64 qubits.
+pub(super) fn random_pauli_sum(
+    seed: u64,
+    num_terms: usize,
+) -> PauliSum {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut repr = PauliSum::new();
+    for _ in 0..num_terms {
+        repr.add_term(
+            Pauli::new((rng.gen(), rng.gen())),
+            rng.gen_range(-1.0..1.0),
+        );
+    }
+
+    repr
+}
+
+// Coefficients agree up to `tolerance`, missing terms count as zero.
+pub(super) fn assert_sums_eq<K>(
+    lhs: &SumRepr<f64, K>,
+    rhs: &SumRepr<f64, K>,
+    tolerance: f64,
+) where
+    K: Code,
+{
+    let coeff =
+        |repr: &SumRepr<f64, K>, code| *repr.coeff(code).unwrap_or(&0.0);
+    for (_, &code) in lhs.iter().chain(rhs.iter()) {
+        assert!((coeff(lhs, code) - coeff(rhs, code)).abs() <= tolerance);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    grouping::{
+        group,
+        Coloring,
+        Compatibility,
+    },
+    terms::{
+        PauliSum,
+        SumRepr,
+    },
+};
+
+fn sample_repr() -> PauliSum {
+    let mut repr = PauliSum::new();
+    for i in 0..200_u64 {
+        // pseudo-random Pauli strings on 6 qubits
+        let pack = (i * 2_654_435_761) % (1 << 12);
+        repr.add_term(Pauli::new((pack, 0)), 1.0 + i as f64);
+    }
+    repr
+}
+
+fn check_partition(
+    repr: &PauliSum,
+    groups: &[PauliSum],
+    compatibility: Compatibility,
+) {
+    let total: usize = groups.iter().map(SumRepr::len).sum();
+    assert_eq!(total, repr.len());
+
+    for g in groups {
+        assert!(!g.is_empty());
+        for (coeff, code) in g.iter() {
+            assert_eq!(repr.coeff(*code), Some(coeff));
+            for (_, other) in g.iter() {
+                assert!(compatibility.check(code, other));
+            }
+        }
+    }
+
+    for w in groups.windows(2) {
+        assert!(w[0].len() >= w[1].len());
+    }
+}
+
+#[test]
+fn commutes_01() {
+    use PauliOp::*;
+
+    let ops = [I, X, Y, Z];
+    for &a in &ops {
+        for &b in &ops {
+            let p = Pauli::with_ops([a]);
+            let q = Pauli::with_ops([b]);
+            let expected = a == I || b == I || a == b;
+            assert_eq!(p.commutes_with(&q), expected);
+            assert_eq!(p.commutes_qubitwise(&q), expected);
+        }
+    }
+}
+
+#[test]
+fn commutes_02() {
+    use PauliOp::*;
+
+    let p = Pauli::with_ops([X, Y, Z]);
+    assert!(p.commutes_with(&Pauli::with_ops([Y, X, Z])));
+    assert!(!p.commutes_qubitwise(&Pauli::with_ops([Y, X, Z])));
+    assert!(!p.commutes_with(&Pauli::with_ops([Y, Y, Z])));
+    assert!(p.commutes_with(&Pauli::with_ops([Z, Z, Z])));
+
+    // Anticommuting sites in both halves of the packed representation
+    let p = Pauli::new((0b01, 0b01));
+    let q = Pauli::new((0b11, 0b11));
+    assert!(p.commutes_with(&q));
+    assert!(!p.commutes_qubitwise(&q));
+    let q = Pauli::new((0b11, 0b01));
+    assert!(!p.commutes_with(&q));
+}
+
+#[test]
+fn commutes_03() {
+    // Compare with the phase of the product
+    let repr = sample_repr();
+    for (_, p) in repr.iter().take(40) {
+        for (_, q) in repr.iter().take(40) {
+            let (w1, _) = *p * *q;
+            let (w2, _) = *q * *p;
+            assert_eq!(p.commutes_with(q), w1 == w2);
+        }
+    }
+}
+
+#[test]
+fn group_empty() {
+    let repr = PauliSum::new();
+    assert!(
+        group(&repr, Compatibility::default(), Coloring::default()).is_empty()
+    );
+}
+
+#[test]
+fn group_partition_01() {
+    let repr = sample_repr();
+
+    for compatibility in
+        [Compatibility::FullyCommuting, Compatibility::QubitWise]
+    {
+        for coloring in [Coloring::LargestFirst, Coloring::Dsatur] {
+            let groups = group(&repr, compatibility, coloring);
+            check_partition(&repr, &groups, compatibility);
+        }
+    }
+}
+
+#[test]
+fn group_partition_02() {
+    let repr = sample_repr();
+
+    let fc = group(&repr, Compatibility::FullyCommuting, Coloring::Dsatur);
+    let qwc = group(&repr, Compatibility::QubitWise, Coloring::Dsatur);
+    assert!(fc.len() < qwc.len());
+}
+
+#[test]
+fn group_qubitwise_01() {
+    use PauliOp::*;
+
+    // All strings made of I and Z are measured together
+    let repr = SumRepr::from([
+        (1.0, Pauli::identity()),
+        (2.0, Pauli::with_ops([Z])),
+        (3.0, Pauli::with_ops([Z, Z])),
+        (4.0, Pauli::with_ops([I, Z, Z])),
+        (5.0, Pauli::with_ops([X, X])),
+    ]);
+
+    for coloring in [Coloring::LargestFirst, Coloring::Dsatur] {
+        let groups = group(&repr, Compatibility::QubitWise, coloring);
+        assert_eq!(groups.len(), 2);
+        for g in &groups {
+            if g.coeff(Pauli::with_ops([X, X])).is_none() {
+                assert_eq!(g.coeff(Pauli::with_ops([Z])), Some(&2.0));
+                assert_eq!(g.coeff(Pauli::with_ops([Z, Z])), Some(&3.0));
+                assert_eq!(g.coeff(Pauli::with_ops([I, Z, Z])), Some(&4.0));
+            }
+        }
+    }
+}
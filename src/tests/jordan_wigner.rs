@@ -2,17 +2,25 @@ use num::Float;
 
 use crate::{
     code::{
-        fermions::Fermions,
+        fermions::{
+            Fermions,
+            Orbital,
+            OrbitalOrdering,
+        },
         qubits::{
             Pauli,
             PauliOp,
         },
     },
-    map::JordanWigner,
+    map::{
+        jordan_wigner_term,
+        JordanWigner,
+    },
     terms::{
         SumRepr,
         Terms,
     },
+    Error,
 };
 
 fn jw_get_result<T: Float>(repr: &SumRepr<T, Fermions>) -> Vec<(T, Pauli)> {
@@ -341,3 +349,127 @@ fn jw_two_elec_06() {
         ],
     );
 }
+
+fn jw_get_result_ordered<T: Float>(
+    repr: &SumRepr<T, Fermions>,
+    ordering: OrbitalOrdering,
+) -> Vec<(T, Pauli)> {
+    let mut jw_map = JordanWigner::with_ordering(repr, ordering);
+    let mut pauli_repr = SumRepr::new();
+    jw_map.add_to(&mut pauli_repr).unwrap();
+    let mut result = vec![];
+    pauli_repr.add_to(&mut result).unwrap();
+    result.sort_by_key(|(_, pauli)| *pauli);
+    result
+}
+
+#[test]
+fn jw_block_ordering_01() {
+    use PauliOp::*;
+
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 2
+    };
+
+    // Orbitals with the same spin are neighbours in the block ordering
+    let repr = SumRepr::from([(2.0, Fermions::try_from((0, 2)).unwrap())]);
+    assert_eq!(
+        jw_get_result_ordered(&repr, ordering),
+        &[
+            (1.0, Pauli::with_ops([X, X])),
+            (1.0, Pauli::with_ops([Y, Y])),
+        ]
+    );
+
+    let repr = SumRepr::from([(2.0, Fermions::try_from((1, 3)).unwrap())]);
+    assert_eq!(
+        jw_get_result_ordered(&repr, ordering),
+        &[
+            (1.0, Pauli::with_ops([I, I, X, X])),
+            (1.0, Pauli::with_ops([I, I, Y, Y])),
+        ]
+    );
+}
+
+#[test]
+fn jw_block_ordering_02() {
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 3
+    };
+    let repr = SumRepr::from([
+        (0.5, Fermions::Offset),
+        (1.0, Fermions::try_from((0, 3)).unwrap()),
+        (2.0, Fermions::try_from((1, 4)).unwrap()),
+        (3.0, Fermions::try_from((0, 1, 5, 2)).unwrap()),
+        (4.0, Fermions::try_from((1, 2, 4, 1)).unwrap()),
+        (5.0, Fermions::try_from((0, 5, 3, 0)).unwrap()),
+    ]);
+
+    // Placing orbitals on qubits in the block ordering is the same as
+    // relabeling orbitals and using the interleaved ordering.
+    let relabeled = repr
+        .relabel(|orb| Orbital::with_index(orb.index_in(ordering)))
+        .unwrap();
+
+    assert_eq!(
+        jw_get_result_ordered(&repr, ordering),
+        jw_get_result(&relabeled)
+    );
+}
+
+#[test]
+fn jw_block_ordering_03() {
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 2
+    };
+    let repr = SumRepr::from([(1.0, Fermions::try_from((0, 4)).unwrap())]);
+
+    let mut pauli_repr = SumRepr::new();
+    JordanWigner::with_ordering(&repr, ordering)
+        .add_to(&mut pauli_repr)
+        .unwrap_err();
+}
+
+#[test]
+fn jw_term_matches_jw() {
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 3
+    };
+    let repr = SumRepr::from([
+        (0.5, Fermions::Offset),
+        (1.0, Fermions::try_from((0, 0)).unwrap()),
+        (2.0, Fermions::try_from((1, 4)).unwrap()),
+        (3.0, Fermions::try_from((0, 5, 3, 2)).unwrap()),
+    ]);
+
+    let mut expected = SumRepr::new();
+    JordanWigner::with_ordering(&repr, ordering)
+        .add_to(&mut expected)
+        .unwrap();
+    let mut pauli_repr = SumRepr::new();
+    for (&coeff, &code) in repr.iter() {
+        jordan_wigner_term(coeff, code, ordering, &mut pauli_repr).unwrap();
+    }
+
+    assert_eq!(pauli_repr.len(), expected.len());
+    for (coeff, &code) in expected.iter() {
+        assert_eq!(pauli_repr.coeff(code), Some(coeff));
+    }
+}
+
+#[test]
+fn jw_term_invalid() {
+    let mut pauli_repr = SumRepr::new();
+    // Orbital outside of the register
+    let code = Fermions::try_from((0, 64)).unwrap();
+    let err = jordan_wigner_term(
+        1.0,
+        code,
+        OrbitalOrdering::Interleaved,
+        &mut pauli_repr,
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, Error::QubitIndex { .. }));
+    assert!(pauli_repr.is_empty());
+}
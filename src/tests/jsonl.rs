@@ -0,0 +1,143 @@
+use super::fixtures::{
+    assert_sums_eq,
+    random_fermi_sum,
+    random_pauli_sum,
+};
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::{
+            Pauli,
+            PauliOp,
+        },
+    },
+    io::jsonl::{
+        read_sum,
+        write_sum,
+        Reader,
+        Writer,
+    },
+    terms::{
+        FermiSum,
+        PauliSum,
+        SumRepr,
+    },
+    Error,
+};
+
+#[test]
+fn jsonl_roundtrip_fermions() {
+    let repr = random_fermi_sum(48, 100, 16);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf).unwrap();
+    let other: FermiSum = read_sum(buf.as_slice()).unwrap();
+
+    assert_eq!(buf.iter().filter(|&&b| b == b'\n').count(), repr.len() + 1);
+    assert_eq!(other.len(), repr.len());
+    assert_sums_eq(&repr, &other, 1e-15);
+}
+
+#[test]
+fn jsonl_roundtrip_pauli() {
+    let repr = random_pauli_sum(48, 100);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf).unwrap();
+    let other: PauliSum = read_sum(buf.as_slice()).unwrap();
+
+    assert_eq!(other.len(), repr.len());
+    assert_sums_eq(&repr, &other, 1e-15);
+}
+
+#[test]
+fn jsonl_header_optional() {
+    let data = "{\"code\":[0,0],\"value\":1.0}\n{\"code\":[],\"value\":2.0}";
+    let repr: FermiSum = read_sum(data.as_bytes()).unwrap();
+    assert_eq!(repr.len(), 2);
+    assert_eq!(repr.coeff(Fermions::Offset), Some(&2.0));
+
+    let data = "\n\n{\"type\":\"sumrepr\",\"encoding\":\"fermions\"}\n\n";
+    let repr: FermiSum = read_sum(data.as_bytes()).unwrap();
+    assert!(repr.is_empty());
+
+    let repr: FermiSum = read_sum("".as_bytes()).unwrap();
+    assert!(repr.is_empty());
+}
+
+#[test]
+fn jsonl_header_invalid() {
+    let data = "{\"type\":\"sumrepr\",\"encoding\":\"qubits\"}\n";
+    assert!(read_sum::<f64, Fermions, _>(data.as_bytes()).is_err());
+    assert!(read_sum::<f64, Pauli, _>(data.as_bytes()).is_ok());
+
+    let data = "{\"type\":\"other\",\"encoding\":\"qubits\"}\n";
+    assert!(read_sum::<f64, Pauli, _>(data.as_bytes()).is_err());
+
+    // Header only allowed on the first line
+    let data = "{\"code\":\"X\",\"value\":1.0}\n{\"type\":\"sumrepr\",\"\
+                encoding\":\"qubits\"}\n";
+    assert!(read_sum::<f64, Pauli, _>(data.as_bytes()).is_err());
+}
+
+#[test]
+fn jsonl_error_line_number() {
+    let data = "{\"type\":\"sumrepr\",\"encoding\":\"fermions\"}\n{\"code\":\
+                [0,0],\"value\":1.0}\n\n{\"code\":[1,0],\"value\":1.0}\n{\"\
+                code\":[0,0],\"value\":1.0}\n";
+    let mut reader = Reader::<_, f64, Fermions>::new(data.as_bytes());
+
+    assert!(reader.next().unwrap().is_ok());
+    match reader.next().unwrap() {
+        Err(Error::Parse {
+            msg,
+        }) => assert!(msg.contains("line 4"), "{msg}"),
+        other => panic!("expected parse error, found: {other:?}"),
+    }
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn jsonl_concatenate() {
+    let code_x = Pauli::with_ops([PauliOp::X]);
+    let code_z = Pauli::with_ops([PauliOp::I, PauliOp::Z]);
+
+    let mut writer = Writer::with_header(Vec::new()).unwrap();
+    writer.write_term(1.0, &code_x).unwrap();
+    let mut buf = writer.finish().unwrap();
+
+    // Append terms without a header
+    let mut writer = Writer::new(&mut buf);
+    writer.write_term(0.5, &code_z).unwrap();
+    writer.write_term(0.5, &code_x).unwrap();
+    writer.finish().unwrap();
+
+    let repr: PauliSum = read_sum(buf.as_slice()).unwrap();
+    assert_eq!(repr.len(), 2);
+    assert_eq!(repr.coeff(code_x), Some(&1.5));
+    assert_eq!(repr.coeff(code_z), Some(&0.5));
+
+    let repr = SumRepr::from([(1.0, code_x)]);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf).unwrap();
+    let text = String::from_utf8(buf).unwrap();
+    assert_eq!(
+        text,
+        "{\"type\":\"sumrepr\",\"encoding\":\"qubits\"}\n{\"code\":\"X\",\"\
+         value\":1.0}\n"
+    );
+}
+
+#[test]
+fn jsonl_canonical_order() {
+    let repr = SumRepr::from([
+        (1.0, Fermions::try_from((0, 1)).unwrap()),
+        (2.0, Fermions::Offset),
+    ]);
+    let mut buf = Vec::new();
+    write_sum(&repr, &mut buf).unwrap();
+    let text = String::from_utf8(buf).unwrap();
+    assert_eq!(
+        text,
+        "{\"type\":\"sumrepr\",\"encoding\":\"fermions\"}\n{\"code\":[],\"\
+         value\":2.0}\n{\"code\":[0,1],\"value\":1.0}\n"
+    );
+}
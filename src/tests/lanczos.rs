@@ -0,0 +1,304 @@
+use num::complex::Complex;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use crate::{
+    code::{
+        fermions::{
+            An,
+            Cr,
+            Fermions,
+            Orbital,
+        },
+        qubits::{
+            Pauli,
+            PauliOp,
+        },
+    },
+    lanczos::{
+        tridiagonal_eigen,
+        Lanczos,
+        MAX_QUBITS,
+    },
+    sparse::CsrMatrix,
+    terms::SumRepr,
+    Error,
+};
+
+fn heisenberg_chain(num_qubits: u16) -> SumRepr<f64, Pauli> {
+    let mut repr = SumRepr::new();
+    for i in 0..num_qubits - 1 {
+        for op in [X, Y, Z] {
+            let mut code = Pauli::identity();
+            code.set(i, op);
+            code.set(i + 1, op);
+            repr.add_term(code, 1.0);
+        }
+    }
+    repr
+}
+
+fn residual(
+    repr: &SumRepr<f64, Pauli>,
+    num_qubits: u16,
+    value: f64,
+    vector: &[Complex<f64>],
+) -> f64 {
+    let matrix = CsrMatrix::from_sumrepr(repr, num_qubits).unwrap();
+    (0..matrix.dim())
+        .map(|r| {
+            let range = matrix.indptr()[r]..matrix.indptr()[r + 1];
+            let hv = matrix.indices()[range.clone()]
+                .iter()
+                .zip(&matrix.values()[range])
+                .fold(Complex::new(0., 0.), |acc, (&c, &m)| {
+                    acc + m * vector[c]
+                });
+            (hv - vector[r] * value).norm_sqr()
+        })
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[test]
+fn tridiagonal_eigen_01() {
+    let (values, vectors) = tridiagonal_eigen(&[2.0], &[0.0]).unwrap();
+    assert_eq!(values, [2.0]);
+    assert_eq!(vectors, [[1.0]]);
+}
+
+#[test]
+fn tridiagonal_eigen_02() {
+    // [[0, 1], [1, 0]]
+    let (values, vectors) =
+        tridiagonal_eigen(&[0.0, 0.0], &[1.0, 0.0]).unwrap();
+    assert!((values[0] + 1.0).abs() < 1e-14);
+    assert!((values[1] - 1.0).abs() < 1e-14);
+    assert!((vectors[0][0] + vectors[0][1]).abs() < 1e-14);
+    assert!((vectors[1][0] - vectors[1][1]).abs() < 1e-14);
+}
+
+#[test]
+fn tridiagonal_eigen_03() {
+    // Discrete Laplacian: eigenvalues 2 - 2 cos(k pi / (n + 1))
+    let n = 20;
+    let (values, vectors) =
+        tridiagonal_eigen(&vec![2.0; n], &vec![-1.0; n]).unwrap();
+    for (k, value) in values.iter().enumerate() {
+        let expected = 2.0
+            - 2.0
+                * ((k + 1) as f64 * std::f64::consts::PI / (n + 1) as f64)
+                    .cos();
+        assert!((value - expected).abs() < 1e-12, "{value} {expected}");
+    }
+    for v in &vectors {
+        let norm: f64 = v.iter().map(|x| x * x).sum();
+        assert!((norm - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn tridiagonal_eigen_not_finite() {
+    let err = tridiagonal_eigen(&[f64::NAN, 0.0], &[1.0, 0.0]).unwrap_err();
+    assert!(matches!(err, Error::Eigensolver { .. }));
+}
+
+#[test]
+fn single_qubit() {
+    let repr = SumRepr::from([
+        (3.0, Pauli::with_ops([X])),
+        (4.0, Pauli::with_ops([Z])),
+        (0.5, Pauli::identity()),
+    ]);
+    let solver = Lanczos {
+        num_eigenvalues: 2,
+        ..Default::default()
+    };
+    let eigen = solver.solve(&repr, 1).unwrap();
+
+    assert!((eigen.values[0] + 4.5).abs() < 1e-10);
+    assert!((eigen.values[1] - 5.5).abs() < 1e-10);
+    assert!(eigen.vectors.is_empty());
+    assert!(eigen.basis.is_none());
+}
+
+#[test]
+fn heisenberg_two_qubits() {
+    let repr = heisenberg_chain(2);
+    let solver = Lanczos {
+        num_eigenvalues: 4,
+        eigenvectors: true,
+        ..Default::default()
+    };
+    let eigen = solver.solve(&repr, 2).unwrap();
+
+    assert!((eigen.values[0] + 3.0).abs() < 1e-10);
+    for value in &eigen.values[1..] {
+        assert!((value - 1.0).abs() < 1e-10);
+    }
+    // Singlet: (|01> - |10>) / sqrt(2)
+    let singlet = &eigen.vectors[0];
+    assert!(singlet[0].norm() < 1e-8);
+    assert!(singlet[3].norm() < 1e-8);
+    assert!((singlet[1] + singlet[2]).norm() < 1e-8);
+    assert!((singlet[1].norm() - 0.5_f64.sqrt()).abs() < 1e-8);
+}
+
+#[test]
+fn heisenberg_chain_eigenvectors() {
+    // Open chain of 8 sites: E_0 = -13.4997...
+    let num_qubits = 8;
+    let repr = heisenberg_chain(num_qubits);
+    let solver = Lanczos {
+        num_eigenvalues: 3,
+        eigenvectors: true,
+        ..Default::default()
+    };
+    let eigen = solver.solve(&repr, num_qubits).unwrap();
+
+    assert!((eigen.values[0] + 13.499_730_394_751_566).abs() < 1e-8);
+    assert!(eigen.values.windows(2).all(|w| w[0] <= w[1] + 1e-10));
+    for (value, vector) in eigen.values.iter().zip(&eigen.vectors) {
+        assert!(residual(&repr, num_qubits, *value, vector) < 1e-6);
+    }
+}
+
+#[test]
+fn heisenberg_chain_small_basis() {
+    let num_qubits = 8;
+    let repr = heisenberg_chain(num_qubits);
+    let full = Lanczos {
+        num_eigenvalues: 2,
+        basis_size: 256,
+        ..Default::default()
+    }
+    .solve(&repr, num_qubits)
+    .unwrap();
+
+    for basis_size in [2, 5] {
+        let solver = Lanczos {
+            num_eigenvalues: 2,
+            max_iterations: 5000,
+            basis_size,
+            eigenvectors: true,
+            ..Default::default()
+        };
+        let eigen = solver.solve(&repr, num_qubits).unwrap();
+        for (value, expected) in eigen.values.iter().zip(&full.values) {
+            assert!((value - expected).abs() < 1e-8);
+        }
+        for (value, vector) in eigen.values.iter().zip(&eigen.vectors) {
+            assert!(residual(&repr, num_qubits, *value, vector) < 1e-6);
+        }
+    }
+}
+
+#[test]
+fn random_hamiltonian_residual() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let num_qubits = 5;
+    let ops = [I, X, Y, Z];
+    let mut repr = SumRepr::new();
+    for _ in 0..30 {
+        let code =
+            Pauli::with_ops((0..num_qubits).map(|_| ops[rng.gen_range(0..4)]));
+        repr.add_term(code, rng.gen_range(-1.0..1.0));
+    }
+    let solver = Lanczos {
+        eigenvectors: true,
+        ..Default::default()
+    };
+    let eigen = solver.solve(&repr, num_qubits).unwrap();
+
+    let l1: f64 = repr.iter().map(|(c, _)| f64::abs(*c)).sum();
+    assert!(eigen.values[0] >= -l1);
+    assert!(
+        residual(&repr, num_qubits, eigen.values[0], &eigen.vectors[0]) < 1e-6
+    );
+}
+
+#[test]
+fn register_too_small() {
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([I, I, Z]))]);
+    let err = Lanczos::default().solve(&repr, 2).unwrap_err();
+    assert!(matches!(err, Error::QubitIndex { .. }));
+}
+
+#[test]
+fn register_too_large() {
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([Z]))]);
+    let err = Lanczos::default().solve(&repr, MAX_QUBITS + 1).unwrap_err();
+    assert!(matches!(err, Error::QubitIndex { .. }));
+}
+
+#[test]
+fn too_many_eigenvalues() {
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([Z]))]);
+    let solver = Lanczos {
+        num_eigenvalues: 3,
+        ..Default::default()
+    };
+    let err = solver.solve(&repr, 1).unwrap_err();
+    assert!(matches!(err, Error::Eigensolver { .. }));
+}
+
+#[test]
+fn fermions_hopping_ring() {
+    // Tight-binding ring of 6 sites with 2 electrons:
+    // single-particle energies -2 cos(2 pi k / 6) = -2, -1, -1, 1, 1, 2
+    let num_sites = 6;
+    let mut repr = SumRepr::new();
+    for i in 0..num_sites {
+        let j = (i + 1) % num_sites;
+        let code = Fermions::one_electron(
+            Cr(Orbital::with_index(i.min(j))),
+            An(Orbital::with_index(i.max(j))),
+        )
+        .unwrap();
+        repr.add_term(code, -1.0);
+    }
+    let solver = Lanczos {
+        num_eigenvalues: 2,
+        eigenvectors: true,
+        ..Default::default()
+    };
+    let eigen = solver.solve_fermions(&repr, 6, 2).unwrap();
+
+    assert!((eigen.values[0] + 3.0).abs() < 1e-10);
+    assert!((eigen.values[1] + 3.0).abs() < 1e-10);
+    let basis = eigen.basis.unwrap();
+    assert_eq!(basis.len(), 15);
+    assert!(basis.iter().all(|b| b.count_ones() == 2));
+    assert!(basis.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(eigen.vectors[0].len(), 15);
+}
+
+#[test]
+fn fermions_vacuum() {
+    let code = Fermions::one_electron(
+        Cr(Orbital::with_index(0)),
+        An(Orbital::with_index(0)),
+    )
+    .unwrap();
+    let repr = SumRepr::from([(2.0, code), (-1.0, Fermions::Offset)]);
+    let eigen = Lanczos::default().solve_fermions(&repr, 3, 0).unwrap();
+
+    assert!((eigen.values[0] + 1.0).abs() < 1e-12);
+    assert_eq!(eigen.basis.unwrap(), [0]);
+}
+
+#[test]
+fn fermions_too_many_electrons() {
+    let repr = SumRepr::from([(1.0, Fermions::Offset)]);
+    let err = Lanczos::default().solve_fermions(&repr, 2, 3).unwrap_err();
+    assert!(matches!(err, Error::Eigensolver { .. }));
+}
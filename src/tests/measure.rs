@@ -0,0 +1,347 @@
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use crate::{
+    circuit::{
+        Circuit,
+        Gate,
+        QasmVersion,
+    },
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    grouping::Compatibility,
+    math::Root4,
+    measure::{
+        basis_rotation,
+        ShotAllocation,
+        VarianceBound,
+    },
+    terms::{
+        PauliSum,
+        SumRepr,
+    },
+    Error,
+};
+
+fn is_diagonal(code: &Pauli) -> bool {
+    code.into_iter().all(|op| op == I || op == Z)
+}
+
+// Check that the basis rotation preserves products of terms.
+fn check_diagonal(
+    group: &PauliSum,
+    compatibility: Compatibility,
+) {
+    let m = basis_rotation(group, compatibility).unwrap();
+    assert_eq!(m.diagonal.len(), group.len());
+
+    let mut images = Vec::new();
+    for (&coeff, &code) in group.iter() {
+        let (omega, diag) = m.circuit.conjugate(code);
+        assert!(is_diagonal(&diag));
+        let sign = if omega == Root4::R0 { 1.0 } else { -1.0 };
+        assert_eq!(m.diagonal.coeff(diag), Some(&(sign * coeff)));
+        images.push((code, sign, diag));
+    }
+
+    for &(a, sign_a, diag_a) in &images {
+        for &(b, sign_b, diag_b) in &images {
+            let (omega, c) = a * b;
+            let Some(&(_, sign_c, diag_c)) =
+                images.iter().find(|(code, ..)| *code == c)
+            else {
+                continue;
+            };
+            // a * b = omega * c, hence the images satisfy the same relation.
+            assert_eq!(omega == Root4::R1, sign_a * sign_b != sign_c);
+            assert_eq!((diag_a * diag_b).1, diag_c);
+        }
+    }
+}
+
+#[test]
+fn qubitwise_01() {
+    let group = SumRepr::from([
+        (1.0, Pauli::with_ops([X, I, Y])),
+        (2.0, Pauli::with_ops([X, Z, I])),
+        (3.0, Pauli::with_ops([I, Z, Y])),
+    ]);
+
+    let m = basis_rotation(&group, Compatibility::QubitWise).unwrap();
+    assert_eq!(m.circuit.gates(), &[Gate::H(0), Gate::Sdg(2), Gate::H(2)]);
+    assert_eq!(m.diagonal.coeff(Pauli::with_ops([Z, I, Z])), Some(&1.0));
+    assert_eq!(m.diagonal.coeff(Pauli::with_ops([Z, Z, I])), Some(&2.0));
+    assert_eq!(m.diagonal.coeff(Pauli::with_ops([I, Z, Z])), Some(&3.0));
+
+    check_diagonal(&group, Compatibility::QubitWise);
+}
+
+#[test]
+fn qubitwise_02() {
+    let group = SumRepr::from([
+        (1.0, Pauli::with_ops([X])),
+        (2.0, Pauli::with_ops([Y])),
+    ]);
+
+    let err = basis_rotation(&group, Compatibility::QubitWise).unwrap_err();
+    assert!(matches!(err, Error::NonCommuting { .. }));
+}
+
+#[test]
+fn qubitwise_identity() {
+    let group = SumRepr::from([(1.5, Pauli::identity())]);
+
+    let m = basis_rotation(&group, Compatibility::QubitWise).unwrap();
+    assert!(m.circuit.is_empty());
+    assert_eq!(m.diagonal.coeff(Pauli::identity()), Some(&1.5));
+}
+
+#[test]
+fn fully_commuting_01() {
+    let group = SumRepr::from([
+        (1.0, Pauli::with_ops([X, X])),
+        (2.0, Pauli::with_ops([Y, Y])),
+        (3.0, Pauli::with_ops([Z, Z])),
+    ]);
+
+    let m = basis_rotation(&group, Compatibility::FullyCommuting).unwrap();
+    assert!(m.diagonal.iter().all(|(_, code)| is_diagonal(code)));
+    check_diagonal(&group, Compatibility::FullyCommuting);
+}
+
+#[test]
+fn fully_commuting_02() {
+    let group = SumRepr::from([
+        (1.0, Pauli::with_ops([X, Z])),
+        (2.0, Pauli::with_ops([Z, Z])),
+    ]);
+
+    let err =
+        basis_rotation(&group, Compatibility::FullyCommuting).unwrap_err();
+    assert!(matches!(err, Error::NonCommuting { .. }));
+}
+
+#[test]
+fn fully_commuting_random() {
+    let mut rng = StdRng::seed_from_u64(31);
+    for _ in 0..50 {
+        let num_qubits = rng.gen_range(1..12_u16);
+
+        // Random Clifford circuit maps Z strings into a commuting group.
+        let mut clifford = Circuit::new(num_qubits);
+        for _ in 0..40 {
+            let a = rng.gen_range(0..num_qubits);
+            let b = rng.gen_range(0..num_qubits);
+            let gate = match rng.gen_range(0..4) {
+                0 => Gate::H(a),
+                1 => Gate::S(a),
+                2 if a != b => Gate::Cnot(a, b),
+                3 if a != b => Gate::Cz(a, b),
+                _ => Gate::Sdg(a),
+            };
+            clifford.push(gate).unwrap();
+        }
+
+        let mut group = PauliSum::new();
+        for _ in 0..rng.gen_range(1..20) {
+            let mut code = Pauli::identity();
+            for i in 0..num_qubits {
+                if rng.gen_bool(0.5) {
+                    code.set(i, Z);
+                }
+            }
+            let (_, code) = clifford.conjugate(code);
+            group.update(code, rng.gen_range(-1.0..1.0));
+        }
+
+        check_diagonal(&group, Compatibility::FullyCommuting);
+    }
+}
+
+#[test]
+fn measurement_to_qasm() {
+    let group = SumRepr::from([(1.0, Pauli::with_ops([I, Y]))]);
+
+    let m = basis_rotation(&group, Compatibility::QubitWise).unwrap();
+    assert_eq!(
+        m.to_qasm(QasmVersion::V2),
+        "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[2];\ncreg c[2];\nsdg \
+         q[1];\nh q[1];\nmeasure q -> c;\n"
+    );
+}
+
+fn sample_groups() -> Vec<PauliSum> {
+    vec![
+        SumRepr::from([
+            (0.5, Pauli::with_ops([Z, Z])),
+            (-1.5, Pauli::with_ops([Z, I])),
+            (7.0, Pauli::identity()),
+        ]),
+        SumRepr::from([(1.0, Pauli::with_ops([X, X]))]),
+        SumRepr::from([
+            (-0.25, Pauli::with_ops([Y, Y])),
+            (0.25, Pauli::with_ops([X, I])),
+        ]),
+        SumRepr::from([(3.0, Pauli::identity())]),
+    ]
+}
+
+#[test]
+fn variance_bound_01() {
+    let groups = sample_groups();
+
+    let std_devs: Vec<_> = groups
+        .iter()
+        .map(|g| VarianceBound::WorstCase.std_dev(g))
+        .collect();
+    assert_eq!(std_devs, [2.0, 1.0, 0.5, 0.0]);
+
+    let std_devs: Vec<_> = groups
+        .iter()
+        .map(|g| VarianceBound::Uncorrelated.std_dev(g))
+        .collect();
+    assert_eq!(std_devs, [2.5_f64.sqrt(), 1.0, 0.125_f64.sqrt(), 0.0]);
+}
+
+#[test]
+fn shot_allocation_total_01() {
+    let groups = sample_groups();
+
+    let alloc =
+        ShotAllocation::with_total(&groups, VarianceBound::WorstCase, 350)
+            .unwrap();
+    assert_eq!(alloc.shots, [200, 100, 50, 0]);
+    assert_eq!(alloc.total(), 350);
+    // (2 + 1 + 0.5)^2 / 350
+    assert!((alloc.std_dev() - (3.5_f64 * 3.5 / 350.).sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn shot_allocation_total_02() {
+    let groups = sample_groups();
+
+    for total in [0, 1, 2, 3, 10, 101, 999] {
+        let alloc = ShotAllocation::with_total(
+            &groups,
+            VarianceBound::Uncorrelated,
+            total,
+        )
+        .unwrap();
+        assert_eq!(alloc.total(), total);
+        assert_eq!(alloc.shots[3], 0);
+    }
+}
+
+#[test]
+fn shot_allocation_total_zero_variance() {
+    let groups = vec![SumRepr::from([(1.0, Pauli::identity())])];
+
+    let alloc =
+        ShotAllocation::with_total(&groups, VarianceBound::WorstCase, 100)
+            .unwrap();
+    assert_eq!(alloc.shots, [0]);
+    assert_eq!(alloc.std_dev(), 0.0);
+}
+
+#[test]
+fn shot_allocation_total_not_enough_shots() {
+    let groups = sample_groups();
+
+    let alloc =
+        ShotAllocation::with_total(&groups, VarianceBound::WorstCase, 1)
+            .unwrap();
+    assert_eq!(alloc.shots, [1, 0, 0, 0]);
+    assert_eq!(alloc.std_dev(), f64::INFINITY);
+}
+
+#[test]
+fn shot_allocation_precision_01() {
+    let groups = sample_groups();
+
+    let alloc =
+        ShotAllocation::with_precision(&groups, VarianceBound::WorstCase, 0.01)
+            .unwrap();
+    // N_g = sigma_g * (sum sigma) / eps^2
+    assert_eq!(alloc.shots, [70_000, 35_000, 17_500, 0]);
+    assert!(alloc.std_dev() <= 0.01 + 1e-12);
+}
+
+#[test]
+fn shot_allocation_precision_02() {
+    let groups = sample_groups();
+
+    for precision in [0.3, 0.1, 0.0123, 0.001] {
+        let alloc = ShotAllocation::with_precision(
+            &groups,
+            VarianceBound::Uncorrelated,
+            precision,
+        )
+        .unwrap();
+        assert!(alloc.std_dev() <= precision);
+
+        // Optimal allocation beats the uniform one with the same total.
+        let uniform = ShotAllocation {
+            shots:    vec![alloc.total() / 3; 3],
+            std_devs: alloc.std_devs[..3].to_vec(),
+        };
+        assert!(alloc.std_dev() <= uniform.std_dev());
+    }
+}
+
+#[test]
+fn shot_allocation_precision_not_positive() {
+    for precision in [0.0, -0.1, f64::NAN] {
+        assert!(matches!(
+            ShotAllocation::with_precision(
+                &sample_groups(),
+                VarianceBound::WorstCase,
+                precision
+            ),
+            Err(Error::Coefficient { .. })
+        ));
+    }
+}
+
+#[test]
+fn shot_allocation_not_finite() {
+    for coeff in [f64::NAN, f64::INFINITY] {
+        let mut groups = sample_groups();
+        groups[1].add_term(Pauli::with_ops([X]), coeff);
+
+        assert!(matches!(
+            ShotAllocation::with_total(&groups, VarianceBound::WorstCase, 100),
+            Err(Error::Coefficient { .. })
+        ));
+        assert!(matches!(
+            ShotAllocation::with_precision(
+                &groups,
+                VarianceBound::Uncorrelated,
+                0.1
+            ),
+            Err(Error::Coefficient { .. })
+        ));
+    }
+}
+
+#[test]
+fn shot_allocation_too_many_shots() {
+    assert!(matches!(
+        ShotAllocation::with_precision(
+            &sample_groups(),
+            VarianceBound::WorstCase,
+            1e-10
+        ),
+        Err(Error::Coefficient { .. })
+    ));
+}
@@ -0,0 +1,91 @@
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use crate::{
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    metrics::Metrics,
+    terms::{
+        PauliSum,
+        SumRepr,
+    },
+};
+
+#[test]
+fn metrics_empty() {
+    let metrics = Metrics::new(&PauliSum::new());
+
+    assert_eq!(metrics.num_terms, 0);
+    assert_eq!(metrics.num_qubits, 0);
+    assert_eq!(metrics.locality, 0);
+    assert_eq!(metrics.identity, 0.0);
+    assert_eq!(metrics.l1_norm, 0.0);
+    assert_eq!(metrics.l2_norm, 0.0);
+    assert_eq!(metrics.max_coeff, 0.0);
+    assert_eq!(metrics.weight_counts, [0]);
+    assert_eq!(metrics.weight_l1, [0.0]);
+}
+
+#[test]
+fn metrics_identity_only() {
+    let repr = SumRepr::from([(-1.5, Pauli::identity())]);
+    let metrics = Metrics::new(&repr);
+
+    assert_eq!(metrics.num_terms, 1);
+    assert_eq!(metrics.identity, -1.5);
+    assert_eq!(metrics.l1_norm, 0.0);
+    assert_eq!(metrics.weight_counts, [1]);
+}
+
+#[test]
+fn metrics_01() {
+    let repr = SumRepr::from([
+        (1.0, Pauli::with_ops([Z])),
+        (-2.0, Pauli::with_ops([I, Z])),
+        (0.5, Pauli::with_ops([X, Y, I, Z])),
+        (-0.5, Pauli::with_ops([Y, I, I, I, I, X])),
+        (0.25, Pauli::with_ops([X, X, X])),
+    ]);
+    let metrics = Metrics::new(&repr);
+
+    assert_eq!(metrics.num_terms, 5);
+    assert_eq!(metrics.num_qubits, 6);
+    assert_eq!(metrics.locality, 3);
+    assert_eq!(metrics.identity, 0.0);
+    assert_eq!(metrics.l1_norm, 4.25);
+    assert_eq!(
+        metrics.l2_norm,
+        (1.0_f64 + 4.0 + 0.25 + 0.25 + 0.0625).sqrt()
+    );
+    assert_eq!(metrics.max_coeff, 2.0);
+    assert_eq!(metrics.weight_counts, [0, 2, 1, 2]);
+    assert_eq!(metrics.weight_l1, [0.0, 3.0, 0.5, 0.75]);
+}
+
+#[test]
+fn metrics_weight_counts_sum() {
+    let mut repr = PauliSum::new();
+    for i in 0..300_u64 {
+        let pack = (i * 2_654_435_761) % (1 << 20);
+        repr.add_term(Pauli::new((pack, i)), 1.0);
+    }
+    let metrics = Metrics::new(&repr);
+
+    assert_eq!(metrics.weight_counts.iter().sum::<usize>(), repr.len());
+    assert_eq!(
+        metrics.num_qubits,
+        repr.iter()
+            .map(|(_, code)| code.min_register_size())
+            .max()
+            .unwrap()
+    );
+    assert!(
+        (metrics.weight_l1.iter().sum::<f64>() - metrics.l1_norm).abs() < 1e-12
+    );
+}
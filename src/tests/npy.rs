@@ -0,0 +1,163 @@
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::Pauli,
+    },
+    io::npy::{
+        write_integrals,
+        write_pauli_table,
+        MAGIC,
+        MAX_SPIN_ORBITALS,
+    },
+    terms::{
+        FermiSum,
+        PauliSum,
+    },
+};
+
+// Split the buffer into consecutive arrays: (header, data).
+fn split_arrays(mut buf: &[u8]) -> Vec<(String, Vec<f64>)> {
+    let mut arrays = Vec::new();
+    while !buf.is_empty() {
+        assert_eq!(buf[..6], MAGIC);
+        assert_eq!(buf[6..8], [1, 0]);
+        let header_len = usize::from(u16::from_le_bytes([buf[8], buf[9]]));
+        assert_eq!((10 + header_len) % 64, 0);
+        let header =
+            String::from_utf8(buf[10..10 + header_len].to_vec()).unwrap();
+        assert!(header.ends_with('\n'));
+        buf = &buf[10 + header_len..];
+
+        let shape = header
+            .split("'shape': (")
+            .nth(1)
+            .unwrap()
+            .split(')')
+            .next()
+            .unwrap();
+        let len = shape
+            .split(',')
+            .map(str::trim)
+            .filter(|dim| !dim.is_empty())
+            .map(|dim| dim.parse::<usize>().unwrap())
+            .product::<usize>();
+        let data = buf[..8 * len]
+            .chunks_exact(8)
+            .map(|bytes| f64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        buf = &buf[8 * len..];
+        arrays.push((header, data));
+    }
+
+    arrays
+}
+
+#[test]
+fn npy_integrals_empty() {
+    let mut buf = Vec::new();
+    write_integrals(&FermiSum::new(), &mut buf).unwrap();
+    let arrays = split_arrays(&buf);
+
+    assert_eq!(arrays.len(), 3);
+    assert!(arrays[0].0.contains("'shape': ()"));
+    assert_eq!(arrays[0].1, [0.]);
+    assert!(arrays[1].0.contains("'shape': (0, 0)"));
+    assert!(arrays[2].0.contains("'shape': (0, 0, 0, 0)"));
+}
+
+#[test]
+fn npy_integrals_one_body() {
+    let repr = FermiSum::from([
+        (1.5, Fermions::Offset),
+        (0.5, Fermions::try_from((0, 2)).unwrap()),
+        (0.25, Fermions::try_from((1, 1)).unwrap()),
+    ]);
+    let mut buf = Vec::new();
+    write_integrals(&repr, &mut buf).unwrap();
+    let arrays = split_arrays(&buf);
+
+    assert_eq!(arrays[0].1, [1.5]);
+    assert!(arrays[1].0.contains("'descr': '<f8'"));
+    assert!(arrays[1].0.contains("'fortran_order': False"));
+    assert!(arrays[1].0.contains("'shape': (3, 3)"));
+    #[rustfmt::skip]
+    assert_eq!(
+        arrays[1].1,
+        [
+            0.,  0.,  0.5,
+            0.,  0.5, 0.,
+            0.5, 0.,  0.,
+        ]
+    );
+    assert!(arrays[2].1.iter().all(|&x| x == 0.));
+}
+
+#[test]
+fn npy_integrals_two_body() {
+    let repr = FermiSum::from([
+        (0.5, Fermions::try_from((0, 1, 3, 2)).unwrap()),
+        (0.25, Fermions::try_from((0, 1, 1, 0)).unwrap()),
+    ]);
+    let mut buf = Vec::new();
+    write_integrals(&repr, &mut buf).unwrap();
+    let arrays = split_arrays(&buf);
+
+    let n = 4;
+    let h2 = &arrays[2].1;
+    let at = |p: usize, q: usize, r: usize, s: usize| {
+        h2[((p * n + q) * n + r) * n + s]
+    };
+    assert!(arrays[2].0.contains("'shape': (4, 4, 4, 4)"));
+    assert_eq!(at(0, 1, 3, 2), 0.5);
+    assert_eq!(at(2, 3, 1, 0), 0.5);
+    // self-conjugate term
+    assert_eq!(at(0, 1, 1, 0), 0.5);
+    assert_eq!(h2.iter().filter(|&&x| x != 0.).count(), 3);
+    assert!(arrays[1].1.iter().all(|&x| x == 0.));
+}
+
+#[test]
+fn npy_integrals_too_large() {
+    let max = u32::try_from(MAX_SPIN_ORBITALS).unwrap();
+    for index in [max, 1 << 30] {
+        let repr =
+            FermiSum::from([(1.0, Fermions::try_from((0, index)).unwrap())]);
+        let mut buf = Vec::new();
+        let err = write_integrals(&repr, &mut buf).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(buf.is_empty());
+    }
+}
+
+#[test]
+fn npy_pauli_table() {
+    let repr = PauliSum::from([
+        (0.5, Pauli::new((3, 4))),
+        (-1.0, Pauli::identity()),
+        (0.25, Pauli::new((u64::MAX, 1))),
+    ]);
+    let mut buf = Vec::new();
+    write_pauli_table(&repr, &mut buf).unwrap();
+
+    let header_len = usize::from(u16::from_le_bytes([buf[8], buf[9]]));
+    let header = std::str::from_utf8(&buf[10..10 + header_len]).unwrap();
+    assert!(header
+        .contains("'descr': [('lo', '<u8'), ('hi', '<u8'), ('coeff', '<f8')]"));
+    assert!(header.contains("'shape': (3,)"));
+
+    let data = &buf[10 + header_len..];
+    assert_eq!(data.len(), 3 * 24);
+    let u64_at =
+        |i: usize| u64::from_le_bytes(data[i..i + 8].try_into().unwrap());
+    let mut codes = Vec::new();
+    for row in 0..3 {
+        let i = 24 * row;
+        let code = Pauli::new((u64_at(i), u64_at(i + 8)));
+        let coeff =
+            f64::from_le_bytes(data[i + 16..i + 24].try_into().unwrap());
+        assert_eq!(repr.coeff(code), Some(&coeff));
+        codes.push(code);
+    }
+    assert!(codes.windows(2).all(|w| w[0] < w[1]));
+}
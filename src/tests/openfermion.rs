@@ -0,0 +1,167 @@
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use super::fixtures::{
+    assert_sums_eq,
+    random_fermi_sum,
+    random_pauli_sum,
+};
+use crate::{
+    code::{
+        fermions::Fermions,
+        qubits::{
+            Pauli,
+            PauliOp,
+        },
+    },
+    io::openfermion::{
+        parse_fermion_operator,
+        parse_qubit_operator,
+        write_fermion_operator,
+        write_qubit_operator,
+    },
+    map::JordanWigner,
+    terms::{
+        FermiSum,
+        PauliSum,
+        SumRepr,
+        Terms,
+    },
+};
+
+fn jordan_wigner(repr: &FermiSum) -> PauliSum {
+    let mut qubit_repr = PauliSum::new();
+    JordanWigner::new(repr).add_to(&mut qubit_repr).unwrap();
+    qubit_repr
+}
+
+#[test]
+fn parse_fermion_operator_empty() {
+    for text in ["", "0", " \n"] {
+        let repr: FermiSum = parse_fermion_operator(text).unwrap();
+        assert!(repr.is_empty());
+    }
+}
+
+#[test]
+fn parse_fermion_operator_combines_conjugates() {
+    let repr: FermiSum = parse_fermion_operator(
+        "1.0 [] +\n0.5 [0^ 3] +\n0.5 [3^ 0] +\n-2.0 [2^ 2] +\n0.25 [0^ 1^ 3 2]",
+    )
+    .unwrap();
+
+    assert_eq!(repr.len(), 4);
+    assert_eq!(repr.coeff(Fermions::Offset), Some(&1.0));
+    assert_eq!(repr.coeff((0, 3).try_into().unwrap()), Some(&0.5));
+    assert_eq!(repr.coeff((2, 2).try_into().unwrap()), Some(&-1.0));
+    assert_eq!(repr.coeff((0, 1, 3, 2).try_into().unwrap()), Some(&0.125));
+}
+
+#[test]
+fn parse_fermion_operator_normal_order() {
+    // a_0 a_0^ = 1 - a_0^ a_0
+    let repr: FermiSum = parse_fermion_operator("2.0 [0 0^]").unwrap();
+    assert_eq!(repr.len(), 2);
+    assert_eq!(repr.coeff(Fermions::Offset), Some(&2.0));
+    assert_eq!(repr.coeff((0, 0).try_into().unwrap()), Some(&-1.0));
+
+    // a_1 a_0^ = - a_0^ a_1
+    let repr: FermiSum = parse_fermion_operator("1.0 [1 0^]").unwrap();
+    assert_eq!(repr.len(), 1);
+    assert_eq!(repr.coeff((0, 1).try_into().unwrap()), Some(&-0.5));
+
+    // a_0^ a_1^ a_2 a_3 = - a_1^ a_0^ a_2 a_3 = a_0^ a_1^ a_3 a_2
+    let repr: FermiSum = parse_fermion_operator("1.0 [1^ 0^ 2 3]").unwrap();
+    assert_eq!(repr.coeff((0, 1, 3, 2).try_into().unwrap()), Some(&0.5));
+
+    // a_0^ a_0^ = 0
+    let repr: FermiSum = parse_fermion_operator("1.0 [0^ 0^ 1 2]").unwrap();
+    assert!(repr.is_empty());
+}
+
+#[test]
+fn parse_fermion_operator_complex_coeff() {
+    let repr: FermiSum = parse_fermion_operator(
+        "(1.5+0j) [] +\n(-0.5-0j) [1^ 1] +\n(1e-05+0j) [0^ 0]",
+    )
+    .unwrap();
+    assert_eq!(repr.coeff(Fermions::Offset), Some(&1.5));
+    assert_eq!(repr.coeff((1, 1).try_into().unwrap()), Some(&-0.25));
+    assert_eq!(repr.coeff((0, 0).try_into().unwrap()), Some(&0.5e-05));
+
+    assert!(parse_fermion_operator::<f64>("(1.5+0.5j) []").is_err());
+    assert!(parse_fermion_operator::<f64>("1j []").is_err());
+}
+
+#[test]
+fn parse_fermion_operator_invalid() {
+    assert!(parse_fermion_operator::<f64>("1.0 [0^ 1").is_err());
+    assert!(parse_fermion_operator::<f64>("1.0 [0^ x]").is_err());
+    assert!(parse_fermion_operator::<f64>("abc [0^ 1]").is_err());
+    assert!(parse_fermion_operator::<f64>("1.0 [0^ 1] 2.0").is_err());
+    assert!(parse_fermion_operator::<f64>("1.0 [0^]").is_err());
+    assert!(parse_fermion_operator::<f64>("1.0 [0^ 1^ 2]").is_err());
+    assert!(parse_fermion_operator::<f64>("1.0 [0^ 1^ 2^ 3 4 5]").is_err());
+}
+
+#[test]
+fn write_fermion_operator_diagonal() {
+    let repr = SumRepr::from([
+        (0.5, Fermions::try_from((1, 1)).unwrap()),
+        (0.25, Fermions::try_from((0, 1, 1, 0)).unwrap()),
+    ]);
+    let mut buf = Vec::new();
+    write_fermion_operator(&repr, &mut buf).unwrap();
+    let text = String::from_utf8(buf).unwrap();
+
+    assert_eq!(text.lines().count(), 2);
+    assert!(text.contains("1 [1^ 1]"));
+    assert!(text.contains("0.5 [0^ 1^ 1 0]"));
+}
+
+#[test]
+fn fermion_operator_roundtrip() {
+    let repr = random_fermi_sum(44, 100, 8);
+
+    let mut buf = Vec::new();
+    write_fermion_operator(&repr, &mut buf).unwrap();
+    let parsed =
+        parse_fermion_operator(&String::from_utf8(buf).unwrap()).unwrap();
+
+    // Terms a_p^ a_q^ a_r a_p and a_p^ a_r^ a_q a_p are combined.
+    assert!(parsed.len() <= repr.len());
+    assert_sums_eq(&jordan_wigner(&repr), &jordan_wigner(&parsed), 1e-12);
+}
+
+#[test]
+fn parse_qubit_operator_terms() {
+    let repr: PauliSum = parse_qubit_operator(
+        "0.5 [] +\n(0.25+0j) [X0 Z1 Y3] +\n-1.0 [Z2] +\n1.0 [Z2]",
+    )
+    .unwrap();
+
+    assert_eq!(repr.len(), 3);
+    assert_eq!(repr.coeff(Pauli::identity()), Some(&0.5));
+    assert_eq!(repr.coeff(Pauli::with_ops([X, Z, I, Y])), Some(&0.25));
+    assert_eq!(repr.coeff(Pauli::with_ops([I, I, Z])), Some(&0.0));
+
+    assert!(parse_qubit_operator::<f64>("1.0 [X0 W1]").is_err());
+    assert!(parse_qubit_operator::<f64>("1.0 [X64]").is_err());
+    assert!(parse_qubit_operator::<f64>("(1.0-1.0j) [X0]").is_err());
+}
+
+#[test]
+fn qubit_operator_roundtrip() {
+    let repr = random_pauli_sum(44, 100);
+
+    let mut buf = Vec::new();
+    write_qubit_operator(&repr, &mut buf).unwrap();
+    let parsed =
+        parse_qubit_operator(&String::from_utf8(buf).unwrap()).unwrap();
+
+    assert_sums_eq(&repr, &parsed, 1e-12);
+}
@@ -0,0 +1,147 @@
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use super::fixtures::{
+    assert_sums_eq,
+    random_pauli_sum,
+};
+use crate::{
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    io::qiskit::{
+        from_label,
+        read_sparse_pauli_op,
+        to_label,
+        write_sparse_pauli_op,
+    },
+    terms::{
+        PauliSum,
+        SumRepr,
+    },
+};
+
+#[test]
+fn label_little_endian() {
+    let code = Pauli::with_ops([X, Y, Z]);
+
+    assert_eq!(to_label(&code, 3), "ZYX");
+    assert_eq!(to_label(&code, 5), "IIZYX");
+    assert_eq!(to_label(&code, 2), "YX");
+    assert_eq!(to_label(&Pauli::identity(), 1), "I");
+    assert_eq!(to_label(&Pauli::identity(), 0), "");
+
+    assert_eq!(from_label("ZYX").unwrap(), code);
+    assert_eq!(from_label("IIZYX").unwrap(), code);
+    assert_eq!(from_label("").unwrap(), Pauli::identity());
+}
+
+#[test]
+fn label_64_qubits() {
+    let mut code = Pauli::identity();
+    code.set(63, X);
+    code.set(0, Z);
+    let label = to_label(&code, 64);
+
+    assert_eq!(label.len(), 64);
+    assert!(label.starts_with("XI"));
+    assert!(label.ends_with("IZ"));
+    assert_eq!(from_label(&label).unwrap(), code);
+
+    assert!(from_label(&"I".repeat(65)).is_err());
+}
+
+#[test]
+fn label_invalid() {
+    assert!(from_label("XA").is_err());
+    assert!(from_label("x").is_err());
+    assert!(from_label("X0").is_err());
+}
+
+#[test]
+fn write_sparse_pauli_op_register_size() {
+    let repr = SumRepr::from([
+        (1.0, Pauli::identity()),
+        (0.5, Pauli::with_ops([I, I, Y])),
+        (0.25, Pauli::with_ops([X])),
+    ]);
+    let mut buf = Vec::new();
+    write_sparse_pauli_op(&repr, &mut buf).unwrap();
+    let mut list: Vec<(String, [f64; 2])> =
+        serde_json::from_slice(&buf).unwrap();
+    list.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        list,
+        [
+            ("III".to_string(), [1.0, 0.0]),
+            ("IIX".to_string(), [0.25, 0.0]),
+            ("YII".to_string(), [0.5, 0.0]),
+        ]
+    );
+
+    let repr = SumRepr::from([(2.0, Pauli::identity())]);
+    let mut buf = Vec::new();
+    write_sparse_pauli_op(&repr, &mut buf).unwrap();
+    assert_eq!(buf, br#"[["I",[2.0,0.0]]]"#);
+
+    let mut buf = Vec::new();
+    write_sparse_pauli_op(&PauliSum::new(), &mut buf).unwrap();
+    assert_eq!(buf, b"[]");
+}
+
+#[test]
+fn read_sparse_pauli_op_coeffs() {
+    let data = r#"[["IZ", 1.0], ["XI", [0.5, 0.0]], ["IZ", [0.25, 0]]]"#;
+    let repr: PauliSum = read_sparse_pauli_op(data.as_bytes()).unwrap();
+
+    assert_eq!(repr.len(), 2);
+    assert_eq!(repr.coeff(Pauli::with_ops([Z])), Some(&1.25));
+    assert_eq!(repr.coeff(Pauli::with_ops([I, X])), Some(&0.5));
+}
+
+#[test]
+fn read_sparse_pauli_op_tolerance() {
+    let data = r#"[
+        ["IZ", [2.0, 1e-13]],
+        ["XI", [-0.5, -5e-13]],
+        ["XX", [0.0, 1e-17]]
+    ]"#;
+    let repr: PauliSum = read_sparse_pauli_op(data.as_bytes()).unwrap();
+
+    assert_eq!(repr.coeff(Pauli::with_ops([Z])), Some(&2.0));
+    assert_eq!(repr.coeff(Pauli::with_ops([I, X])), Some(&-0.5));
+    assert_eq!(repr.coeff(Pauli::with_ops([X, X])), Some(&0.0));
+}
+
+#[test]
+fn read_sparse_pauli_op_invalid() {
+    for data in [
+        r#"[["IZ", [1.0, 0.5]]]"#,
+        r#"[["IZ", [1.0, 1e-9]]]"#,
+        r#"[["IZ", [0.0, 1e-9]]]"#,
+        r#"[["IW", 1.0]]"#,
+        r#"[["IZ"]]"#,
+        r#"{"IZ": 1.0}"#,
+        r#"[["IZ", "1.0"]]"#,
+    ] {
+        assert!(read_sparse_pauli_op::<f64, _>(data.as_bytes()).is_err());
+    }
+}
+
+#[test]
+fn sparse_pauli_op_roundtrip() {
+    let repr = random_pauli_sum(45, 100);
+
+    let mut buf = Vec::new();
+    write_sparse_pauli_op(&repr, &mut buf).unwrap();
+    let parsed: PauliSum = read_sparse_pauli_op(buf.as_slice()).unwrap();
+
+    assert_eq!(parsed.len(), repr.len());
+    assert_sums_eq(&repr, &parsed, 1e-15);
+}
@@ -1,5 +1,6 @@
 use crate::{
     code::qubits::{
+        parse_sparse_term,
         Pauli,
         PauliGroup,
         PauliOp,
@@ -450,3 +451,183 @@ fn pauliop_03() {
     assert_eq!(u8::from(PauliOp::Y), 2);
     assert_eq!(u8::from(PauliOp::Z), 3);
 }
+
+#[test]
+fn permute_01() {
+    use PauliOp::*;
+
+    let code = Pauli::with_ops([X, I, Y, Z]);
+
+    assert_eq!(code.permute(&[0, 1, 2, 3]).unwrap(), code);
+    assert_eq!(
+        code.permute(&[3, 2, 1, 0]).unwrap(),
+        Pauli::with_ops([Z, Y, I, X])
+    );
+    assert_eq!(
+        code.permute(&[1, 63, 0, 2]).unwrap(),
+        Pauli::with_ops([Y, X, Z])
+    );
+    assert_eq!(Pauli::identity().permute(&[]).unwrap(), Pauli::identity());
+}
+
+#[test]
+fn permute_02() {
+    use PauliOp::*;
+
+    let code = Pauli::with_ops([X, I, Y, Z]);
+
+    code.permute(&[0, 1, 2]).unwrap_err();
+    code.permute(&[0, 1, 2, 64]).unwrap_err();
+    // not injective
+    code.permute(&[0, 1, 0, 3]).unwrap_err();
+    code.permute(&[0, 0, 2, 3]).unwrap_err();
+}
+
+#[test]
+fn permute_03() {
+    let code = Pauli::new((0, 0b11 << 62));
+    let perm: Vec<u16> = (0..64).rev().collect();
+
+    assert_eq!(code.permute(&perm).unwrap(), Pauli::new((0b11, 0)));
+}
+
+#[test]
+fn embed_01() {
+    use PauliOp::*;
+
+    let code = Pauli::with_ops([X, I, Y]);
+
+    assert_eq!(code.embed(0).unwrap(), code);
+    assert_eq!(code.embed(1).unwrap(), Pauli::with_ops([I, X, I, Y]));
+    assert_eq!(code.embed(30).unwrap(), Pauli::new((0b01 << 60, 0b10)));
+    assert_eq!(code.embed(61).unwrap(), Pauli::new((0, 0b10_0001 << 58)));
+
+    code.embed(62).unwrap_err();
+    code.embed(u16::MAX).unwrap_err();
+}
+
+#[test]
+fn embed_02() {
+    assert_eq!(Pauli::identity().embed(64).unwrap(), Pauli::identity());
+    assert_eq!(
+        Pauli::identity().embed(u16::MAX).unwrap(),
+        Pauli::identity()
+    );
+}
+
+#[test]
+fn xz_masks() {
+    assert_eq!(Pauli::identity().xz_masks(), (0, 0));
+    assert_eq!(
+        Pauli::with_ops([PauliOp::X, PauliOp::Y, PauliOp::Z, PauliOp::I])
+            .xz_masks(),
+        (0b011, 0b110)
+    );
+
+    let mut code = Pauli::identity();
+    code.set(33, PauliOp::Y);
+    code.set(63, PauliOp::X);
+    code.set(31, PauliOp::Z);
+    assert_eq!(code.xz_masks(), (1 << 33 | 1 << 63, 1 << 31 | 1 << 33));
+}
+
+#[test]
+fn sparse_to_string() {
+    use PauliOp::*;
+
+    assert_eq!(Pauli::identity().to_sparse(), "I");
+    assert_eq!(Pauli::with_ops([X, I, Z]).to_sparse(), "X0 Z2");
+    assert_eq!(Pauli::new((0, 3 << 62)).to_sparse(), "Z63");
+    assert_eq!(Pauli::new((u64::MAX, 0)).to_sparse().split(' ').count(), 32);
+}
+
+#[test]
+fn sparse_parse() {
+    use PauliOp::*;
+
+    assert_eq!(Pauli::from_sparse("").unwrap(), Pauli::identity());
+    assert_eq!(Pauli::from_sparse("I").unwrap(), Pauli::identity());
+    assert_eq!(Pauli::from_sparse(" I3 ").unwrap(), Pauli::identity());
+    assert_eq!(
+        Pauli::from_sparse("Y1 X0").unwrap(),
+        Pauli::with_ops([X, Y])
+    );
+    assert_eq!(
+        Pauli::from_sparse("X0Y1 Z2").unwrap(),
+        Pauli::with_ops([X, Y, Z])
+    );
+    assert_eq!(Pauli::from_sparse("Z63").unwrap(), Pauli::new((0, 3 << 62)));
+}
+
+#[test]
+fn sparse_parse_errors() {
+    assert!(matches!(
+        Pauli::from_sparse("X64"),
+        Err(Error::QubitIndex { .. })
+    ));
+    assert!(matches!(
+        Pauli::from_sparse("X99999999999999999999"),
+        Err(Error::QubitIndex { .. })
+    ));
+    assert!(matches!(Pauli::from_sparse("X"), Err(Error::Parse { .. })));
+    assert!(matches!(
+        Pauli::from_sparse("X0 Y"),
+        Err(Error::Parse { .. })
+    ));
+    assert!(matches!(Pauli::from_sparse("A0"), Err(Error::Parse { .. })));
+    assert!(matches!(Pauli::from_sparse("0X"), Err(Error::Parse { .. })));
+    assert!(matches!(
+        Pauli::from_sparse("X0 Z0"),
+        Err(Error::Parse { .. })
+    ));
+}
+
+#[test]
+fn sparse_roundtrip() {
+    for pack in [
+        (0, 0),
+        (0b1110_0100, 0),
+        (u64::MAX, 0x0123_4567_89ab_cdef),
+        (0, 1 << 62),
+    ] {
+        let code = Pauli::new(pack);
+        assert_eq!(Pauli::from_sparse(&code.to_sparse()).unwrap(), code);
+        assert_eq!(code.to_sparse().parse::<Pauli>().unwrap(), code);
+        assert_eq!(code.to_string().parse::<Pauli>().unwrap(), code);
+    }
+}
+
+#[test]
+fn dense_parse_errors() {
+    assert!("".parse::<Pauli>().is_err());
+    assert!("IXP".parse::<Pauli>().is_err());
+    assert!("X".repeat(65).parse::<Pauli>().is_err());
+    assert_eq!(
+        "X".repeat(64).parse::<Pauli>().unwrap().num_nontrivial(),
+        64
+    );
+}
+
+#[test]
+fn sparse_term_parse() {
+    use PauliOp::*;
+
+    assert_eq!(
+        parse_sparse_term::<f64>("0.25 X0 Z1 Y3").unwrap(),
+        (0.25, Pauli::with_ops([X, Z, I, Y]))
+    );
+    assert_eq!(
+        parse_sparse_term::<f64>("-1.5e-2*X1").unwrap(),
+        (-0.015, Pauli::with_ops([I, X]))
+    );
+    assert_eq!(
+        parse_sparse_term::<f64>(" + Z0 ").unwrap(),
+        (1.0, Pauli::with_ops([Z]))
+    );
+    assert_eq!(
+        parse_sparse_term::<f64>("-3").unwrap(),
+        (-3.0, Pauli::identity())
+    );
+    assert!(parse_sparse_term::<f64>("abc X0").is_err());
+    assert!(parse_sparse_term::<f64>("0.5 X0 X0").is_err());
+}
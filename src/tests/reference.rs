@@ -0,0 +1,139 @@
+use num::complex::Complex;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use PauliOp::{
+    I,
+    X,
+    Z,
+};
+
+use crate::{
+    code::{
+        fermions::{
+            An,
+            Cr,
+            Fermions,
+            Orbital,
+            OrbitalOrdering,
+        },
+        qubits::{
+            Pauli,
+            PauliOp,
+        },
+    },
+    map::JordanWigner,
+    reference::{
+        basis_expectation,
+        hartree_fock,
+    },
+    statevector::expectation,
+    terms::{
+        FermiSum,
+        PauliSum,
+        SumRepr,
+        Terms,
+    },
+};
+
+#[test]
+fn basis_expectation_matches_statevector() {
+    let mut rng = StdRng::seed_from_u64(40);
+    let num_qubits = 5;
+    let mut repr = PauliSum::new();
+    for _ in 0..40 {
+        let mut code = Pauli::identity();
+        for i in 0..num_qubits {
+            code.set(i, PauliOp::try_from(rng.gen_range(0..4_u32)).unwrap());
+        }
+        repr.add_term(code, rng.gen_range(-1.0..1.0));
+    }
+
+    for b in 0..1_u64 << num_qubits {
+        let mut state = vec![Complex::new(0.0, 0.0); 1 << num_qubits];
+        state[usize::try_from(b).unwrap()] = Complex::new(1.0, 0.0);
+        let expected = expectation(&repr, &state).unwrap();
+
+        assert!((basis_expectation(&repr, b) - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn basis_expectation_64_qubits() {
+    let mut code = Pauli::identity();
+    code.set(0, Z);
+    code.set(63, Z);
+    let repr = SumRepr::from([
+        (1.0, code),
+        (2.0, Pauli::with_ops([X])),
+        (3.0, Pauli::identity()),
+    ]);
+
+    assert_eq!(basis_expectation(&repr, 0), 4.0);
+    assert_eq!(basis_expectation(&repr, 1 << 63), 2.0);
+    assert_eq!(basis_expectation(&repr, 1 << 63 | 1), 4.0);
+}
+
+#[test]
+fn basis_expectation_empty() {
+    let repr = PauliSum::new();
+    assert_eq!(basis_expectation(&repr, 0b101), 0.0);
+
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([I, X]))]);
+    assert_eq!(basis_expectation(&repr, 0b11), 0.0);
+}
+
+#[test]
+fn hartree_fock_interleaved() {
+    let ordering = OrbitalOrdering::Interleaved;
+
+    assert_eq!(hartree_fock(0, ordering).unwrap(), 0);
+    assert_eq!(hartree_fock(1, ordering).unwrap(), 0b1);
+    assert_eq!(hartree_fock(4, ordering).unwrap(), 0b1111);
+    assert_eq!(hartree_fock(64, ordering).unwrap(), u64::MAX);
+    assert!(hartree_fock(65, ordering).is_err());
+}
+
+#[test]
+fn hartree_fock_block() {
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 3
+    };
+
+    assert_eq!(hartree_fock(1, ordering).unwrap(), 0b000_001);
+    assert_eq!(hartree_fock(2, ordering).unwrap(), 0b001_001);
+    assert_eq!(hartree_fock(5, ordering).unwrap(), 0b011_111);
+    assert_eq!(hartree_fock(6, ordering).unwrap(), 0b111_111);
+    assert!(hartree_fock(7, ordering).is_err());
+
+    let ordering = OrbitalOrdering::Block {
+        num_orbitals: 40
+    };
+    assert!(hartree_fock(60, ordering).is_err());
+}
+
+#[test]
+fn hartree_fock_energy_jordan_wigner() {
+    // Each integral is mapped together with its Hermitian conjugate, so the
+    // energy is twice the sum of occupied diagonal integrals.
+    let mut repr = FermiSum::new();
+    for i in 0..64 {
+        let orbital = Orbital::with_index(i);
+        let code = Fermions::one_electron(Cr(orbital), An(orbital)).unwrap();
+        repr.add_term(code, f64::from(i));
+    }
+    repr.add_term(Fermions::Offset, 0.5);
+    let mut qubit_repr = PauliSum::with_capacity(repr.len() * 2);
+    JordanWigner::new(&repr).add_to(&mut qubit_repr).unwrap();
+
+    for num_electrons in [0, 1, 10, 63, 64] {
+        let bits =
+            hartree_fock(num_electrons, OrbitalOrdering::Interleaved).unwrap();
+        let expected =
+            0.5 + 2.0 * (0..num_electrons).map(f64::from).sum::<f64>();
+
+        assert!((basis_expectation(&qubit_repr, bits) - expected).abs() < 1e-9);
+    }
+}
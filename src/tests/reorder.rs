@@ -0,0 +1,185 @@
+use crate::{
+    code::fermions::Fermions,
+    map::{
+        reorder::{
+            jordan_wigner_sum_weight,
+            jordan_wigner_weight,
+            reorder,
+            Heuristic,
+            Objective,
+        },
+        JordanWigner,
+    },
+    terms::{
+        FermiSum,
+        PauliSum,
+        Terms,
+    },
+};
+
+fn jw_max_weight(code: Fermions) -> u32 {
+    let repr = FermiSum::from([(1.0, code)]);
+    let mut pauli_repr = PauliSum::new();
+    JordanWigner::new(&repr).add_to(&mut pauli_repr).unwrap();
+
+    pauli_repr
+        .iter()
+        .map(|(_, code)| u32::from(code.num_nontrivial()))
+        .max()
+        .unwrap()
+}
+
+fn sample_repr() -> FermiSum {
+    let mut repr = FermiSum::new();
+    repr.add_term(Fermions::Offset, 1.0);
+    for p in 0..8 {
+        for q in p..8 {
+            if (p + 2 * q) % 3 == 0 {
+                repr.add_term(Fermions::try_from((p, q)).unwrap(), 1.0);
+            }
+        }
+    }
+    for (p, q, r, s) in [
+        (0, 6, 6, 0),
+        (0, 7, 6, 1),
+        (1, 2, 7, 5),
+        (2, 3, 5, 4),
+        (1, 3, 3, 1),
+        (0, 4, 5, 1),
+    ] {
+        repr.add_term(Fermions::try_from((p, q, r, s)).unwrap(), 0.5);
+    }
+
+    repr
+}
+
+#[test]
+fn jordan_wigner_weight_01() {
+    assert_eq!(jordan_wigner_weight(&Fermions::Offset), 0);
+
+    for p in 0..6 {
+        for q in p..6 {
+            let code = Fermions::try_from((p, q)).unwrap();
+            assert_eq!(jordan_wigner_weight(&code), jw_max_weight(code));
+        }
+    }
+}
+
+#[test]
+fn jordan_wigner_weight_02() {
+    for p in 0..6 {
+        for q in p + 1..6 {
+            for s in p..6 {
+                for r in s + 1..6 {
+                    let code = Fermions::try_from((p, q, r, s)).unwrap();
+                    assert_eq!(
+                        jordan_wigner_weight(&code),
+                        jw_max_weight(code),
+                        "{code}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn reorder_empty() {
+    let repr = FermiSum::new();
+    let reordering = reorder(&repr, Objective::Total, Heuristic::Greedy);
+
+    assert!(reordering.orbitals.is_empty());
+    assert!(reordering.permutation.is_empty());
+    assert_eq!(reordering.initial, reordering.optimized);
+}
+
+#[test]
+fn reorder_permutation_01() {
+    let repr = sample_repr();
+    let n = 8;
+
+    for objective in [Objective::Total, Objective::Max] {
+        for heuristic in [
+            Heuristic::Greedy,
+            Heuristic::Annealing {
+                iterations: 1000,
+                seed:       1234,
+            },
+        ] {
+            let reordering = reorder(&repr, objective, heuristic);
+
+            assert_eq!(reordering.orbitals, (0..n).collect::<Vec<_>>());
+            let mut perm = reordering.permutation.clone();
+            perm.sort_unstable();
+            assert_eq!(perm, (0..n).collect::<Vec<_>>());
+
+            let relabeled = reordering.apply(&repr).unwrap();
+            assert_eq!(relabeled.len(), repr.len());
+            assert_eq!(jordan_wigner_sum_weight(&repr), reordering.initial);
+            assert_eq!(
+                jordan_wigner_sum_weight(&relabeled),
+                reordering.optimized
+            );
+        }
+    }
+}
+
+#[test]
+fn reorder_never_worse_01() {
+    let repr = sample_repr();
+
+    let greedy = reorder(&repr, Objective::Total, Heuristic::Greedy);
+    assert!(greedy.optimized.total <= greedy.initial.total);
+
+    let annealing = reorder(
+        &repr,
+        Objective::Total,
+        Heuristic::Annealing {
+            iterations: 2000,
+            seed:       7,
+        },
+    );
+    assert!(annealing.optimized.total <= greedy.optimized.total);
+
+    let annealing = reorder(
+        &repr,
+        Objective::Max,
+        Heuristic::Annealing {
+            iterations: 2000,
+            seed:       7,
+        },
+    );
+    assert!(annealing.optimized.max <= annealing.initial.max);
+}
+
+#[test]
+fn reorder_spin_blocks_01() {
+    // Hopping only between orbitals with the same spin, interleaved
+    let repr = FermiSum::from([
+        (1.0, Fermions::try_from((0, 2)).unwrap()),
+        (1.0, Fermions::try_from((2, 4)).unwrap()),
+        (1.0, Fermions::try_from((1, 3)).unwrap()),
+        (1.0, Fermions::try_from((3, 5)).unwrap()),
+    ]);
+
+    let reordering = reorder(&repr, Objective::Total, Heuristic::Greedy);
+    assert_eq!(reordering.initial.total, 12);
+    assert_eq!(reordering.optimized.total, 8);
+}
+
+#[test]
+fn reorder_large_indices() {
+    let repr = FermiSum::from([
+        (1.0, Fermions::try_from((0, 1_000_000)).unwrap()),
+        (1.0, Fermions::try_from((1, u32::MAX - 1)).unwrap()),
+        (1.0, Fermions::try_from((1_000_000, 1_000_000)).unwrap()),
+    ]);
+
+    let reordering = reorder(&repr, Objective::Total, Heuristic::Greedy);
+    assert_eq!(reordering.orbitals, [0, 1, 1_000_000, u32::MAX - 1]);
+    assert_eq!(reordering.initial.max, u32::MAX - 1);
+    assert_eq!(reordering.optimized.total, 5);
+
+    let relabeled = reordering.apply(&repr).unwrap();
+    assert_eq!(jordan_wigner_sum_weight(&relabeled), reordering.optimized);
+}
@@ -0,0 +1,188 @@
+use num::complex::Complex;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use crate::{
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    sparse::{
+        CooMatrix,
+        CsrMatrix,
+    },
+    terms::{
+        PauliSum,
+        SumRepr,
+    },
+};
+
+type Dense = Vec<Vec<Complex<f64>>>;
+
+fn pauli_matrix(op: PauliOp) -> Dense {
+    let c = |re, im| Complex::new(re, im);
+    match op {
+        I => vec![vec![c(1., 0.), c(0., 0.)], vec![c(0., 0.), c(1., 0.)]],
+        X => vec![vec![c(0., 0.), c(1., 0.)], vec![c(1., 0.), c(0., 0.)]],
+        Y => vec![vec![c(0., 0.), c(0., -1.)], vec![c(0., 1.), c(0., 0.)]],
+        Z => vec![vec![c(1., 0.), c(0., 0.)], vec![c(0., 0.), c(-1., 0.)]],
+    }
+}
+
+fn kron(
+    a: &Dense,
+    b: &Dense,
+) -> Dense {
+    let (n, m) = (a.len(), b.len());
+    let mut out = vec![vec![Complex::new(0., 0.); n * m]; n * m];
+    for i in 0..n {
+        for j in 0..n {
+            for k in 0..m {
+                for l in 0..m {
+                    out[i * m + k][j * m + l] = a[i][j] * b[k][l];
+                }
+            }
+        }
+    }
+    out
+}
+
+// Reference dense matrix: qubit 0 is the least significant bit.
+fn dense(
+    repr: &PauliSum,
+    num_qubits: u16,
+) -> Dense {
+    let dim = 1 << num_qubits;
+    let mut out = vec![vec![Complex::new(0., 0.); dim]; dim];
+    for (&coeff, code) in repr.iter() {
+        let mut m = vec![vec![Complex::new(1., 0.)]];
+        for i in (0..num_qubits).rev() {
+            m = kron(&m, &pauli_matrix(code.pauli(i).unwrap()));
+        }
+        for r in 0..dim {
+            for c in 0..dim {
+                out[r][c] += m[r][c] * coeff;
+            }
+        }
+    }
+    out
+}
+
+fn random_repr(
+    rng: &mut StdRng,
+    num_qubits: u16,
+    num_terms: usize,
+) -> PauliSum {
+    let mut repr = PauliSum::new();
+    for _ in 0..num_terms {
+        let mut code = Pauli::identity();
+        for i in 0..num_qubits {
+            code.set(i, PauliOp::try_from(rng.gen_range(0..4_u32)).unwrap());
+        }
+        repr.add_term(code, rng.gen_range(-1.0..1.0));
+    }
+    repr
+}
+
+#[test]
+fn csr_matches_dense() {
+    let mut rng = StdRng::seed_from_u64(37);
+    for num_qubits in 1..=4 {
+        let repr = random_repr(&mut rng, num_qubits, 12);
+        let matrix = CsrMatrix::from_sumrepr(&repr, num_qubits).unwrap();
+        let reference = dense(&repr, num_qubits);
+
+        assert_eq!(matrix.dim(), 1 << num_qubits);
+        assert_eq!(matrix.indptr().len(), matrix.dim() + 1);
+        for (r, row) in reference.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                assert!((matrix.get(r, c) - value).norm() < 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn csr_is_hermitian() {
+    let mut rng = StdRng::seed_from_u64(137);
+    let repr = random_repr(&mut rng, 6, 40);
+    let matrix = CsrMatrix::from_sumrepr(&repr, 6).unwrap();
+
+    for r in 0..matrix.dim() {
+        for c in 0..matrix.dim() {
+            assert_eq!(matrix.get(r, c), matrix.get(c, r).conj());
+        }
+    }
+}
+
+#[test]
+fn csr_sorted_indices() {
+    let mut rng = StdRng::seed_from_u64(237);
+    let repr = random_repr(&mut rng, 5, 30);
+    let matrix = CsrMatrix::from_sumrepr(&repr, 5).unwrap();
+
+    for w in matrix.indptr().windows(2) {
+        let row = &matrix.indices()[w[0]..w[1]];
+        assert!(row.windows(2).all(|p| p[0] < p[1]));
+    }
+}
+
+#[test]
+fn csr_drops_zeros() {
+    let repr = SumRepr::from([
+        (1.0, Pauli::with_ops([Z])),
+        (1.0, Pauli::with_ops([I, Z])),
+    ]);
+    let matrix = CsrMatrix::from_sumrepr(&repr, 2).unwrap();
+
+    // diag(2, 0, 0, -2)
+    assert_eq!(matrix.nnz(), 2);
+    assert_eq!(matrix.indptr(), &[0, 1, 1, 1, 2]);
+    assert_eq!(matrix.indices(), &[0, 3]);
+    assert_eq!(
+        matrix.values(),
+        &[Complex::new(2.0, 0.0), Complex::new(-2.0, 0.0)]
+    );
+}
+
+#[test]
+fn csr_larger_register() {
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([Y]))]);
+    let matrix = CsrMatrix::from_sumrepr(&repr, 3).unwrap();
+
+    assert_eq!(matrix.dim(), 8);
+    assert_eq!(matrix.nnz(), 8);
+    assert_eq!(matrix.get(6, 7), Complex::new(0.0, -1.0));
+    assert_eq!(matrix.get(7, 6), Complex::new(0.0, 1.0));
+}
+
+#[test]
+fn csr_errors() {
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([I, I, X]))]);
+
+    assert!(CsrMatrix::from_sumrepr(&repr, 2).is_err());
+    assert!(CsrMatrix::from_sumrepr(&repr, 64).is_err());
+}
+
+#[test]
+fn csr_to_coo() {
+    let mut rng = StdRng::seed_from_u64(337);
+    let repr = random_repr(&mut rng, 4, 10);
+    let matrix = CsrMatrix::from_sumrepr(&repr, 4).unwrap();
+    let coo = CooMatrix::from(matrix.clone());
+
+    assert_eq!(coo.dim, 16);
+    assert_eq!(coo.values.len(), matrix.nnz());
+    for ((&r, &c), &value) in coo.rows.iter().zip(&coo.cols).zip(&coo.values) {
+        assert_eq!(matrix.get(r, c), value);
+    }
+}
@@ -0,0 +1,216 @@
+use num::complex::Complex;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use crate::{
+    circuit::{
+        Circuit,
+        Gate,
+    },
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    stabilizer::StabilizerState,
+    statevector::expectation,
+    terms::{
+        PauliSum,
+        SumRepr,
+    },
+};
+
+type State = Vec<Complex<f64>>;
+
+// Reference simulation of Clifford gates on a dense state vector.
+fn apply_dense(
+    state: &mut State,
+    gate: Gate,
+) {
+    let bit = |b: usize, q: u16| b >> q & 1 == 1;
+    let old = state.clone();
+    let i = Complex::new(0., 1.);
+    for (b, amp) in state.iter_mut().enumerate() {
+        *amp = match gate {
+            Gate::H(q) => {
+                let (b0, b1) = (b & !(1 << q), b | 1 << q);
+                let sign = if bit(b, q) { -1. } else { 1. };
+                (old[b0] + old[b1] * sign) * 0.5_f64.sqrt()
+            }
+            Gate::S(q) => {
+                if bit(b, q) {
+                    old[b] * i
+                } else {
+                    old[b]
+                }
+            }
+            Gate::Sdg(q) => {
+                if bit(b, q) {
+                    -old[b] * i
+                } else {
+                    old[b]
+                }
+            }
+            Gate::Cnot(c, t) => {
+                if bit(b, c) {
+                    old[b ^ 1 << t]
+                } else {
+                    old[b]
+                }
+            }
+            Gate::Cz(a, c) => {
+                if bit(b, a) && bit(b, c) {
+                    -old[b]
+                } else {
+                    old[b]
+                }
+            }
+            Gate::Swap(a, c) => {
+                let swapped = if bit(b, a) == bit(b, c) {
+                    b
+                } else {
+                    b ^ (1 << a | 1 << c)
+                };
+                old[swapped]
+            }
+            Gate::Rz(..) => unreachable!(),
+        };
+    }
+}
+
+fn random_gate(
+    rng: &mut StdRng,
+    num_qubits: u16,
+) -> Gate {
+    let a = rng.gen_range(0..num_qubits);
+    let b = (a + rng.gen_range(1..num_qubits)) % num_qubits;
+    match rng.gen_range(0..6) {
+        0 => Gate::H(a),
+        1 => Gate::S(a),
+        2 => Gate::Sdg(a),
+        3 => Gate::Cnot(a, b),
+        4 => Gate::Cz(a, b),
+        _ => Gate::Swap(a, b),
+    }
+}
+
+fn random_repr(
+    rng: &mut StdRng,
+    num_qubits: u16,
+    num_terms: usize,
+) -> PauliSum {
+    let mut repr = PauliSum::new();
+    for _ in 0..num_terms {
+        let mut code = Pauli::identity();
+        for i in 0..num_qubits {
+            code.set(i, PauliOp::try_from(rng.gen_range(0..4_u32)).unwrap());
+        }
+        repr.add_term(code, rng.gen_range(-1.0..1.0));
+    }
+    repr
+}
+
+#[test]
+fn basis_state() {
+    let state = StabilizerState::with_bits(3, 0b101);
+
+    assert_eq!(state.num_qubits(), 3);
+    assert_eq!(state.pauli_expectation(Pauli::identity()).unwrap(), 1);
+    assert_eq!(state.pauli_expectation(Pauli::with_ops([Z])).unwrap(), -1);
+    assert_eq!(state.pauli_expectation(Pauli::with_ops([I, Z])).unwrap(), 1);
+    assert_eq!(
+        state.pauli_expectation(Pauli::with_ops([Z, Z, Z])).unwrap(),
+        1
+    );
+    assert_eq!(state.pauli_expectation(Pauli::with_ops([X])).unwrap(), 0);
+    assert_eq!(state.pauli_expectation(Pauli::with_ops([I, Y])).unwrap(), 0);
+}
+
+#[test]
+fn single_qubit_gates() {
+    // S H |0> = |+i>
+    let mut state = StabilizerState::new(1);
+    state.apply(Gate::H(0));
+    state.apply(Gate::S(0));
+
+    assert_eq!(state.pauli_expectation(Pauli::with_ops([Y])).unwrap(), 1);
+    assert_eq!(state.pauli_expectation(Pauli::with_ops([X])).unwrap(), 0);
+
+    state.apply(Gate::Sdg(0));
+    state.apply(Gate::Sdg(0));
+    assert_eq!(state.pauli_expectation(Pauli::with_ops([Y])).unwrap(), -1);
+}
+
+#[test]
+fn ghz_64_qubits() {
+    let mut circuit = Circuit::new(64);
+    circuit.push(Gate::H(0)).unwrap();
+    for q in 1..64 {
+        circuit.push(Gate::Cnot(q - 1, q)).unwrap();
+    }
+    let state = StabilizerState::from_circuit(&circuit);
+
+    let all = |op| {
+        let mut code = Pauli::identity();
+        for q in 0..64 {
+            code.set(q, op);
+        }
+        code
+    };
+    let mut z_pair = Pauli::identity();
+    z_pair.set(5, Z);
+    z_pair.set(63, Z);
+
+    assert_eq!(state.pauli_expectation(all(X)).unwrap(), 1);
+    assert_eq!(state.pauli_expectation(all(Z)).unwrap(), 1);
+    assert_eq!(state.pauli_expectation(z_pair).unwrap(), 1);
+    assert_eq!(state.pauli_expectation(Pauli::with_ops([Z])).unwrap(), 0);
+    // Y^{64} = (-1)^{32} X^{64} Z^{64} on GHZ
+    assert_eq!(state.pauli_expectation(all(Y)).unwrap(), 1);
+}
+
+#[test]
+fn matches_statevector() {
+    let mut rng = StdRng::seed_from_u64(41);
+    for num_qubits in 2..=5 {
+        for _ in 0..10 {
+            let mut state = StabilizerState::with_bits(num_qubits, 0);
+            let mut dense = vec![Complex::new(0., 0.); 1 << num_qubits];
+            dense[0] = Complex::new(1., 0.);
+            for _ in 0..30 {
+                let gate = random_gate(&mut rng, num_qubits);
+                state.apply(gate);
+                apply_dense(&mut dense, gate);
+            }
+            let repr = random_repr(&mut rng, num_qubits, 50);
+
+            let value = state.expectation(&repr).unwrap();
+            let expected = expectation(&repr, &dense).unwrap();
+            assert!((value - expected).abs() < 1e-10, "{value} {expected}");
+        }
+    }
+}
+
+#[test]
+fn expectation_errors() {
+    let state = StabilizerState::new(2);
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([I, I, Z]))]);
+
+    assert!(state.expectation(&repr).is_err());
+    assert!(state.pauli_expectation(Pauli::with_ops([I, I, X])).is_err());
+}
+
+#[test]
+#[should_panic(expected = "gate acts outside of the register")]
+fn gate_outside_register() {
+    let mut state = StabilizerState::new(2);
+    state.apply(Gate::Cnot(0, 2));
+}
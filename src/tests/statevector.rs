@@ -0,0 +1,171 @@
+use num::complex::Complex;
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use crate::{
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    sparse::CsrMatrix,
+    statevector::{
+        apply,
+        expectation,
+    },
+    terms::{
+        PauliSum,
+        SumRepr,
+    },
+};
+
+fn random_repr(
+    rng: &mut StdRng,
+    num_qubits: u16,
+    num_terms: usize,
+) -> PauliSum {
+    let mut repr = PauliSum::new();
+    for _ in 0..num_terms {
+        let mut code = Pauli::identity();
+        for i in 0..num_qubits {
+            code.set(i, PauliOp::try_from(rng.gen_range(0..4_u32)).unwrap());
+        }
+        repr.add_term(code, rng.gen_range(-1.0..1.0));
+    }
+    repr
+}
+
+fn random_state(
+    rng: &mut StdRng,
+    num_qubits: u16,
+) -> Vec<Complex<f64>> {
+    (0..1 << num_qubits)
+        .map(|_| {
+            Complex::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0))
+        })
+        .collect()
+}
+
+fn matvec(
+    matrix: &CsrMatrix<f64>,
+    state: &[Complex<f64>],
+) -> Vec<Complex<f64>> {
+    (0..matrix.dim())
+        .map(|r| (0..matrix.dim()).map(|c| matrix.get(r, c) * state[c]).sum())
+        .collect()
+}
+
+#[test]
+fn apply_matches_csr() {
+    let mut rng = StdRng::seed_from_u64(39);
+    for num_qubits in 1..=5 {
+        let repr = random_repr(&mut rng, num_qubits, 20);
+        let state = random_state(&mut rng, num_qubits);
+        let matrix = CsrMatrix::from_sumrepr(&repr, num_qubits).unwrap();
+        let expected = matvec(&matrix, &state);
+
+        let mut result = state.clone();
+        apply(&repr, &mut result).unwrap();
+
+        for (a, b) in result.iter().zip(&expected) {
+            assert!((a - b).norm() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn apply_pauli_y() {
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([I, Y]))]);
+    // |00>, |01>, |10>, |11>; qubit 0 is the least significant bit
+    let mut state = [
+        Complex::new(1.0, 0.0),
+        Complex::new(0.0, 0.0),
+        Complex::new(0.0, 0.0),
+        Complex::new(0.0, 0.0),
+    ];
+    apply(&repr, &mut state).unwrap();
+
+    assert_eq!(
+        state,
+        [
+            Complex::new(0.0, 0.0),
+            Complex::new(0.0, 0.0),
+            Complex::new(0.0, 1.0),
+            Complex::new(0.0, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn apply_empty_sum() {
+    let repr = PauliSum::new();
+    let mut state = [Complex::new(1.0, 2.0), Complex::new(3.0, 4.0)];
+    apply(&repr, &mut state).unwrap();
+
+    assert_eq!(state, [Complex::new(0.0, 0.0), Complex::new(0.0, 0.0)]);
+}
+
+#[test]
+fn expectation_matches_apply() {
+    let mut rng = StdRng::seed_from_u64(139);
+    for num_qubits in 1..=6 {
+        let repr = random_repr(&mut rng, num_qubits, 30);
+        let state = random_state(&mut rng, num_qubits);
+        let mut h_state = state.clone();
+        apply(&repr, &mut h_state).unwrap();
+        let expected: Complex<f64> =
+            state.iter().zip(&h_state).map(|(a, b)| a.conj() * b).sum();
+
+        let value = expectation(&repr, &state).unwrap();
+
+        assert!(expected.im.abs() < 1e-10);
+        assert!((value - expected.re).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn expectation_bell_state() {
+    let repr = SumRepr::from([
+        (1.0, Pauli::with_ops([X, X])),
+        (2.0, Pauli::with_ops([Y, Y])),
+        (3.0, Pauli::with_ops([Z, Z])),
+        (4.0, Pauli::with_ops([Z, I])),
+    ]);
+    // (|00> + |11>) / sqrt(2): <XX> = 1, <YY> = -1, <ZZ> = 1, <ZI> = 0
+    let amp = Complex::new(0.5_f64.sqrt(), 0.0);
+    let zero = Complex::new(0.0, 0.0);
+    let value = expectation(&repr, &[amp, zero, zero, amp]).unwrap();
+
+    assert!((value - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn expectation_larger_register() {
+    let repr = SumRepr::from([(1.5, Pauli::with_ops([Z]))]);
+    let mut state = vec![Complex::new(0.0, 0.0); 8];
+    state[5] = Complex::new(1.0, 0.0);
+
+    assert_eq!(expectation(&repr, &state).unwrap(), -1.5);
+}
+
+#[test]
+fn state_errors() {
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([I, I, X]))]);
+    let mut state = vec![Complex::new(0.0, 0.0); 4];
+
+    assert!(apply(&repr, &mut state).is_err());
+    assert!(expectation(&repr, &state).is_err());
+
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([X]))]);
+    let mut state = vec![Complex::new(0.0, 0.0); 3];
+    assert!(apply(&repr, &mut state).is_err());
+    assert!(expectation(&repr, &[]).is_err());
+}
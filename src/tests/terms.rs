@@ -1,6 +1,11 @@
 use crate::{
-    code::qubits::Pauli,
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
     terms::{
+        kronecker_sum,
+        tensor,
         HeapRepr,
         StackRepr,
         SumRepr,
@@ -67,3 +72,103 @@ fn heaprepr_terms() {
     repr.add_to(&mut elems).unwrap();
     elems.sort_by(|a, b| a.1.cmp(&b.1));
 }
+
+#[test]
+fn paulisum_permute_01() {
+    use PauliOp::*;
+
+    let repr = SumRepr::from([
+        (1.0, Pauli::with_ops([X, Y, Z])),
+        (2.0, Pauli::with_ops([I, I, Z])),
+        (3.0, Pauli::identity()),
+    ]);
+
+    let perm = repr.permute(&[2, 0, 1]).unwrap();
+    assert_eq!(perm.len(), 3);
+    assert_eq!(perm.coeff(Pauli::with_ops([Y, Z, X])), Some(&1.0));
+    assert_eq!(perm.coeff(Pauli::with_ops([I, Z])), Some(&2.0));
+    assert_eq!(perm.coeff(Pauli::identity()), Some(&3.0));
+}
+
+#[test]
+fn paulisum_permute_02() {
+    use PauliOp::*;
+
+    let repr = SumRepr::from([(1.0, Pauli::with_ops([X, Y, Z]))]);
+
+    repr.permute(&[0, 0, 1]).unwrap_err();
+    repr.permute(&[0, 1, 3]).unwrap_err();
+    repr.permute(&[0, 1]).unwrap_err();
+    repr.permute(&(0..65).collect::<Vec<_>>()).unwrap_err();
+}
+
+#[test]
+fn paulisum_embed_01() {
+    use PauliOp::*;
+
+    let repr = SumRepr::from([
+        (1.0, Pauli::with_ops([X, Y])),
+        (2.0, Pauli::identity()),
+    ]);
+
+    let embedded = repr.embed(2).unwrap();
+    assert_eq!(embedded.coeff(Pauli::with_ops([I, I, X, Y])), Some(&1.0));
+    assert_eq!(embedded.coeff(Pauli::identity()), Some(&2.0));
+
+    repr.embed(63).unwrap_err();
+}
+
+#[test]
+fn tensor_01() {
+    use PauliOp::*;
+
+    let a = SumRepr::from([
+        (1.0, Pauli::with_ops([X, Z])),
+        (2.0, Pauli::with_ops([Y])),
+    ]);
+    let b = SumRepr::from([
+        (3.0, Pauli::with_ops([Z])),
+        (4.0, Pauli::with_ops([I, X])),
+    ]);
+
+    let repr = tensor(&a, &b, 2).unwrap();
+    assert_eq!(repr.len(), 4);
+    assert_eq!(repr.coeff(Pauli::with_ops([X, Z, Z])), Some(&3.0));
+    assert_eq!(repr.coeff(Pauli::with_ops([X, Z, I, X])), Some(&4.0));
+    assert_eq!(repr.coeff(Pauli::with_ops([Y, I, Z])), Some(&6.0));
+    assert_eq!(repr.coeff(Pauli::with_ops([Y, I, I, X])), Some(&8.0));
+}
+
+#[test]
+fn tensor_02() {
+    use PauliOp::*;
+
+    let a = SumRepr::from([(1.0, Pauli::with_ops([X, Z]))]);
+    let b = SumRepr::from([(3.0, Pauli::with_ops([Z]))]);
+
+    tensor(&a, &b, 1).unwrap_err();
+    tensor(&a, &b, 64).unwrap_err();
+    tensor(&a, &SumRepr::new(), 2).unwrap();
+    assert!(tensor(&a, &SumRepr::new(), 2).unwrap().is_empty());
+}
+
+#[test]
+fn kronecker_sum_01() {
+    use PauliOp::*;
+
+    let a = SumRepr::from([
+        (1.0, Pauli::with_ops([X, Z])),
+        (0.5, Pauli::identity()),
+    ]);
+    let b =
+        SumRepr::from([(3.0, Pauli::with_ops([Z])), (0.5, Pauli::identity())]);
+
+    let repr = kronecker_sum(&a, &b, 3).unwrap();
+    assert_eq!(repr.len(), 3);
+    assert_eq!(repr.coeff(Pauli::with_ops([X, Z])), Some(&1.0));
+    assert_eq!(repr.coeff(Pauli::with_ops([I, I, I, Z])), Some(&3.0));
+    assert_eq!(repr.coeff(Pauli::identity()), Some(&1.0));
+
+    kronecker_sum(&a, &b, 1).unwrap_err();
+    kronecker_sum(&a, &b, 64).unwrap_err();
+}
@@ -0,0 +1,467 @@
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use PauliOp::{
+    I,
+    X,
+    Y,
+    Z,
+};
+
+use crate::{
+    circuit::{
+        Circuit,
+        Gate,
+    },
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    math::Root4,
+    terms::{
+        PauliSum,
+        SumRepr,
+    },
+    trotter::{
+        pauli_rotation,
+        Formula,
+        TermOrdering,
+        Trotter,
+    },
+    Error,
+};
+
+fn sample_repr() -> PauliSum {
+    SumRepr::from([
+        (0.5, Pauli::identity()),
+        (1.0, Pauli::with_ops([X, X])),
+        (-2.0, Pauli::with_ops([Z, I])),
+        (0.25, Pauli::with_ops([Y, Y])),
+        (-0.75, Pauli::with_ops([I, X])),
+    ])
+}
+
+// Sum of rotation angles for each Pauli string.
+fn total_angles(exps: &[(f64, Pauli)]) -> PauliSum {
+    let mut repr = PauliSum::new();
+    for &(theta, code) in exps {
+        repr.add_term(code, theta);
+    }
+    repr
+}
+
+#[test]
+fn pauli_rotation_basis_change() {
+    let mut rng = StdRng::seed_from_u64(33);
+    for _ in 0..100 {
+        let code = Pauli::new((rng.gen(), rng.gen()));
+        if code == Pauli::identity() {
+            continue;
+        }
+
+        let mut circuit = Circuit::new(64);
+        pauli_rotation(&mut circuit, 0.1, code).unwrap();
+        let pos = circuit
+            .gates()
+            .iter()
+            .position(|g| matches!(g, Gate::Rz(..)))
+            .unwrap();
+        let Gate::Rz(last, phi) = circuit.gates()[pos] else {
+            unreachable!()
+        };
+        assert_eq!(phi, 0.2);
+        assert_eq!(u16::from(code.min_register_size()), last + 1);
+
+        // Gates before the rotation map the code onto Z at the last qubit.
+        let mut before = Circuit::new(64);
+        circuit.gates()[..pos]
+            .iter()
+            .for_each(|&g| before.push(g).unwrap());
+        let mut z = Pauli::identity();
+        z.set(last, Z);
+        assert_eq!(before.conjugate(code), (Root4::R0, z));
+
+        // Gates after the rotation undo the basis change.
+        let mut after = Circuit::new(64);
+        circuit.gates()[pos + 1..]
+            .iter()
+            .for_each(|&g| after.push(g).unwrap());
+        assert_eq!(after.conjugate(z), (Root4::R0, code));
+    }
+}
+
+#[test]
+fn pauli_rotation_identity() {
+    let mut circuit = Circuit::new(2);
+    pauli_rotation(&mut circuit, 1.0, Pauli::identity()).unwrap();
+    assert!(circuit.is_empty());
+}
+
+#[test]
+fn pauli_rotation_y() {
+    let mut circuit = Circuit::new(1);
+    pauli_rotation(&mut circuit, 0.5, Pauli::with_ops([Y])).unwrap();
+    assert_eq!(
+        circuit.gates(),
+        &[
+            Gate::Sdg(0),
+            Gate::H(0),
+            Gate::Rz(0, 1.0),
+            Gate::H(0),
+            Gate::S(0)
+        ]
+    );
+}
+
+#[test]
+fn pauli_rotation_not_finite() {
+    let mut circuit = Circuit::new(2);
+    let code = Pauli::with_ops([X, Z]);
+    for theta in [f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            pauli_rotation(&mut circuit, theta, code),
+            Err(Error::Coefficient { .. })
+        ));
+    }
+    assert!(circuit.is_empty());
+}
+
+#[test]
+fn first_order_01() {
+    let repr = sample_repr();
+    let exps = Trotter::new(Formula::First, 2)
+        .unwrap()
+        .exponentials(&repr, 1.0);
+
+    let step = [
+        (-1.0, Pauli::with_ops([Z, I])),
+        (-0.375, Pauli::with_ops([I, X])),
+        (0.5, Pauli::with_ops([X, X])),
+        (0.125, Pauli::with_ops([Y, Y])),
+    ];
+    assert_eq!(exps, [step, step].concat());
+}
+
+#[test]
+fn second_order_merges_steps() {
+    let repr = SumRepr::from([
+        (1.0, Pauli::with_ops([X])),
+        (1.0, Pauli::with_ops([Z])),
+    ]);
+    let exps = Trotter::new(Formula::Suzuki(1), 2)
+        .unwrap()
+        .exponentials(&repr, 2.0);
+
+    assert_eq!(
+        exps,
+        [
+            (0.5, Pauli::with_ops([X])),
+            (1.0, Pauli::with_ops([Z])),
+            (1.0, Pauli::with_ops([X])),
+            (1.0, Pauli::with_ops([Z])),
+            (0.5, Pauli::with_ops([X])),
+        ]
+    );
+}
+
+#[test]
+fn total_angle_is_conserved() {
+    let repr = sample_repr();
+    for formula in [
+        Formula::First,
+        Formula::Suzuki(1),
+        Formula::Suzuki(2),
+        Formula::Suzuki(3),
+    ] {
+        for steps in [1, 3] {
+            let exps = Trotter::new(formula, steps)
+                .unwrap()
+                .exponentials(&repr, 0.7);
+            let angles = total_angles(&exps);
+            assert_eq!(angles.len(), repr.len() - 1);
+            for (&theta, &code) in angles.iter() {
+                let coeff = repr.coeff(code).unwrap();
+                assert!((theta - coeff * 0.7).abs() < 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn fourth_order_num_exponentials() {
+    let repr = SumRepr::from([
+        (1.0, Pauli::with_ops([X])),
+        (1.0, Pauli::with_ops([Z])),
+    ]);
+    // Five second-order steps, each with 3 exponentials after merging,
+    // joined at boundaries.
+    let exps = Trotter::new(Formula::Suzuki(2), 1)
+        .unwrap()
+        .exponentials(&repr, 1.0);
+    assert_eq!(exps.len(), 5 * 2 + 1);
+    assert_eq!(Formula::Suzuki(2).order(), 4);
+}
+
+#[test]
+fn ordering_magnitude() {
+    let repr = sample_repr();
+    let trotter =
+        Trotter::with_ordering(Formula::First, 1, TermOrdering::Magnitude)
+            .unwrap();
+    let exps = trotter.exponentials(&repr, 1.0);
+
+    assert_eq!(
+        exps,
+        [
+            (-2.0, Pauli::with_ops([Z, I])),
+            (1.0, Pauli::with_ops([X, X])),
+            (-0.75, Pauli::with_ops([I, X])),
+            (0.25, Pauli::with_ops([Y, Y])),
+        ]
+    );
+}
+
+#[test]
+fn ordering_magnitude_nan() {
+    let mut repr = sample_repr();
+    repr.add_term(Pauli::with_ops([Z, Z]), f64::NAN);
+    let trotter =
+        Trotter::with_ordering(Formula::First, 1, TermOrdering::Magnitude)
+            .unwrap();
+    let exps = trotter.exponentials(&repr, 1.0);
+
+    assert_eq!(exps.len(), 5);
+    assert!(exps[0].0.is_nan());
+    assert_eq!(exps[1], (-2.0, Pauli::with_ops([Z, I])));
+}
+
+#[test]
+fn ordering_commuting() {
+    let repr = sample_repr();
+    let trotter =
+        Trotter::with_ordering(Formula::First, 1, TermOrdering::Commuting)
+            .unwrap();
+    let exps = trotter.exponentials(&repr, 1.0);
+    assert_eq!(exps.len(), 4);
+
+    // ZI and IX commute, as do XX and YY, but IX anticommutes with YY.
+    let codes: Vec<_> = exps.iter().map(|(_, code)| *code).collect();
+    assert_eq!(
+        codes,
+        [
+            Pauli::with_ops([Z, I]),
+            Pauli::with_ops([I, X]),
+            Pauli::with_ops([X, X]),
+            Pauli::with_ops([Y, Y]),
+        ]
+    );
+}
+
+#[test]
+fn circuit_gate_counts() {
+    let repr = sample_repr();
+    let circuit = Trotter::new(Formula::First, 3)
+        .unwrap()
+        .circuit(&repr, 1.0)
+        .unwrap();
+    let counts = circuit.gate_counts();
+
+    assert_eq!(circuit.num_qubits(), 2);
+    assert_eq!(counts.rotations, 12);
+    // XX and YY need 2 CNOTs each
+    assert_eq!(counts.two_qubit, 12);
+    // XX: 4 H, IX: 2 H, YY: 4 H + 2 S + 2 Sdg
+    assert_eq!(counts.single_qubit, 3 * 14 + 12);
+}
+
+#[test]
+fn circuit_not_finite() {
+    let mut repr = sample_repr();
+    repr.add_term(Pauli::with_ops([Z, Z]), f64::NAN);
+    let trotter = Trotter::new(Formula::First, 1).unwrap();
+
+    assert!(matches!(
+        trotter.circuit(&repr, 1.0),
+        Err(Error::Coefficient { .. })
+    ));
+    assert!(matches!(
+        trotter.circuit(&sample_repr(), f64::INFINITY),
+        Err(Error::Coefficient { .. })
+    ));
+}
+
+#[test]
+fn zero_steps() {
+    assert_eq!(Trotter::new(Formula::First, 0), None);
+}
+
+#[test]
+fn zero_order() {
+    assert_eq!(Trotter::new(Formula::Suzuki(0), 1), None);
+}
+
+#[test]
+fn min_steps_precision_not_positive() {
+    let repr = sample_repr();
+    let trotter = Trotter::default();
+
+    for precision in [0.0, -1.0, f64::NAN] {
+        assert_eq!(trotter.min_steps(&repr, 1.0, precision), None);
+    }
+}
+
+#[test]
+fn error_bound_commuting() {
+    let repr = SumRepr::from([
+        (1.0, Pauli::with_ops([Z, Z])),
+        (2.0, Pauli::with_ops([X, X])),
+        (3.0, Pauli::with_ops([Y, Y])),
+        (4.0, Pauli::identity()),
+    ]);
+
+    for formula in [Formula::First, Formula::Suzuki(1)] {
+        let trotter = Trotter::new(formula, 1).unwrap();
+        assert_eq!(trotter.error_bound(&repr, 10.0), Some(0.0));
+        assert_eq!(trotter.min_steps(&repr, 10.0, 1e-6), Some(1));
+    }
+}
+
+#[test]
+fn error_bound_first_order() {
+    let repr = sample_repr();
+    // Anticommuting pairs: (XX, ZI), (YY, ZI), (YY, IX)
+    let alpha = 2.0 * (1.0 * 2.0 + 0.25 * 2.0 + 0.25 * 0.75);
+
+    for ordering in [
+        TermOrdering::Lexicographic,
+        TermOrdering::Magnitude,
+        TermOrdering::Commuting,
+    ] {
+        let trotter =
+            Trotter::with_ordering(Formula::First, 4, ordering).unwrap();
+        let bound = trotter.error_bound(&repr, 0.5).unwrap();
+        assert!((bound - 0.25 / 8.0 * alpha).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn error_bound_second_order() {
+    let (a, b) = (0.75_f64, -1.5_f64);
+    let repr =
+        SumRepr::from([(a, Pauli::with_ops([X])), (b, Pauli::with_ops([Z]))]);
+
+    // S_2 = exp(-i A t/2) exp(-i B t) exp(-i A t/2) with A = aX, B = bZ:
+    // ||[B, [B, A]]|| / 12 + ||[A, [A, B]]|| / 24
+    let beta = 4.0 * a.abs() * b * b / 12.0 + 4.0 * a * a * b.abs() / 24.0;
+    let bound = Trotter::new(Formula::Suzuki(1), 3)
+        .unwrap()
+        .error_bound(&repr, 2.0)
+        .unwrap();
+    assert!((bound - 8.0 / 9.0 * beta).abs() < 1e-12);
+
+    // Reversed order of terms swaps the roles of A and B.
+    let beta = 4.0 * b.abs() * a * a / 12.0 + 4.0 * b * b * a.abs() / 24.0;
+    let bound =
+        Trotter::with_ordering(Formula::Suzuki(1), 3, TermOrdering::Magnitude)
+            .unwrap()
+            .error_bound(&repr, 2.0)
+            .unwrap();
+    assert!((bound - 8.0 / 9.0 * beta).abs() < 1e-12);
+}
+
+#[test]
+fn error_bound_second_order_random() {
+    let mut rng = StdRng::seed_from_u64(19);
+    for _ in 0..10 {
+        let mut repr = PauliSum::new();
+        for _ in 0..rng.gen_range(1..40) {
+            let code = Pauli::new((rng.gen_range(0..1 << 12), 0));
+            repr.add_term(code, rng.gen_range(-1.0..1.0));
+        }
+        let mut terms: Vec<_> = repr
+            .iter()
+            .filter(|(_, &code)| code != Pauli::identity())
+            .map(|(&coeff, &code)| (coeff, code))
+            .collect();
+        terms.sort_by_key(|(_, code)| *code);
+
+        // Direct sum over all triples of terms
+        let mut beta = 0.0;
+        for (i, &(c, p)) in terms.iter().enumerate() {
+            for &(d, q) in &terms[i + 1..] {
+                if p.commutes_with(&q) {
+                    continue;
+                }
+                beta += 4.0 * c * c * d.abs() / 24.0;
+                for &(e, r) in &terms[i + 1..] {
+                    // Counted once for each of p, q anticommuting with r
+                    for s in [p, q] {
+                        if !s.commutes_with(&r) {
+                            beta += 4.0 * (c * d * e).abs() / 12.0;
+                        }
+                    }
+                }
+            }
+        }
+
+        let bound = Trotter::new(Formula::Suzuki(1), 2)
+            .unwrap()
+            .error_bound(&repr, 1.0)
+            .unwrap();
+        assert!((bound - beta / 4.0).abs() < 1e-12 * (1.0 + beta));
+    }
+}
+
+#[test]
+fn error_bound_higher_order() {
+    let repr = sample_repr();
+    let trotter = Trotter::new(Formula::Suzuki(2), 1).unwrap();
+
+    assert_eq!(trotter.error_bound(&repr, 1.0), None);
+    assert_eq!(trotter.min_steps(&repr, 1.0, 0.1), None);
+}
+
+#[test]
+fn min_steps_01() {
+    let repr = sample_repr();
+    for formula in [Formula::First, Formula::Suzuki(1)] {
+        for precision in [0.5, 1e-2, 1e-5] {
+            let trotter = Trotter::new(formula, 1).unwrap();
+            let steps = trotter.min_steps(&repr, 3.0, precision).unwrap();
+
+            let bound = |r| {
+                Trotter::new(formula, r)
+                    .unwrap()
+                    .error_bound(&repr, 3.0)
+                    .unwrap()
+            };
+            assert!(bound(steps) <= precision);
+            assert!(steps == 1 || bound(steps - 1) > precision);
+        }
+    }
+}
+
+#[test]
+fn error_bound_many_terms() {
+    let mut rng = StdRng::seed_from_u64(35);
+    let mut repr = PauliSum::new();
+    for _ in 0..2000 {
+        let code = Pauli::new((rng.gen::<u64>() & 0xffff_ffff, 0));
+        repr.update(code, rng.gen_range(-1.0..1.0));
+    }
+
+    let bound = Trotter::new(Formula::First, 1)
+        .unwrap()
+        .error_bound(&repr, 1.0)
+        .unwrap();
+    let naive: f64 = repr
+        .iter()
+        .flat_map(|(c, p)| repr.iter().map(move |(d, q)| (c, p, d, q)))
+        .filter(|(_, p, _, q)| !p.commutes_with(q))
+        .map(|(c, _, d, _)| (c * d).abs())
+        .sum();
+    // naive sum counts each pair twice, ||[cP, dQ]|| = 2|cd|
+    assert!((bound - naive / 2.0).abs() < 1e-9 * naive);
+}
@@ -0,0 +1,621 @@
+//! Trotter-Suzuki product formulas.
+//!
+//! Approximate the time evolution operator `exp(-i H t)` of a Hamiltonian
+//! given as a sum of Pauli strings `H = sum c_j P_j` by a product of
+//! exponentials of single terms: `exp(-i theta P)`.  Each exponential is
+//! compiled into a ladder of CNOT gates around a single Z rotation.
+
+use num::{
+    traits::float::TotalOrder,
+    Float,
+};
+use rayon::prelude::*;
+
+use crate::{
+    circuit::{
+        Circuit,
+        Gate,
+    },
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    grouping::{
+        group,
+        Coloring,
+        Compatibility,
+    },
+    terms::SumRepr,
+    Error,
+};
+
+/// Product formula.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Formula {
+    /// First-order Lie-Trotter formula
+    #[default]
+    First,
+    /// Symmetric Suzuki formula of order `2k`.
+    ///
+    /// `Suzuki(1)` is the second-order Strang splitting.  Formulas of
+    /// higher order are built recursively.
+    Suzuki(u32),
+}
+
+impl Formula {
+    /// Order of the formula.
+    #[must_use]
+    pub fn order(self) -> u32 {
+        match self {
+            Self::First => 1,
+            Self::Suzuki(k) => 2 * k,
+        }
+    }
+}
+
+/// Order of terms in a single Trotter step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TermOrdering {
+    /// Lexicographic order of Pauli strings
+    #[default]
+    Lexicographic,
+    /// Decreasing magnitude of coefficients
+    Magnitude,
+    /// Groups of mutually commuting terms, largest groups first
+    Commuting,
+}
+
+/// Trotter-Suzuki decomposition of the time evolution operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trotter {
+    formula:  Formula,
+    steps:    u32,
+    ordering: TermOrdering,
+}
+
+impl Default for Trotter {
+    fn default() -> Self {
+        Self {
+            formula:  Formula::default(),
+            steps:    1,
+            ordering: TermOrdering::default(),
+        }
+    }
+}
+
+impl Trotter {
+    /// Product formula with `steps` Trotter steps, with terms in the
+    /// lexicographic order.
+    ///
+    /// Returns `None` if `steps` is zero or if the formula is `Suzuki(0)`.
+    #[must_use]
+    pub fn new(
+        formula: Formula,
+        steps: u32,
+    ) -> Option<Self> {
+        Self::with_ordering(formula, steps, TermOrdering::default())
+    }
+
+    /// Product formula with a custom order of terms.
+    ///
+    /// Returns `None` if `steps` is zero or if the formula is `Suzuki(0)`.
+    #[must_use]
+    pub fn with_ordering(
+        formula: Formula,
+        steps: u32,
+        ordering: TermOrdering,
+    ) -> Option<Self> {
+        if steps == 0 || formula.order() == 0 {
+            return None;
+        }
+
+        Some(Self {
+            formula,
+            steps,
+            ordering,
+        })
+    }
+
+    #[must_use]
+    pub fn formula(&self) -> Formula {
+        self.formula
+    }
+
+    #[must_use]
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    #[must_use]
+    pub fn ordering(&self) -> TermOrdering {
+        self.ordering
+    }
+
+    /// Terms of `repr` without the identity, in the order of a single step.
+    pub(crate) fn ordered_terms<T>(
+        &self,
+        repr: &SumRepr<T, Pauli>,
+    ) -> Vec<(T, Pauli)>
+    where
+        T: Float + TotalOrder,
+    {
+        let mut terms: Vec<_> = repr
+            .iter()
+            .filter(|(_, &code)| code != Pauli::identity())
+            .map(|(&coeff, &code)| (coeff, code))
+            .collect();
+        terms.sort_by_key(|(_, code)| *code);
+
+        match self.ordering {
+            TermOrdering::Lexicographic => terms,
+            TermOrdering::Magnitude => {
+                terms.sort_by(|(a, _), (b, _)| b.abs().total_cmp(&a.abs()));
+                terms
+            }
+            TermOrdering::Commuting => {
+                let mut sorted = SumRepr::with_capacity(terms.len());
+                for (coeff, code) in terms {
+                    sorted.add_term(code, coeff);
+                }
+                group(&sorted, Compatibility::FullyCommuting, Coloring::Dsatur)
+                    .into_iter()
+                    .flat_map(|g| {
+                        let mut g: Vec<_> = g
+                            .iter()
+                            .map(|(&coeff, &code)| (coeff, code))
+                            .collect();
+                        g.sort_by_key(|(_, code)| *code);
+                        g
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Sequence of exponentials `exp(-i theta P)` approximating
+    /// `exp(-i H t)`, as pairs `(theta, P)`.
+    ///
+    /// The identity term contributes only a global phase and is omitted.
+    /// Consecutive exponentials of the same Pauli string are merged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     terms::SumRepr,
+    /// #     trotter::{Formula, Trotter},
+    /// # };
+    /// use PauliOp::{
+    ///     X,
+    ///     Z,
+    /// };
+    ///
+    /// let repr = SumRepr::from([
+    ///     (1.0, Pauli::with_ops([X])),
+    ///     (0.5, Pauli::with_ops([Z])),
+    /// ]);
+    ///
+    /// let exps = Trotter::new(Formula::Suzuki(1), 1)
+    ///     .unwrap()
+    ///     .exponentials(&repr, 2.0);
+    /// assert_eq!(
+    ///     exps,
+    ///     [
+    ///         (1.0, Pauli::with_ops([X])),
+    ///         (1.0, Pauli::with_ops([Z])),
+    ///         (1.0, Pauli::with_ops([X])),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn exponentials<T>(
+        &self,
+        repr: &SumRepr<T, Pauli>,
+        time: T,
+    ) -> Vec<(T, Pauli)>
+    where
+        T: Float + TotalOrder,
+    {
+        let terms = self.ordered_terms(repr);
+        let tau = time / T::from(self.steps).expect("conversion from u32");
+
+        let mut exps = Vec::new();
+        for _ in 0..self.steps {
+            match self.formula {
+                Formula::First => {
+                    exps.extend(terms.iter().map(|&(c, code)| (c * tau, code)));
+                }
+                Formula::Suzuki(k) => suzuki(&terms, k, tau, &mut exps),
+            }
+        }
+
+        let mut merged: Vec<(T, Pauli)> = Vec::with_capacity(exps.len());
+        for (theta, code) in exps {
+            match merged.last_mut() {
+                Some((last, last_code)) if *last_code == code => {
+                    *last = *last + theta;
+                }
+                _ => merged.push((theta, code)),
+            }
+        }
+
+        merged
+    }
+
+    /// Upper bound on the spectral norm of the error of the product formula:
+    /// `|| exp(-i H t) - S(t/r)^r ||`, where `r` is the number of steps.
+    ///
+    /// The bound is based on commutators of terms of `repr` taken in the
+    /// order of a single Trotter step:
+    ///
+    /// - first order: `t^2 / (2r) * sum_{i<j} ||[H_i, H_j]||`,
+    /// - second order: `t^3 / r^2 * (A / 12 + B / 24)`, where `A = sum_i
+    ///   ||[sum_{k>i} H_k, [sum_{j>i} H_j, H_i]]||` and `B = sum_i ||[H_i,
+    ///   [H_i, sum_{j>i} H_j]]||`.
+    ///
+    /// Norms of commutators of Pauli sums are bounded using the triangle
+    /// inequality.  In `A`, the term `H_k` is counted for each of `H_i`,
+    /// `H_j` it anticommutes with, which overestimates the nested
+    /// commutator if it anticommutes with both.  Both bounds take time
+    /// quadratic and memory linear in the number of terms, and are computed
+    /// in parallel.
+    ///
+    /// Returns `None` for formulas of order higher than two.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     terms::SumRepr,
+    /// #     trotter::{Formula, Trotter},
+    /// # };
+    /// use PauliOp::{
+    ///     X,
+    ///     Z,
+    /// };
+    ///
+    /// let repr = SumRepr::from([
+    ///     (1.0, Pauli::with_ops([X])),
+    ///     (0.5, Pauli::with_ops([Z])),
+    /// ]);
+    ///
+    /// // ||[X, Z/2]|| = 1
+    /// let bound = Trotter::new(Formula::First, 10)
+    ///     .unwrap()
+    ///     .error_bound(&repr, 2.0);
+    /// assert_eq!(bound, Some(0.2));
+    /// ```
+    #[must_use]
+    pub fn error_bound<T>(
+        &self,
+        repr: &SumRepr<T, Pauli>,
+        time: T,
+    ) -> Option<T>
+    where
+        T: Float + TotalOrder + Send + Sync,
+    {
+        let terms = self.ordered_terms(repr);
+        let r = T::from(self.steps).expect("conversion from u32");
+        let two = T::one() + T::one();
+        match self.formula.order() {
+            1 => Some(time * time / (two * r) * first_order_norm(&terms)),
+            2 => Some(time.abs().powi(3) / (r * r) * second_order_norm(&terms)),
+            _ => None,
+        }
+    }
+
+    /// Smallest number of steps for which [`Trotter::error_bound`] does
+    /// not exceed `precision`.
+    ///
+    /// The number of steps of `self` is ignored.  Returns `None` for formulas
+    /// of order higher than two, if `precision` is not positive, or if the
+    /// number of steps does not fit into `u32`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     terms::SumRepr,
+    /// #     trotter::{Formula, Trotter},
+    /// # };
+    /// use PauliOp::{
+    ///     X,
+    ///     Z,
+    /// };
+    ///
+    /// let repr = SumRepr::from([
+    ///     (1.0, Pauli::with_ops([X])),
+    ///     (0.5, Pauli::with_ops([Z])),
+    /// ]);
+    ///
+    /// let trotter = Trotter::default();
+    /// assert_eq!(trotter.min_steps(&repr, 2.0, 0.01), Some(200));
+    /// ```
+    #[must_use]
+    pub fn min_steps<T>(
+        &self,
+        repr: &SumRepr<T, Pauli>,
+        time: T,
+        precision: T,
+    ) -> Option<u32>
+    where
+        T: Float + TotalOrder + Send + Sync,
+    {
+        if precision.is_nan() || precision <= T::zero() {
+            return None;
+        }
+
+        // The bound decreases as r^(-order).
+        let one_step = Self::with_ordering(self.formula, 1, self.ordering)?
+            .error_bound(repr, time)?;
+        let order = i32::try_from(self.formula.order()).ok()?;
+        let steps = (one_step / precision)
+            .powf(T::one() / T::from(order)?)
+            .ceil()
+            .max(T::one());
+        let mut steps = steps.to_u32()?;
+
+        // Guard against rounding errors.
+        while Self::with_ordering(self.formula, steps, self.ordering)?
+            .error_bound(repr, time)?
+            > precision
+        {
+            steps = steps.checked_add(1)?;
+        }
+
+        Some(steps)
+    }
+
+    /// Circuit implementing the product formula for `exp(-i H t)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a rotation angle is not finite, e.g. if a
+    /// coefficient of `repr` or `time` is not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use f2q::{
+    /// #     code::qubits::{Pauli, PauliOp},
+    /// #     terms::SumRepr,
+    /// #     trotter::{Formula, Trotter},
+    /// # };
+    /// # fn main() -> Result<(), f2q::Error> {
+    /// use PauliOp::{
+    ///     X,
+    ///     Z,
+    /// };
+    ///
+    /// let repr = SumRepr::from([
+    ///     (1.0, Pauli::with_ops([X, X])),
+    ///     (0.5, Pauli::with_ops([Z])),
+    /// ]);
+    ///
+    /// let circuit = Trotter::new(Formula::First, 10)
+    ///     .unwrap()
+    ///     .circuit(&repr, 1.0)?;
+    /// let counts = circuit.gate_counts();
+    ///
+    /// assert_eq!(circuit.num_qubits(), 2);
+    /// assert_eq!(counts.rotations, 20);
+    /// assert_eq!(counts.two_qubit, 20);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn circuit<T>(
+        &self,
+        repr: &SumRepr<T, Pauli>,
+        time: T,
+    ) -> Result<Circuit, Error>
+    where
+        T: Float + TotalOrder,
+    {
+        let num_qubits = repr
+            .iter()
+            .map(|(_, code)| u16::from(code.min_register_size()))
+            .max()
+            .unwrap_or(0);
+        let mut circuit = Circuit::new(num_qubits);
+        for (theta, code) in self.exponentials(repr, time) {
+            pauli_rotation(
+                &mut circuit,
+                theta.to_f64().expect("conversion to f64"),
+                code,
+            )?;
+        }
+
+        Ok(circuit)
+    }
+}
+
+// sum_{i<j} ||[H_i, H_j]||
+fn first_order_norm<T>(terms: &[(T, Pauli)]) -> T
+where
+    T: Float + Send + Sync,
+{
+    let two = T::one() + T::one();
+    (0..terms.len())
+        .into_par_iter()
+        .map(|i| {
+            let (c, code) = terms[i];
+            terms[i + 1..]
+                .iter()
+                .filter(|(_, other)| !code.commutes_with(other))
+                .fold(T::zero(), |acc, &(d, _)| acc + two * (c * d).abs())
+        })
+        .reduce(T::zero, |a, b| a + b)
+}
+
+// A / 12 + B / 24, see: Trotter::error_bound()
+fn second_order_norm<T>(terms: &[(T, Pauli)]) -> T
+where
+    T: Float + Send + Sync,
+{
+    let two = T::one() + T::one();
+    let four = two * two;
+
+    let masks: Vec<_> = terms.iter().map(|(_, code)| code.xz_masks()).collect();
+    let anticommute = |(x1, z1): (u64, u64), (x2, z2): (u64, u64)| {
+        ((x1 & z2) ^ (z1 & x2)).count_ones() % 2 == 1
+    };
+
+    // Terms are visited in reverse order.  Before visiting term i, later[j]
+    // is the sum of |c_k| over terms k > i anticommuting with term j.
+    let mut later = vec![T::zero(); terms.len()];
+    let (mut a, mut b) = (T::zero(), T::zero());
+    for i in (0..terms.len()).rev() {
+        let c = terms[i].0;
+        let (sum, sum_later) = later
+            .par_iter_mut()
+            .enumerate()
+            .filter(|(j, _)| anticommute(masks[i], masks[*j]))
+            .map(|(j, later_j)| {
+                let d = terms[j].0.abs();
+                let sums = if j > i {
+                    (d, d * *later_j)
+                } else {
+                    (T::zero(), T::zero())
+                };
+                *later_j = *later_j + c.abs();
+                sums
+            })
+            .reduce(
+                || (T::zero(), T::zero()),
+                |(a1, b1), (a2, b2)| (a1 + a2, b1 + b2),
+            );
+
+        // [H_j, H_i] = 2 c_j c_i P_j P_i, if the terms anticommute.
+        // P_j P_i anticommutes with P_k, only if P_k anticommutes with
+        // P_i or P_j.
+        a = a + four * c.abs() * (sum * sum + sum_later);
+        // [P_i, [P_i, P_j]] = 4 P_j, if the terms anticommute
+        b = b + four * c * c * sum;
+    }
+
+    a / T::from(12).expect("conversion from integer")
+        + b / T::from(24).expect("conversion from integer")
+}
+
+fn suzuki<T>(
+    terms: &[(T, Pauli)],
+    k: u32,
+    tau: T,
+    exps: &mut Vec<(T, Pauli)>,
+) where
+    T: Float,
+{
+    if k == 1 {
+        let half = tau / (T::one() + T::one());
+        exps.extend(terms.iter().map(|&(c, code)| (c * half, code)));
+        exps.extend(terms.iter().rev().map(|&(c, code)| (c * half, code)));
+        return;
+    }
+
+    let four = T::from(4).expect("conversion from integer");
+    let p = T::one()
+        / (four
+            - four.powf(
+                T::one() / T::from(2 * k - 1).expect("conversion from u32"),
+            ));
+    for _ in 0..2 {
+        suzuki(terms, k - 1, p * tau, exps);
+    }
+    suzuki(terms, k - 1, (T::one() - four * p) * tau, exps);
+    for _ in 0..2 {
+        suzuki(terms, k - 1, p * tau, exps);
+    }
+}
+
+/// Append a circuit implementing `exp(-i theta P)` to `circuit`.
+///
+/// The Pauli string is rotated to a product of Pauli Z operators, whose
+/// parity is computed with a ladder of CNOT gates onto the last qubit of
+/// the support of `code`, where the Z rotation is applied.  The identity
+/// is a global phase and yields no gates.
+///
+/// # Errors
+///
+/// Returns error if `theta` is not finite.  The circuit is left intact.
+///
+/// # Examples
+///
+/// ```rust
+/// # use f2q::{
+/// #     circuit::{Circuit, Gate},
+/// #     code::qubits::{Pauli, PauliOp},
+/// #     trotter::pauli_rotation,
+/// # };
+/// use PauliOp::{
+///     I,
+///     X,
+///     Z,
+/// };
+///
+/// let mut circuit = Circuit::new(3);
+/// pauli_rotation(&mut circuit, 0.25, Pauli::with_ops([X, I, Z])).unwrap();
+///
+/// assert_eq!(
+///     circuit.gates(),
+///     &[
+///         Gate::H(0),
+///         Gate::Cnot(0, 2),
+///         Gate::Rz(2, 0.5),
+///         Gate::Cnot(0, 2),
+///         Gate::H(0),
+///     ]
+/// );
+/// ```
+pub fn pauli_rotation(
+    circuit: &mut Circuit,
+    theta: f64,
+    code: Pauli,
+) -> Result<(), Error> {
+    let support: Vec<_> = (0..64)
+        .filter_map(|i| match code.pauli(i) {
+            Some(PauliOp::I) | None => None,
+            Some(op) => Some((i, op)),
+        })
+        .collect();
+    let Some(&(last, _)) = support.last() else {
+        return Ok(());
+    };
+    if !theta.is_finite() {
+        return Err(Error::Coefficient {
+            msg: format!("rotation angle must be finite: {theta}"),
+        });
+    }
+
+    for &(i, op) in &support {
+        match op {
+            PauliOp::X => circuit.push(Gate::H(i))?,
+            PauliOp::Y => {
+                circuit.push(Gate::Sdg(i))?;
+                circuit.push(Gate::H(i))?;
+            }
+            _ => (),
+        }
+    }
+    for w in support.windows(2) {
+        circuit.push(Gate::Cnot(w[0].0, w[1].0))?;
+    }
+    circuit.push(Gate::Rz(last, 2. * theta))?;
+    for w in support.windows(2).rev() {
+        circuit.push(Gate::Cnot(w[0].0, w[1].0))?;
+    }
+    for &(i, op) in support.iter().rev() {
+        match op {
+            PauliOp::X => circuit.push(Gate::H(i))?,
+            PauliOp::Y => {
+                circuit.push(Gate::H(i))?;
+                circuit.push(Gate::S(i))?;
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
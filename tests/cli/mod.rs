@@ -0,0 +1,112 @@
+use std::{
+    io::Write,
+    process::{
+        Command,
+        Output,
+        Stdio,
+    },
+};
+
+use f2q::{
+    code::qubits::{
+        Pauli,
+        PauliOp,
+    },
+    io::jsonl,
+    terms::PauliSum,
+};
+
+// Run the binary with `input` passed to STDIN.
+fn f2q(
+    args: &[&str],
+    input: &str,
+) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_f2q"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The command may exit before reading its input.
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+// Four spatial orbitals in the block ordering, but only indices up to 5
+// are used.
+const BLOCK_INPUT: &str = r#"
+{
+    "type": "sumrepr",
+    "encoding": "fermions",
+    "terms": [
+        { "code": [3, 3], "value": 1.0 },
+        { "code": [5, 5], "value": 0.5 }
+    ]
+}
+"#;
+
+#[test]
+fn convert_block_ordering_requires_num_orbitals() {
+    for stream in [false, true] {
+        let mut args = vec!["convert", "-m", "jordan-wigner"];
+        args.extend(["--input-ordering", "block"]);
+        if stream {
+            args.push("--stream");
+        }
+
+        let output = f2q(&args, BLOCK_INPUT);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(output.stdout.is_empty());
+    }
+}
+
+#[test]
+fn convert_block_ordering_num_orbitals() {
+    let output = f2q(
+        &[
+            "convert",
+            "-m",
+            "jordan-wigner",
+            "--input-ordering",
+            "block",
+            "--num-orbitals",
+            "4",
+        ],
+        BLOCK_INPUT,
+    );
+    assert!(output.status.success());
+
+    // Index 3 is spin-up orbital 3, index 5 is spin-down orbital 1.
+    let repr: PauliSum = serde_json::from_slice(&output.stdout).unwrap();
+    let mut z6 = Pauli::identity();
+    z6.set(6, PauliOp::Z);
+    let mut z3 = Pauli::identity();
+    z3.set(3, PauliOp::Z);
+    assert_eq!(repr.coeff(z6), Some(&-1.0));
+    assert_eq!(repr.coeff(z3), Some(&-0.5));
+    assert_eq!(repr.coeff(Pauli::identity()), Some(&1.5));
+}
+
+#[test]
+fn convert_stream_partial_size() {
+    let mut args = vec!["convert", "-m", "jordan-wigner"];
+    args.extend(["--input-ordering", "block", "--num-orbitals", "4"]);
+    let output = f2q(&args, BLOCK_INPUT);
+    assert!(output.status.success());
+    let expected: PauliSum = serde_json::from_slice(&output.stdout).unwrap();
+
+    args.extend(["--stream", "--partial-size", "1", "--output-format"]);
+    args.push("jsonl");
+    let output = f2q(&args, BLOCK_INPUT);
+    assert!(output.status.success());
+
+    // Partial sums of a single Pauli string are not merged.
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.lines().count() > expected.len() + 1);
+    let repr: PauliSum = jsonl::read_sum(text.as_bytes()).unwrap();
+    assert_eq!(repr.len(), expected.len());
+    for (coeff, &code) in expected.iter() {
+        assert_eq!(repr.coeff(code), Some(coeff));
+    }
+}
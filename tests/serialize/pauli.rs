@@ -145,3 +145,15 @@ fn deserialize_paulis() {
     let codes: Vec<Pauli> = serde_json::from_reader(reader).unwrap();
     assert_eq!(codes, paulis_compare());
 }
+
+#[test]
+fn deserialize_sparse() {
+    let code: Pauli = serde_json::from_str(r#""X0 Y3 Z17""#).unwrap();
+    assert_eq!(code.to_sparse(), "X0 Y3 Z17");
+
+    let code: Pauli = serde_json::from_str(r#""Z63""#).unwrap();
+    assert_eq!(code, Pauli::new((0, 3 << 62)));
+
+    let _ = serde_json::from_str::<Pauli>(r#""X64""#).unwrap_err();
+    let _ = serde_json::from_str::<Pauli>(r#""X0 X0""#).unwrap_err();
+}
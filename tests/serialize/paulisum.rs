@@ -1,6 +1,7 @@
 use f2q::{
     code::qubits::{
         Pauli,
+        PauliNotation,
         PauliOp,
     },
     terms::{
@@ -219,3 +220,68 @@ fn pauliisum_deserialize_03() {
         &0.3
     );
 }
+
+#[test]
+fn paulisum_serialize_sparse() {
+    let mut code = Pauli::identity();
+    code.set(3, PauliOp::X);
+    code.set(60, PauliOp::Y);
+    let repr = SumRepr::from([(0.5, code), (0.25, Pauli::identity())]);
+
+    let json = serde_json::to_value(repr.with_notation(PauliNotation::Sparse))
+        .unwrap();
+    let terms = json["terms"].as_array().unwrap();
+
+    assert_eq!(json["type"], "sumrepr");
+    assert_eq!(json["encoding"], "qubits");
+    assert!(
+        terms.contains(&serde_json::json!({"code": "X3 Y60", "value": 0.5}))
+    );
+    assert!(terms.contains(&serde_json::json!({"code": "I", "value": 0.25})));
+
+    let dense =
+        serde_json::to_value(repr.with_notation(PauliNotation::Dense)).unwrap();
+    assert_eq!(dense, serde_json::to_value(&repr).unwrap());
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn paulisum_deserialize_sparse() {
+    let data = r#"
+        {
+            "type": "sumrepr",
+            "encoding": "qubits",
+            "terms":  [
+                { "code": "X0 Z17", "value": 0.1 },
+                { "code": "Y1Y2", "value": 0.2 },
+                { "code": "IXYZ", "value": 0.3 }
+            ]
+        }
+    "#;
+    let repr: PauliSum = serde_json::from_str(data).unwrap();
+
+    let mut code = Pauli::with_ops([PauliOp::X]);
+    code.set(17, PauliOp::Z);
+    assert_eq!(repr.len(), 3);
+    assert_eq!(repr.coeff(code).unwrap(), &0.1);
+    assert_eq!(
+        repr.coeff(Pauli::with_ops([PauliOp::I, PauliOp::Y, PauliOp::Y]))
+            .unwrap(),
+        &0.2
+    );
+}
+
+#[test]
+fn paulisum_serde_sparse_yaml() {
+    let mut code = Pauli::identity();
+    code.set(0, PauliOp::Z);
+    code.set(63, PauliOp::X);
+    let repr = SumRepr::from([(1.5, code)]);
+
+    let yaml =
+        serde_yaml::to_string(&repr.with_notation(PauliNotation::Sparse))
+            .unwrap();
+    assert!(yaml.contains("Z0 X63"));
+    let back: PauliSum = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(back.coeff(code), Some(&1.5));
+}